@@ -2,7 +2,7 @@
 //!
 //! Run with: `cargo run --example batch_processing`
 
-use crate_checker::{BatchOperation, BatchTarget, CrateClient, Result};
+use crate_checker::{BatchOperation, BatchTarget, CrateClient, Result, UpdateKind};
 use std::collections::HashMap;
 use std::time::Instant;
 
@@ -140,7 +140,7 @@ async fn example_batch_operations(client: &CrateClient) -> Result<()> {
     ];
 
     let start = Instant::now();
-    let response = client.process_batch_operations(operations).await?;
+    let response = client.process_batch_operations(operations, 4).await?;
     let duration = start.elapsed();
 
     println!("   Batch Response:");
@@ -192,11 +192,8 @@ async fn example_check_dependencies(client: &CrateClient) -> Result<()> {
             Ok(latest) => {
                 print!("   {} v{} ", crate_name, required_version);
 
-                // Simple version comparison (just major version for this example)
-                let latest_major = latest.split('.').next().unwrap_or("0");
-                let required_major = required_version.split('.').next().unwrap_or("0");
-
-                if latest_major != required_major {
+                // `crate-checker outdated` does the same for a whole Cargo.toml
+                if UpdateKind::between(required_version, &latest) == Some(UpdateKind::Major) {
                     println!("⚠️  (latest: v{})", latest);
                     outdated.push((crate_name, latest));
                 } else {
@@ -31,7 +31,7 @@ async fn example_custom_timeout() -> Result<()> {
     // Create a client with a short timeout for demonstration
     let client = CrateClient::builder()
         .timeout(Duration::from_secs(5))
-        .user_agent("crate-checker-examples/1.0")
+        .user_agent("crate-checker-examples/1.0 (https://github.com/sandlbn/crate-checker)")
         .build()?;
 
     println!("   Testing with 5-second timeout...");
@@ -61,7 +61,7 @@ async fn example_custom_api_url() -> Result<()> {
     // - Corporate environments with custom registries
     let client = CrateClient::builder()
         .base_url("https://crates.io/api/v1") // Default, but could be custom
-        .user_agent("my-company-tool/2.0")
+        .user_agent("my-company-tool/2.0 (tools@example.com)")
         .timeout(Duration::from_secs(30))
         .build()?;
 
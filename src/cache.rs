@@ -0,0 +1,393 @@
+//! Response cache backends for the API server
+
+use crate::config::{CacheBackend, CacheConfig};
+use crate::error::{CrateCheckerError, Result};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Cached response entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub data: Value,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl CacheEntry {
+    /// Entry holding `data` for `ttl` from now
+    pub fn new(data: Value, ttl: Duration) -> Self {
+        let ttl = chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX);
+        Self {
+            data,
+            expires_at: Utc::now()
+                .checked_add_signed(ttl)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        }
+    }
+
+    /// Whether the entry is past its expiry time
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+
+    /// Time left before the entry expires
+    pub fn expires_in(&self) -> Duration {
+        (self.expires_at - Utc::now()).to_std().unwrap_or_default()
+    }
+}
+
+/// Key-value store for cached responses
+///
+/// `get` never returns an expired entry.
+pub trait Cache: Send + Sync {
+    /// Look up a live entry
+    fn get(&self, key: &str) -> Option<CacheEntry>;
+
+    /// Store `data` under `key` for `ttl`
+    fn set(&self, key: &str, data: Value, ttl: Duration);
+
+    /// Drop every expired entry, returning how many were removed
+    fn purge_expired(&self) -> usize;
+
+    /// Number of stored entries, including expired ones not yet purged
+    fn len(&self) -> usize;
+
+    /// Whether no entries are stored
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every entry whose key starts with `prefix`, returning how many were removed
+    fn remove_prefix(&self, prefix: &str) -> usize;
+
+    /// Drop every entry, returning how many were removed
+    fn clear(&self) -> usize {
+        self.remove_prefix("")
+    }
+
+    /// Approximate storage used by the stored entries, in bytes
+    fn approximate_size(&self) -> u64;
+}
+
+/// Purge expired entries from `cache` every `interval` until the task is aborted
+pub fn spawn_cleanup(cache: Arc<dyn Cache>, interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // The first tick completes immediately
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let removed = cache.purge_expired();
+            if removed > 0 {
+                debug!("Purged {} expired cache entries", removed);
+            }
+        }
+    })
+}
+
+/// Build the cache backend selected by the configuration
+pub fn from_config(config: &CacheConfig) -> Result<Arc<dyn Cache>> {
+    match config.backend {
+        CacheBackend::Memory => Ok(Arc::new(MemoryCache::new(config.max_entries))),
+        CacheBackend::Disk => {
+            let directory = config.directory.as_deref().ok_or_else(|| {
+                CrateCheckerError::validation("Cache directory is required for the disk backend")
+            })?;
+            Ok(Arc::new(DiskCache::new(directory)?))
+        }
+    }
+}
+
+/// In-process cache, lost on restart
+pub struct MemoryCache {
+    entries: DashMap<String, CacheEntry>,
+    max_entries: usize,
+}
+
+impl MemoryCache {
+    /// Cache that drops expired entries once it holds more than `max_entries`
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: DashMap::new(),
+            max_entries,
+        }
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let entry = self.entries.get(key)?.clone();
+        if entry.is_expired() {
+            self.entries.remove(key);
+            return None;
+        }
+        Some(entry)
+    }
+
+    fn set(&self, key: &str, data: Value, ttl: Duration) {
+        // Clean up expired entries periodically
+        if self.entries.len() > self.max_entries {
+            self.entries.retain(|_, entry| !entry.is_expired());
+        }
+
+        self.entries
+            .insert(key.to_string(), CacheEntry::new(data, ttl));
+    }
+
+    fn purge_expired(&self) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, entry| !entry.is_expired());
+        before.saturating_sub(self.entries.len())
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn remove_prefix(&self, prefix: &str) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|key, _| !key.starts_with(prefix));
+        before.saturating_sub(self.entries.len())
+    }
+
+    fn approximate_size(&self) -> u64 {
+        self.entries
+            .iter()
+            .map(|entry| (entry.key().len() + entry.value().data.to_string().len()) as u64)
+            .sum()
+    }
+}
+
+/// Cache persisted as one JSON file per entry, surviving restarts
+///
+/// Each file holds the [`CacheEntry`] with its expiry time, so a restarted server
+/// serves entries that are still live and ignores the rest.
+pub struct DiskCache {
+    directory: PathBuf,
+}
+
+impl DiskCache {
+    /// Cache rooted at `directory`, creating it if needed
+    pub fn new(directory: impl AsRef<Path>) -> Result<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        std::fs::create_dir_all(&directory)?;
+        Ok(Self { directory })
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.directory
+            .join(format!("{}.json", urlencoding::encode(key)))
+    }
+
+    /// Paths of all stored entry files
+    fn entry_paths(&self) -> Vec<PathBuf> {
+        std::fs::read_dir(&self.directory)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect()
+    }
+
+    /// Key stored in an entry file, decoded from its name
+    fn entry_key(path: &Path) -> Option<String> {
+        let stem = path.file_stem()?.to_str()?;
+        urlencoding::decode(stem).ok().map(|key| key.into_owned())
+    }
+}
+
+impl Cache for DiskCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let path = self.entry_path(key);
+        let content = std::fs::read(&path).ok()?;
+        let entry = match serde_json::from_slice::<CacheEntry>(&content) {
+            Ok(entry) if !entry.is_expired() => entry,
+            _ => {
+                // Expired or unreadable, remove it
+                let _ = std::fs::remove_file(&path);
+                return None;
+            }
+        };
+        Some(entry)
+    }
+
+    fn set(&self, key: &str, data: Value, ttl: Duration) {
+        let path = self.entry_path(key);
+        let result = serde_json::to_vec(&CacheEntry::new(data, ttl))
+            .map_err(CrateCheckerError::from)
+            .and_then(|bytes| {
+                // Write then rename so readers never see a partial file
+                let tmp = path.with_extension("json.tmp");
+                std::fs::write(&tmp, bytes)?;
+                std::fs::rename(&tmp, &path)?;
+                Ok(())
+            });
+        if let Err(e) = result {
+            warn!("Failed to write cache entry {}: {}", path.display(), e);
+        }
+    }
+
+    fn purge_expired(&self) -> usize {
+        self.entry_paths()
+            .into_iter()
+            .filter(|path| {
+                let live = std::fs::read(path)
+                    .ok()
+                    .and_then(|content| serde_json::from_slice::<CacheEntry>(&content).ok())
+                    .is_some_and(|entry| !entry.is_expired());
+                !live && std::fs::remove_file(path).is_ok()
+            })
+            .count()
+    }
+
+    fn len(&self) -> usize {
+        self.entry_paths().len()
+    }
+
+    fn remove_prefix(&self, prefix: &str) -> usize {
+        self.entry_paths()
+            .into_iter()
+            .filter(|path| {
+                Self::entry_key(path).is_some_and(|key| key.starts_with(prefix))
+                    && std::fs::remove_file(path).is_ok()
+            })
+            .count()
+    }
+
+    fn approximate_size(&self) -> u64 {
+        self.entry_paths()
+            .iter()
+            .filter_map(|path| std::fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_disk_cache_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let cache = DiskCache::new(dir.path()).unwrap();
+
+        assert!(cache.get("crate:serde").is_none());
+        cache.set(
+            "crate:serde",
+            json!({"name": "serde"}),
+            Duration::from_secs(60),
+        );
+
+        let entry = cache.get("crate:serde").unwrap();
+        assert_eq!(entry.data, json!({"name": "serde"}));
+        assert!(entry.expires_in() > Duration::from_secs(50));
+
+        // A new cache over the same directory sees the entry (warm start)
+        let restarted = DiskCache::new(dir.path()).unwrap();
+        assert_eq!(
+            restarted.get("crate:serde").unwrap().data,
+            json!({"name": "serde"})
+        );
+    }
+
+    #[test]
+    fn test_disk_cache_expiry() {
+        let dir = TempDir::new().unwrap();
+        let cache = DiskCache::new(dir.path()).unwrap();
+
+        cache.set("crate:tokio", json!({"name": "tokio"}), Duration::ZERO);
+        let path = cache.entry_path("crate:tokio");
+        assert!(path.exists());
+
+        assert!(cache.get("crate:tokio").is_none());
+        assert!(!path.exists(), "expired entry should be removed");
+
+        // An entry written by an earlier run that has since expired is ignored
+        let stale = CacheEntry {
+            data: json!({"name": "stale"}),
+            expires_at: Utc::now() - chrono::Duration::seconds(1),
+        };
+        std::fs::write(
+            cache.entry_path("crate:stale"),
+            serde_json::to_vec(&stale).unwrap(),
+        )
+        .unwrap();
+        assert!(cache.get("crate:stale").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_task_purges_expired_entries() {
+        let cache: Arc<dyn Cache> = Arc::new(MemoryCache::new(1000));
+        for i in 0..5 {
+            cache.set(&format!("short:{i}"), json!(i), Duration::from_millis(20));
+        }
+        cache.set("long", json!("kept"), Duration::from_secs(60));
+        assert_eq!(cache.len(), 6);
+
+        let cleanup = spawn_cleanup(cache.clone(), Duration::from_millis(50));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        cleanup.abort();
+
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get("long").is_some());
+    }
+
+    #[test]
+    fn test_disk_cache_purge_expired() {
+        let dir = TempDir::new().unwrap();
+        let cache = DiskCache::new(dir.path()).unwrap();
+        cache.set("expired", json!(1), Duration::ZERO);
+        cache.set("live", json!(2), Duration::from_secs(60));
+        assert_eq!(cache.len(), 2);
+
+        assert_eq!(cache.purge_expired(), 1);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get("live").is_some());
+    }
+
+    #[test]
+    fn test_remove_prefix_and_clear() {
+        let dir = TempDir::new().unwrap();
+        let backends: Vec<Box<dyn Cache>> = vec![
+            Box::new(MemoryCache::new(10)),
+            Box::new(DiskCache::new(dir.path()).unwrap()),
+        ];
+        for cache in backends {
+            let ttl = Duration::from_secs(60);
+            cache.set("crate:serde", json!({"name": "serde"}), ttl);
+            cache.set("crate:serde:1.0.0", json!({"num": "1.0.0"}), ttl);
+            cache.set("crate:tokio", json!({"name": "tokio"}), ttl);
+            cache.set("versions:serde", json!([]), ttl);
+            assert!(cache.approximate_size() > 0);
+
+            assert_eq!(cache.remove_prefix("crate:serde"), 2);
+            assert!(cache.get("crate:serde").is_none());
+            assert!(cache.get("crate:tokio").is_some());
+            assert_eq!(cache.remove_prefix("missing:"), 0);
+
+            assert_eq!(cache.clear(), 2);
+            assert!(cache.is_empty());
+            assert_eq!(cache.approximate_size(), 0);
+        }
+    }
+
+    #[test]
+    fn test_memory_cache_expiry() {
+        let cache = MemoryCache::new(10);
+        cache.set("live", json!(1), Duration::from_secs(60));
+        cache.set("expired", json!(2), Duration::ZERO);
+
+        assert_eq!(cache.get("live").unwrap().data, json!(1));
+        assert!(cache.get("expired").is_none());
+    }
+}
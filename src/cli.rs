@@ -1,27 +1,37 @@
 //! Command-line interface for the crate checker application
 
 use crate::client::CrateClient;
-use crate::config::{AppConfig, EnvironmentConfig};
+use crate::config::{AppConfig, ConfigFormat, EnvironmentConfig, LoggingConfig};
 use crate::error::Result;
+use crate::sarif::SarifLog;
 use crate::server::start_server;
 use crate::types::*;
 use crate::utils::{
-    create_example_batch_inputs, format_download_count, parse_json_file, parse_json_input,
-    parse_timeout, truncate_text, validate_batch_input,
+    create_example_batch_inputs, format_download_count, format_duration, format_file_size,
+    generate_trace_id, github_annotation, is_latest_keyword, parse_cargo_lock, parse_crate_list,
+    parse_json_file, parse_json_input, parse_manifest_dependencies,
+    parse_manifest_registry_dependencies, parse_timeout, progress_indicator, resolve_version_spec,
+    sort_versions_desc, sparkline, traceparent_header, truncate_text, validate_batch_input,
+    versions_between,
 };
-use crate::DEFAULT_SERVER_PORT;
-use clap::{Parser, Subcommand, ValueEnum};
+use crate::{CRATES_IO_SOURCE, DEFAULT_SERVER_PORT};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use futures::stream::{self, StreamExt};
 use serde::Serialize;
 use serde_json;
+use std::collections::{HashMap, HashSet};
+use std::io::{IsTerminal, Write};
 use std::path::PathBuf;
-use tabled::{Table, Tabled};
+use std::time::Duration;
+use tabled::{settings::Style, Table, Tabled};
 use tracing::{error, info, warn};
 
 /// Crate Checker - A comprehensive Rust crate information retrieval tool
 #[derive(Parser)]
 #[command(
     name = "crate-checker",
-    version = "1.0.0",
+    version = crate::VERSION,
     about = "Check crate existence, versions, dependencies and more from crates.io",
     long_about = "A comprehensive tool for retrieving information about Rust crates from crates.io. 
 Supports checking crate existence, getting version information, searching crates, 
@@ -37,7 +47,7 @@ pub struct Cli {
     pub verbose: bool,
 
     /// Enable quiet mode (only errors)
-    #[arg(short, long, global = true)]
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
     pub quiet: bool,
 
     /// Configuration file path
@@ -52,6 +62,38 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub api_url: Option<String>,
 
+    /// Answer from the local cargo registry index instead of crates.io (`check`,
+    /// `check-multiple`, `batch` crate lists and latest-version lookups only)
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Print exact download counts in tables instead of abbreviating them (e.g. 1.5M)
+    #[arg(long, global = true)]
+    pub raw_numbers: bool,
+
+    /// Include pre-releases in version listings, `deps` version resolution and
+    /// latest-version selection
+    #[arg(long, global = true)]
+    pub include_prerelease: bool,
+
+    /// Trace id propagated as `X-Trace-Id` (and `traceparent` when W3C-shaped) on requests
+    #[arg(long, global = true, value_name = "ID")]
+    pub trace_id: Option<String>,
+
+    /// Propagate a freshly generated trace id on requests
+    #[arg(long, global = true, conflicts_with = "trace_id")]
+    pub trace: bool,
+
+    /// Emit GitHub Actions `::error::`/`::warning::` annotations for problems found on
+    /// stderr, leaving stdout to the formatted output (enabled automatically when
+    /// GITHUB_ACTIONS=true)
+    #[arg(long, global = true, env = "GITHUB_ACTIONS")]
+    pub github_annotations: bool,
+
+    /// When to color output: `auto` colors terminals unless NO_COLOR is set
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    pub color: ColorChoice,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -67,11 +109,25 @@ pub enum Commands {
         /// Specific version to check (optional)
         #[arg(short, long)]
         version: Option<String>,
+
+        /// Report whether the crate exists, is yanked or partially yanked, listing yanked versions
+        #[arg(long, conflicts_with = "version")]
+        status: bool,
+
+        /// Resolve "latest" to the newest stable release, never a pre-release
+        #[arg(long, conflicts_with = "status")]
+        stable: bool,
+    },
+
+    /// Check whether a name is free for publishing a new crate
+    Available {
+        /// Name to check (`foo-bar` and `foo_bar` count as the same name)
+        crate_name: String,
     },
 
     /// Check multiple crates at once with merged output
     CheckMultiple {
-        /// Names of the crates to check (space-separated)
+        /// Names of the crates to check (space-separated; `-` reads names from stdin)
         crate_names: Vec<String>,
 
         /// Show only summary (don't list individual results)
@@ -81,12 +137,25 @@ pub enum Commands {
         /// Exit with error code if any crate doesn't exist
         #[arg(long)]
         fail_on_missing: bool,
+
+        /// Only check existence (one HEAD request per crate), skipping the version lookup
+        #[arg(long)]
+        fast: bool,
+
+        /// Print only a one-line summary such as "12/15 exist (3 missing: a, b, c)"
+        #[arg(long)]
+        compact_summary: bool,
+
+        /// Check a crate named more than once every time it appears, instead of once
+        #[arg(long)]
+        allow_duplicates: bool,
     },
 
-    /// Get detailed information about a crate
+    /// Get detailed information about one or more crates
     Info {
-        /// Name of the crate
-        crate_name: String,
+        /// Names of the crates (several are fetched concurrently into one table)
+        #[arg(required = true)]
+        crate_names: Vec<String>,
 
         /// Include dependency information
         #[arg(short, long)]
@@ -109,6 +178,14 @@ pub enum Commands {
         /// Limit number of versions to show
         #[arg(short, long)]
         limit: Option<usize>,
+
+        /// Show the metadata of a single version instead of listing all of them
+        #[arg(short, long, conflicts_with_all = ["no_yanked", "limit"])]
+        version: Option<String>,
+
+        /// Show only stable (non-prerelease, non-yanked) versions, newest first
+        #[arg(long, conflicts_with = "version")]
+        stable: bool,
     },
 
     /// Search for crates by name or keywords
@@ -125,6 +202,26 @@ pub enum Commands {
         exact: bool,
     },
 
+    /// List the most downloaded crates in a category
+    Category {
+        /// Category slug (e.g. command-line-utilities)
+        name: String,
+
+        /// Maximum number of results
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+    },
+
+    /// List the most downloaded crates tagged with a keyword
+    Keyword {
+        /// Keyword (e.g. async)
+        name: String,
+
+        /// Maximum number of results
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+    },
+
     /// Show dependencies for a crate version
     Deps {
         /// Name of the crate
@@ -139,6 +236,44 @@ pub enum Commands {
         runtime_only: bool,
     },
 
+    /// List the feature flags of a crate version
+    Features {
+        /// Name of the crate
+        crate_name: String,
+
+        /// Version (defaults to latest)
+        #[arg(short, long, default_value = "latest")]
+        version: String,
+    },
+
+    /// Show the transitive dependency tree of a crate version
+    Tree {
+        /// Name of the crate
+        crate_name: String,
+
+        /// Version or requirement (defaults to latest)
+        #[arg(short, long, default_value = "latest")]
+        version: String,
+
+        /// Number of dependency levels to resolve
+        #[arg(short, long, default_value = "3")]
+        depth: usize,
+
+        /// Follow only runtime (normal) dependencies, pruning build dependencies
+        #[arg(long)]
+        runtime_only: bool,
+    },
+
+    /// Show crates that depend on a crate
+    Rdeps {
+        /// Name of the crate
+        crate_name: String,
+
+        /// Maximum number of dependents to list
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+    },
+
     /// Show download statistics for a crate
     Stats {
         /// Name of the crate
@@ -147,6 +282,112 @@ pub enum Commands {
         /// Show version-specific stats
         #[arg(short, long)]
         versions: bool,
+
+        /// Show the daily download history
+        #[arg(long)]
+        history: bool,
+    },
+
+    /// Estimate how widely used a crate is (download percentile and label)
+    Popularity {
+        /// Name of the crate
+        crate_name: String,
+    },
+
+    /// Produce an aggregate report (downloads, age, licenses, yank rate) for several crates
+    Report {
+        /// Names of the crates to include (space-separated)
+        crate_names: Vec<String>,
+
+        /// File with newline-separated crate names
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+
+    /// Find dependencies shared by several crates and flag conflicting requirements
+    Analyze {
+        /// Names of the crates to analyze (space-separated)
+        #[arg(required = true)]
+        crate_names: Vec<String>,
+    },
+
+    /// Resolve latest versions and print Cargo.lock-style [[package]] entries
+    Lock {
+        /// Names of the crates to lock (space-separated)
+        #[arg(required = true)]
+        crate_names: Vec<String>,
+    },
+
+    /// Check the crates.io packages pinned in a Cargo.lock for yanked or unpublished versions
+    CheckLock {
+        /// Path to the lockfile
+        #[arg(default_value = "Cargo.lock")]
+        lockfile: PathBuf,
+
+        /// Emit a SARIF 2.1.0 report instead of the selected output format
+        #[arg(long)]
+        sarif: bool,
+    },
+
+    /// Measure request throughput and latency against the API
+    Bench {
+        /// Number of existence checks to issue
+        #[arg(long, default_value_t = 50)]
+        crates: usize,
+
+        /// Requests kept in flight (defaults to the configured max_concurrent)
+        #[arg(long)]
+        concurrency: Option<usize>,
+
+        /// Run for this long instead of a fixed count (e.g. 10s, 1m)
+        #[arg(long, conflicts_with = "crates")]
+        duration: Option<String>,
+    },
+
+    /// Show added, removed and bumped dependencies between two Cargo.toml files
+    ManifestDiff {
+        /// Manifest before the change
+        old: PathBuf,
+
+        /// Manifest after the change
+        new: PathBuf,
+    },
+
+    /// Show manifest dependencies with a newer release on crates.io
+    Outdated {
+        /// Path to the Cargo.toml to check
+        #[arg(short, long, default_value = "Cargo.toml")]
+        manifest: PathBuf,
+
+        /// Exit with error if any dependency is behind its latest release
+        #[arg(long)]
+        fail_on_outdated: bool,
+    },
+
+    /// Check that every crates.io dependency of a Cargo.toml exists, catching typos
+    Validate {
+        /// Path to the Cargo.toml to validate
+        #[arg(short, long, default_value = "Cargo.toml")]
+        manifest: PathBuf,
+
+        /// Exit with error if any dependency doesn't exist or could not be checked
+        #[arg(long)]
+        fail_on_missing: bool,
+    },
+
+    /// Poll crates and report whenever their newest version changes
+    Watch {
+        /// Names of the crates to watch (space-separated)
+        #[arg(required = true)]
+        crate_names: Vec<String>,
+
+        /// Time between polls (e.g. 30s, 5m, 1h)
+        #[arg(short, long, default_value = "60s")]
+        interval: String,
+
+        /// Poll once, print the current versions and exit (for cron usage)
+        #[arg(long)]
+        once: bool,
     },
 
     /// Process multiple crates at once
@@ -155,13 +396,21 @@ pub enum Commands {
         #[arg(long, long, conflicts_with = "file")]
         json: Option<String>,
 
-        /// JSON file with batch input
+        /// JSON file with batch input (`-` reads JSON or newline-separated crate names from stdin)
         #[arg(long, long, conflicts_with = "json")]
         file: Option<PathBuf>,
 
         /// Process requests in parallel
         #[arg(short, long)]
         parallel: bool,
+
+        /// Resolve version requirements (e.g. "^1") to concrete versions before processing
+        #[arg(long)]
+        resolve: bool,
+
+        /// With --resolve, print the resolution plan without processing it
+        #[arg(long, requires = "resolve")]
+        dry_run: bool,
     },
 
     /// Start HTTP API server
@@ -183,15 +432,113 @@ pub enum Commands {
         config: Option<PathBuf>,
     },
 
+    /// Check that a running API server is healthy
+    Health {
+        /// Base URL of the server
+        #[arg(long, default_value = "http://localhost:3000")]
+        url: String,
+    },
+
     /// Generate sample configuration file
+    ///
+    /// The sample is in the `--format` given, otherwise TOML unless the output file
+    /// ends in `.json`, `.yaml` or `.yml`.
+    // clap shares global argument values by id across subcommands, so the global
+    // `--format` is shadowed by a hidden argument with its id to free `--format` here
+    #[command(arg(
+        clap::Arg::new("format")
+            .long("output-format")
+            .hide(true)
+            .value_parser(clap::value_parser!(OutputFormat))
+    ))]
     Config {
         /// Output file (prints to stdout if not specified)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Format of the sample (defaults to the output file's extension, then TOML)
+        #[arg(
+            id = "config_format",
+            short = 'f',
+            long = "format",
+            value_name = "FORMAT",
+            value_enum
+        )]
+        format: Option<ConfigFormat>,
     },
 
     /// Show examples of JSON batch input formats
     Examples,
+
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    /// Print the JSON Schema of the JSON output types
+    ///
+    /// Prints one schema when a type is given, otherwise an object of all of them keyed
+    /// by type name. Schemas are always JSON, whatever `--format` says.
+    Schema {
+        /// Output type to describe
+        #[arg(value_enum)]
+        kind: Option<SchemaKind>,
+    },
+}
+
+/// Output types with a published JSON Schema
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchemaKind {
+    CrateInfo,
+    CrateCheckResult,
+    BatchResult,
+    CrateSearchResult,
+}
+
+impl SchemaKind {
+    /// Name of the Rust type, used as the schema title
+    fn name(&self) -> &'static str {
+        match self {
+            Self::CrateInfo => "CrateInfo",
+            Self::CrateCheckResult => "CrateCheckResult",
+            Self::BatchResult => "BatchResult",
+            Self::CrateSearchResult => "CrateSearchResult",
+        }
+    }
+
+    fn schema(&self) -> schemars::schema::RootSchema {
+        match self {
+            Self::CrateInfo => schemars::schema_for!(CrateInfo),
+            Self::CrateCheckResult => schemars::schema_for!(CrateCheckResult),
+            Self::BatchResult => schemars::schema_for!(BatchResult),
+            Self::CrateSearchResult => schemars::schema_for!(CrateSearchResult),
+        }
+    }
+}
+
+/// When to emit ANSI colors
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color terminals, unless the NO_COLOR environment variable is set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color a stream, given whether it is a terminal
+    pub fn enabled(self, is_terminal: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => {
+                is_terminal && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+            }
+        }
+    }
 }
 
 /// Output format options
@@ -208,6 +555,11 @@ pub enum OutputFormat {
     Compact,
     /// CSV format
     Csv,
+    /// GitHub-flavored Markdown tables (fenced JSON for non-tabular output)
+    Markdown,
+    /// JSON Lines: one JSON object per line for list results
+    #[value(alias = "ndjson")]
+    Jsonl,
 }
 
 /// Tabled display for crate information
@@ -219,6 +571,8 @@ struct CrateInfoDisplay {
     version: String,
     #[tabled(rename = "Downloads")]
     downloads: String,
+    #[tabled(rename = "MSRV")]
+    msrv: String,
     #[tabled(rename = "Description")]
     description: String,
 }
@@ -249,6 +603,39 @@ struct SearchResultDisplay {
     description: String,
 }
 
+/// Tabled display for outdated dependencies
+#[derive(Tabled)]
+struct OutdatedDisplay {
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Required")]
+    requirement: String,
+    #[tabled(rename = "Latest")]
+    latest: String,
+    #[tabled(rename = "Update")]
+    update: String,
+}
+
+/// Tabled display for reverse dependencies
+#[derive(Tabled)]
+struct ReverseDependencyDisplay {
+    #[tabled(rename = "Dependent")]
+    name: String,
+    #[tabled(rename = "Version")]
+    version: String,
+    #[tabled(rename = "Downloads")]
+    downloads: String,
+}
+
+/// Tabled display for feature flags
+#[derive(Tabled)]
+struct FeatureDisplay {
+    #[tabled(rename = "Feature")]
+    name: String,
+    #[tabled(rename = "Enables")]
+    enables: String,
+}
+
 /// Tabled display for dependencies
 #[derive(Tabled)]
 struct DependencyDisplay {
@@ -273,23 +660,212 @@ struct MultiCheckDisplay {
     version: String,
 }
 
+/// Tabled display for ecosystem report entries
+#[derive(Tabled)]
+struct ReportEntryDisplay {
+    #[tabled(rename = "Crate")]
+    name: String,
+    #[tabled(rename = "Version")]
+    version: String,
+    #[tabled(rename = "Downloads")]
+    downloads: String,
+    #[tabled(rename = "License")]
+    license: String,
+    #[tabled(rename = "Age (days)")]
+    age_days: i64,
+    #[tabled(rename = "Yanked")]
+    yanked: String,
+}
+
+/// Tabled display for shared dependencies of the analyze command
+#[derive(Tabled)]
+struct SharedDependencyDisplay {
+    #[tabled(rename = "Dependency")]
+    name: String,
+    #[tabled(rename = "Required By")]
+    required_by: String,
+    #[tabled(rename = "Conflict")]
+    conflict: String,
+}
+
+/// Tabled display for lockfile findings
+#[derive(Tabled)]
+struct LockFindingDisplay {
+    #[tabled(rename = "Crate")]
+    name: String,
+    #[tabled(rename = "Version")]
+    version: String,
+    #[tabled(rename = "Problem")]
+    problem: String,
+}
+
+/// Upload size limit crates.io applies to crates without a custom `max_upload_size`
+const CRATES_IO_DEFAULT_MAX_UPLOAD_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Fixed sample of crate names cycled through by the bench command
+const BENCH_CRATES: &[&str] = &[
+    "serde",
+    "tokio",
+    "rand",
+    "syn",
+    "quote",
+    "regex",
+    "clap",
+    "anyhow",
+    "thiserror",
+    "log",
+];
+
+/// A `[[package]]` entry of a Cargo.lock fragment
+#[derive(Serialize)]
+struct LockPackage {
+    name: String,
+    version: String,
+    source: &'static str,
+}
+
+/// Cargo.lock fragment produced by the lock command
+#[derive(Serialize)]
+struct LockFragment {
+    package: Vec<LockPackage>,
+}
+
 /// Summary for multi-check results
 #[derive(Serialize)]
 struct MultiCheckSummary {
     total_checked: usize,
     existing: usize,
     missing: usize,
+    /// Crates whose check failed, so their existence is unknown
+    errored: usize,
     existing_crates: Vec<String>,
     missing_crates: Vec<String>,
+    errored_crates: Vec<String>,
+}
+
+impl MultiCheckSummary {
+    /// One-line summary for badges and chat, e.g. "12/15 exist (3 missing: a, b, c)"
+    fn compact_line(&self) -> String {
+        let mut line = format!("{}/{} exist", self.existing, self.total_checked);
+        if !self.missing_crates.is_empty() {
+            line.push_str(&format!(
+                " ({} missing: {})",
+                self.missing,
+                self.missing_crates.join(", ")
+            ));
+        }
+        if !self.errored_crates.is_empty() {
+            line.push_str(&format!(
+                " ({} errored: {})",
+                self.errored,
+                self.errored_crates.join(", ")
+            ));
+        }
+        line
+    }
+}
+
+/// Pipe-delimited one-line outcome for CI logs
+///
+/// `entries` are `(label, status)` pairs, e.g. `("serde=1.0.210", "OK")`; the line ends
+/// with the totals: "serde=1.0.210 OK | nope MISSING | total=2 ok=1 fail=1".
+fn compact_status_line(entries: &[(String, &str)], total: usize, ok: usize) -> String {
+    let mut parts: Vec<String> = entries
+        .iter()
+        .map(|(label, status)| format!("{} {}", label, status))
+        .collect();
+    parts.push(format!("total={} ok={} fail={}", total, ok, total - ok));
+    parts.join(" | ")
+}
+
+/// Compact line for batch results; a check is OK when the crate and any requested version exist
+fn batch_compact_line(results: &[CrateCheckResult]) -> String {
+    let entries: Vec<(String, &str)> = results
+        .iter()
+        .map(|result| {
+            let version = result
+                .resolved_version
+                .as_ref()
+                .or(result.latest_version.as_ref());
+            if result.outcome == CheckOutcome::Error {
+                (result.crate_name.clone(), "ERROR")
+            } else if result.outcome == CheckOutcome::Missing {
+                (result.crate_name.clone(), "MISSING")
+            } else if result.version_exists == Some(false) {
+                let requested = result.requested_version.as_deref().unwrap_or_default();
+                (format!("{}@{}", result.crate_name, requested), "MISSING")
+            } else if let Some(version) = version {
+                (format!("{}={}", result.crate_name, version), "OK")
+            } else {
+                (result.crate_name.clone(), "OK")
+            }
+        })
+        .collect();
+    let ok = entries.iter().filter(|(_, status)| *status == "OK").count();
+    compact_status_line(&entries, results.len(), ok)
+}
+
+/// Filters of the versions command
+struct VersionFilter {
+    no_yanked: bool,
+    stable: bool,
+    limit: Option<usize>,
+}
+
+/// Flags of the check-multiple command
+struct MultiCheckOptions {
+    summary_only: bool,
+    fail_on_missing: bool,
+    fast: bool,
+    compact_summary: bool,
+    allow_duplicates: bool,
+    github_annotations: bool,
+    color: bool,
+}
+
+/// How table output writes download counts
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum NumberFormat {
+    /// Abbreviated, e.g. `1.5M`
+    #[default]
+    Humanized,
+    /// Exact integers, for scripts that parse the table
+    Raw,
+}
+
+impl NumberFormat {
+    fn downloads(self, count: u64) -> String {
+        match self {
+            Self::Humanized => format_download_count(count),
+            Self::Raw => count.to_string(),
+        }
+    }
+}
+
+/// Flags of the batch command
+struct BatchCommandOptions {
+    parallel: bool,
+    resolve: bool,
+    dry_run: bool,
+    /// Draw a progress bar on stderr while a parallel version map is checked
+    progress: bool,
+}
+
+/// Tabled display for a batch resolution plan
+#[derive(Tabled)]
+struct VersionPlanDisplay {
+    #[tabled(rename = "Crate")]
+    name: String,
+    #[tabled(rename = "Requested")]
+    requested: String,
+    #[tabled(rename = "Resolved")]
+    resolved: String,
 }
 
 /// Run the CLI application
 pub async fn run() -> Result<()> {
     let cli = Cli::parse();
 
-    // Initialize logging
-    init_logging(cli.verbose, cli.quiet, &cli.format);
-
     // Load configuration
     let config = if let Some(config_path) = &cli.config {
         AppConfig::load_from_file(Some(config_path))?
@@ -302,6 +878,16 @@ pub async fn run() -> Result<()> {
     let mut final_config = config;
     env_config.apply_overrides(&mut final_config);
 
+    // Initialize logging; the server logs as configured in its own config file, if given
+    let logging = match &cli.command {
+        Commands::Server {
+            config: Some(config_path),
+            ..
+        } => AppConfig::load_from_file(Some(config_path))?.logging,
+        _ => final_config.logging.clone(),
+    };
+    let _log_guard = init_logging(&cli, &logging)?;
+
     // Create client with configuration
     let mut client_builder = CrateClient::builder();
 
@@ -311,59 +897,163 @@ pub async fn run() -> Result<()> {
         client_builder = client_builder.base_url(&final_config.crates_io.api_url);
     }
 
-    if let Some(timeout_str) = &cli.timeout {
-        let timeout = parse_timeout(timeout_str)?;
-        client_builder = client_builder.timeout(timeout);
-    } else {
-        client_builder = client_builder.timeout(std::time::Duration::from_secs(
-            final_config.crates_io.timeout_seconds,
-        ));
+    let timeout = resolve_timeout(&cli, &final_config)?;
+    client_builder = client_builder.timeout(timeout);
+
+    let trace_id = match (&cli.trace_id, cli.trace) {
+        (Some(trace_id), _) => Some(trace_id.clone()),
+        (None, true) => Some(generate_trace_id()),
+        (None, false) => None,
+    };
+    if let Some(trace_id) = trace_id {
+        info!("Using trace id: {}", trace_id);
+        if let Some(traceparent) = traceparent_header(&trace_id) {
+            client_builder = client_builder.header("traceparent", traceparent);
+        }
+        client_builder = client_builder.header("X-Trace-Id", trace_id);
+    }
+
+    if let Some(token) = &final_config.crates_io.registry_token {
+        client_builder = client_builder.registry_token(token);
+    }
+    if let Some(token) = &final_config.crates_io.token {
+        client_builder = client_builder.bearer_token(token);
+    }
+
+    if final_config.rate_limiting.enabled {
+        client_builder = client_builder.rate_limit(
+            final_config.rate_limiting.requests_per_minute,
+            final_config.rate_limiting.burst_size,
+        );
     }
 
-    let client = client_builder.build()?;
+    let client = client_builder
+        .offline(cli.offline)
+        .include_prerelease(cli.include_prerelease)
+        .max_concurrent(final_config.crates_io.max_concurrent)
+        .retry_attempts(final_config.crates_io.retry_attempts)
+        .slow_request_threshold(Duration::from_millis(
+            final_config.crates_io.slow_request_threshold_ms,
+        ))
+        .build()?;
 
     // Execute command
+    let mut out = std::io::stdout();
+    let numbers = if cli.raw_numbers {
+        NumberFormat::Raw
+    } else {
+        NumberFormat::Humanized
+    };
     match cli.command {
         Commands::Check {
             crate_name,
             version,
+            status,
+            stable,
         } => {
-            handle_check(client, &crate_name, version.as_deref(), &cli.format).await?;
+            if status {
+                handle_check_status(client, &crate_name, &cli.format, &mut out).await?;
+            } else {
+                handle_check(
+                    client,
+                    &crate_name,
+                    version.as_deref(),
+                    stable,
+                    &cli.format,
+                    &mut out,
+                )
+                .await?;
+            }
         }
         Commands::CheckMultiple {
             crate_names,
             summary_only,
             fail_on_missing,
+            fast,
+            compact_summary,
+            allow_duplicates,
         } => {
-            handle_check_multiple(
-                client,
-                crate_names,
+            let options = MultiCheckOptions {
                 summary_only,
                 fail_on_missing,
-                &cli.format,
-            )
-            .await?;
+                fast,
+                compact_summary,
+                allow_duplicates,
+                github_annotations: cli.github_annotations,
+                color: cli.color.enabled(std::io::stdout().is_terminal()),
+            };
+            handle_check_multiple(client, crate_names, &options, &cli.format, &mut out).await?;
         }
         Commands::Info {
-            crate_name,
+            crate_names,
             deps,
             stats,
         } => {
-            handle_info(client, &crate_name, deps, stats, &cli.format).await?;
+            if let [crate_name] = crate_names.as_slice() {
+                handle_info(
+                    client,
+                    crate_name,
+                    deps,
+                    stats,
+                    numbers,
+                    &cli.format,
+                    &mut out,
+                )
+                .await?;
+            } else {
+                handle_info_multiple(
+                    client,
+                    crate_names,
+                    deps,
+                    stats,
+                    numbers,
+                    &cli.format,
+                    &mut out,
+                )
+                .await?;
+            }
         }
         Commands::Versions {
             crate_name,
             no_yanked,
             limit,
+            version,
+            stable,
         } => {
-            handle_versions(client, &crate_name, no_yanked, limit, &cli.format).await?;
+            if let Some(version) = version {
+                handle_version_info(
+                    client,
+                    &crate_name,
+                    &version,
+                    numbers,
+                    &cli.format,
+                    &mut out,
+                )
+                .await?;
+            } else {
+                let filter = VersionFilter {
+                    no_yanked,
+                    stable,
+                    limit,
+                };
+                handle_versions(client, &crate_name, &filter, numbers, &cli.format, &mut out)
+                    .await?;
+            }
         }
         Commands::Search {
             query,
             limit,
             exact,
         } => {
-            handle_search(client, &query, limit, exact, &cli.format).await?;
+            handle_search(client, &query, limit, exact, numbers, &cli.format, &mut out).await?;
+        }
+        Commands::Category { name, limit } => {
+            let results = client.list_category(&name, Some(limit)).await?;
+            write_search_results(results, numbers, &cli.format, &mut out)?;
+        }
+        Commands::Keyword { name, limit } => {
+            let results = client.list_keyword(&name, Some(limit)).await?;
+            write_search_results(results, numbers, &cli.format, &mut out)?;
         }
         Commands::Deps {
             crate_name,
@@ -376,26 +1066,140 @@ pub async fn run() -> Result<()> {
                 version.as_deref(),
                 runtime_only,
                 &cli.format,
+                &mut out,
             )
             .await?;
         }
-        Commands::Stats {
+        Commands::Available { crate_name } => {
+            handle_available(client, &crate_name, &cli.format, &mut out).await?;
+        }
+        Commands::Features {
             crate_name,
-            versions,
+            version,
         } => {
-            handle_stats(client, &crate_name, versions, &cli.format).await?;
+            handle_features(client, &crate_name, &version, &cli.format, &mut out).await?;
         }
-        Commands::Batch {
-            json,
-            file,
-            parallel,
+        Commands::Tree {
+            crate_name,
+            version,
+            depth,
+            runtime_only,
+        } => {
+            handle_tree(
+                client,
+                &crate_name,
+                &version,
+                depth,
+                runtime_only,
+                &cli.format,
+                &mut out,
+            )
+            .await?;
+        }
+        Commands::Rdeps { crate_name, limit } => {
+            handle_rdeps(client, &crate_name, limit, numbers, &cli.format, &mut out).await?;
+        }
+        Commands::Stats {
+            crate_name,
+            versions,
+            history,
+        } => {
+            handle_stats(
+                client,
+                &crate_name,
+                versions,
+                history,
+                numbers,
+                &cli.format,
+                &mut out,
+            )
+            .await?;
+        }
+        Commands::Popularity { crate_name } => {
+            handle_popularity(client, &crate_name, numbers, &cli.format, &mut out).await?;
+        }
+        Commands::Report { crate_names, file } => {
+            handle_report(
+                client,
+                crate_names,
+                file.as_deref(),
+                numbers,
+                &cli.format,
+                &mut out,
+            )
+            .await?;
+        }
+        Commands::Analyze { crate_names } => {
+            handle_analyze(client, crate_names, &cli.format, &mut out).await?;
+        }
+        Commands::Lock { crate_names } => {
+            handle_lock(client, crate_names, &mut out).await?;
+        }
+        Commands::CheckLock { lockfile, sarif } => {
+            handle_check_lock(client, &lockfile, sarif, &cli.format, &mut out).await?;
+        }
+        Commands::Bench {
+            crates,
+            concurrency,
+            duration,
+        } => {
+            let limit = match duration {
+                Some(duration) => BenchLimit::Duration(parse_timeout(&duration)?),
+                None => BenchLimit::Requests(crates),
+            };
+            handle_bench(client, limit, concurrency, &cli.format, &mut out).await?;
+        }
+        Commands::ManifestDiff { old, new } => {
+            handle_manifest_diff(client, &old, &new, &cli.format, &mut out).await?;
+        }
+        Commands::Outdated {
+            manifest,
+            fail_on_outdated,
+        } => {
+            handle_outdated(
+                client,
+                &manifest,
+                fail_on_outdated,
+                cli.github_annotations,
+                &cli.format,
+                &mut out,
+            )
+            .await?;
+        }
+        Commands::Validate {
+            manifest,
+            fail_on_missing,
+        } => {
+            handle_validate(client, &manifest, fail_on_missing, &cli.format, &mut out).await?;
+        }
+        Commands::Watch {
+            crate_names,
+            interval,
+            once,
+        } => {
+            let interval = parse_timeout(&interval)?;
+            handle_watch(client, crate_names, interval, once, &cli.format, &mut out).await?;
+        }
+        Commands::Batch {
+            json,
+            file,
+            parallel,
+            resolve,
+            dry_run,
         } => {
+            let options = BatchCommandOptions {
+                parallel,
+                resolve,
+                dry_run,
+                progress: !cli.quiet && std::io::stderr().is_terminal(),
+            };
             handle_batch(
                 client,
                 json.as_deref(),
                 file.as_deref(),
-                parallel,
+                &options,
                 &cli.format,
+                &mut out,
             )
             .await?;
         }
@@ -413,39 +1217,84 @@ pub async fn run() -> Result<()> {
             if let Some(config_path) = config {
                 server_config = AppConfig::load_from_file(Some(config_path))?;
             }
+            if cli.timeout.is_some() {
+                server_config.crates_io.timeout_seconds = timeout.as_secs().max(1);
+            }
 
             start_server(server_config).await?;
         }
-        Commands::Config { output } => {
-            handle_config(output.as_deref())?;
+        Commands::Config { output, format } => {
+            handle_config(output.as_deref(), format, &mut out)?;
+        }
+        Commands::Health { url } => {
+            handle_health(&url, timeout, &cli.format, &mut out).await?;
         }
         Commands::Examples => {
-            handle_examples();
+            handle_examples(&mut out)?;
+        }
+        Commands::Completions { shell } => {
+            handle_completions(shell, &mut out);
+        }
+        Commands::Schema { kind } => {
+            handle_schema(kind, &mut out)?;
         }
     }
 
     Ok(())
 }
 
+/// Resolve the upstream request timeout for this invocation
+///
+/// Precedence is `--timeout` flag > `CRATE_CHECKER__CRATES_IO__TIMEOUT_SECONDS` >
+/// `[crates_io] timeout_seconds` in the config file > the built-in default. The last
+/// three are already layered by [`AppConfig::load_from_file`], so only the flag is
+/// checked here.
+pub fn resolve_timeout(cli: &Cli, config: &AppConfig) -> Result<Duration> {
+    match &cli.timeout {
+        Some(flag) => parse_timeout(flag),
+        None => Ok(Duration::from_secs(config.crates_io.timeout_seconds)),
+    }
+}
+
 /// Handle the check command
 async fn handle_check(
     client: CrateClient,
     crate_name: &str,
     version: Option<&str>,
+    stable: bool,
     format: &OutputFormat,
+    out: &mut dyn Write,
 ) -> Result<()> {
     if let Some(version) = version {
         // Check specific version
         let versions = client.get_all_versions(crate_name).await?;
-        let version_exists = versions.iter().any(|v| v.num == version);
+        let spec = if stable && is_latest_keyword(version) {
+            "latest-stable"
+        } else {
+            version
+        };
 
-        let result = serde_json::json!({
+        let mut result = serde_json::json!({
             "crate": crate_name,
             "version": version,
-            "exists": version_exists
         });
+        let version_exists = match resolve_version_spec(spec, &versions) {
+            VersionResolution::Found(resolved) => {
+                if resolved != version {
+                    result["resolved_version"] = resolved.into();
+                }
+                true
+            }
+            VersionResolution::NotFound { suggestion } => {
+                if let Some(suggestion) = suggestion {
+                    result["suggestion"] = suggestion.into();
+                }
+                false
+            }
+        };
+        result["exists"] = version_exists.into();
 
-        output_result(&serde_json::to_value(result)?, format)?;
+        output_result(&serde_json::to_value(result)?, format, out)?;
 
         if !version_exists {
             std::process::exit(1);
@@ -453,12 +1302,21 @@ async fn handle_check(
     } else {
         // Check crate existence
         let exists = client.crate_exists(crate_name).await?;
-        let result = serde_json::json!({
+        let mut result = serde_json::json!({
             "crate": crate_name,
             "exists": exists
         });
+        if exists && stable {
+            result["latest_stable"] = match client.get_latest_stable_version(crate_name).await {
+                Ok(version) => version.into(),
+                Err(crate::error::CrateCheckerError::VersionNotFound { .. }) => {
+                    serde_json::Value::Null
+                }
+                Err(e) => return Err(e),
+            };
+        }
 
-        output_result(&serde_json::to_value(&result)?, format)?;
+        output_result(&serde_json::to_value(&result)?, format, out)?;
 
         if !exists {
             std::process::exit(1);
@@ -468,32 +1326,209 @@ async fn handle_check(
     Ok(())
 }
 
+/// Handle the check command with --status
+async fn handle_check_status(
+    client: CrateClient,
+    crate_name: &str,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let report = client.crate_status_report(crate_name).await?;
+
+    match format {
+        OutputFormat::Table => {
+            writeln!(out, "{}: {}", report.crate_name, report.status)?;
+            if !report.yanked_versions.is_empty() {
+                writeln!(
+                    out,
+                    "Yanked versions: {}",
+                    report.yanked_versions.join(", ")
+                )?;
+            }
+        }
+        _ => {
+            output_result(&serde_json::to_value(&report)?, format, out)?;
+        }
+    }
+
+    if report.status == CrateStatus::NotFound {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Handle the health command
+async fn handle_health(
+    url: &str,
+    timeout: Duration,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let health_url = format!("{}/health", url.trim_end_matches('/'));
+    let response = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()?
+        .get(&health_url)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(crate::error::CrateCheckerError::ServerError {
+            status: response.status().as_u16(),
+            message: format!("Health check failed at {}", health_url),
+        });
+    }
+    let health: HealthResponse = response.json().await?;
+
+    match format {
+        OutputFormat::Table => {
+            writeln!(
+                out,
+                "{}: {} (version {}, up {})",
+                url,
+                health.status,
+                health.version,
+                format_duration(Duration::from_secs(health.uptime_seconds))
+            )?;
+        }
+        _ => {
+            output_result(&serde_json::to_value(&health)?, format, out)?;
+        }
+    }
+
+    if health.status != "healthy" {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Handle the available command
+async fn handle_available(
+    client: CrateClient,
+    crate_name: &str,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let invalid = client.validate_crate_name(crate_name).err();
+    let available = invalid.is_none() && client.is_name_available(crate_name).await?;
+
+    match format {
+        OutputFormat::Table => match &invalid {
+            Some(e) => writeln!(out, "{}: no ({})", crate_name, e)?,
+            None if available => writeln!(out, "{}: yes, the name is available", crate_name)?,
+            None => writeln!(out, "{}: no, the name is taken", crate_name)?,
+        },
+        _ => {
+            let mut result = serde_json::json!({
+                "crate": crate_name,
+                "available": available,
+            });
+            if let Some(e) = invalid {
+                result["reason"] = e.to_string().into();
+            }
+            output_result(&result, format, out)?;
+        }
+    }
+
+    if !available {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Argument standing for "read from stdin" in place of crate names or a file
+const STDIN_ARG: &str = "-";
+
+/// Replace a `-` argument with the newline-separated crate names read from stdin
+///
+/// Blank lines and `#` comments are skipped, so `cargo metadata` or `grep` output can be piped in.
+fn expand_stdin_crate_names(crate_names: Vec<String>) -> Result<Vec<String>> {
+    if !crate_names.iter().any(|name| name == STDIN_ARG) {
+        return Ok(crate_names);
+    }
+
+    let stdin_names = parse_crate_list(&std::io::read_to_string(std::io::stdin())?);
+    Ok(crate_names
+        .into_iter()
+        .flat_map(|name| {
+            if name == STDIN_ARG {
+                stdin_names.clone()
+            } else {
+                vec![name]
+            }
+        })
+        .collect())
+}
+
+/// Read batch input from stdin: a JSON document, or newline-separated crate names
+fn parse_stdin_batch_input() -> Result<BatchInput> {
+    let content = std::io::read_to_string(std::io::stdin())?;
+    if content.trim_start().starts_with('{') {
+        return parse_json_input(&content);
+    }
+
+    Ok(BatchInput::CrateList {
+        crates: parse_crate_list(&content),
+    })
+}
+
+const ANSI_GREEN: &str = "32";
+const ANSI_RED: &str = "31";
+
+/// Wrap `text` in an ANSI color when coloring is enabled
+fn paint(text: &str, color: &str, enabled: bool) -> String {
+    if enabled {
+        format!("\x1b[{}m{}\x1b[0m", color, text)
+    } else {
+        text.to_string()
+    }
+}
+
 /// Handle the check multiple command
 async fn handle_check_multiple(
     client: CrateClient,
     crate_names: Vec<String>,
-    summary_only: bool,
-    fail_on_missing: bool,
+    options: &MultiCheckOptions,
     format: &OutputFormat,
+    out: &mut dyn Write,
 ) -> Result<()> {
     use crate::error::CrateCheckerError;
 
+    let mut crate_names = expand_stdin_crate_names(crate_names)?;
     if crate_names.is_empty() {
         return Err(CrateCheckerError::ValidationError(
             "At least one crate name must be provided".to_string(),
         ));
     }
 
+    if !options.allow_duplicates {
+        // Keep the first occurrence of each name, so results follow the input order
+        let mut seen = HashSet::new();
+        crate_names.retain(|name| seen.insert(name.clone()));
+    }
+
     info!("Checking {} crates", crate_names.len());
 
     let mut existing_crates = Vec::new();
     let mut missing_crates = Vec::new();
+    let mut errored_crates = Vec::new();
     let mut results = Vec::new();
 
-    // Check each crate
-    for crate_name in &crate_names {
-        match client.crate_exists(crate_name).await {
-            Ok(exists) => {
+    // Check the crates concurrently, keeping the input order
+    let checks: Vec<_> = stream::iter(&crate_names)
+        .map(|crate_name| {
+            let client = &client;
+            async move {
+                if options.fast {
+                    return client
+                        .crate_exists_head(crate_name)
+                        .await
+                        .map(|exists| (exists, "—".to_string(), false));
+                }
+
+                let exists = client.crate_exists(crate_name).await?;
                 let version = if exists {
                     match client.get_latest_version(crate_name).await {
                         Ok(v) => v,
@@ -502,7 +1537,20 @@ async fn handle_check_multiple(
                 } else {
                     "N/A".to_string()
                 };
-
+                // Only annotations report yanked crates, so skip the lookup otherwise
+                let yanked = exists
+                    && options.github_annotations
+                    && client.is_yanked(crate_name).await.unwrap_or(false);
+                Ok((exists, version, yanked))
+            }
+        })
+        .buffered(client.max_concurrent())
+        .collect()
+        .await;
+
+    for (crate_name, check) in crate_names.iter().zip(checks) {
+        match check {
+            Ok((exists, version, yanked)) => {
                 let status = if exists { "EXISTS" } else { "MISSING" };
 
                 results.push(MultiCheckDisplay {
@@ -512,19 +1560,33 @@ async fn handle_check_multiple(
                 });
 
                 if exists {
+                    if yanked {
+                        eprintln!(
+                            "{}",
+                            github_annotation("warning", &yanked_message(crate_name))
+                        );
+                    }
                     existing_crates.push(crate_name.clone());
                 } else {
+                    if options.github_annotations {
+                        let message = format!("Crate '{}' does not exist on crates.io", crate_name);
+                        eprintln!("{}", github_annotation("error", &message));
+                    }
                     missing_crates.push(crate_name.clone());
                 }
             }
             Err(e) => {
                 error!("Error checking crate '{}': {}", crate_name, e);
+                if options.github_annotations {
+                    let message = format!("Failed to check crate '{}': {}", crate_name, e);
+                    eprintln!("{}", github_annotation("warning", &message));
+                }
                 results.push(MultiCheckDisplay {
                     name: crate_name.clone(),
                     status: "ERROR".to_string(),
                     version: "N/A".to_string(),
                 });
-                missing_crates.push(crate_name.clone());
+                errored_crates.push(crate_name.clone());
             }
         }
     }
@@ -534,48 +1596,92 @@ async fn handle_check_multiple(
         total_checked: crate_names.len(),
         existing: existing_crates.len(),
         missing: missing_crates.len(),
+        errored: errored_crates.len(),
         existing_crates: existing_crates.clone(),
         missing_crates: missing_crates.clone(),
+        errored_crates: errored_crates.clone(),
     };
 
     // Output results based on format and options
     match format {
-        OutputFormat::Table => {
-            if !summary_only {
-                println!("{}", Table::new(results));
-                println!();
+        _ if options.compact_summary => {
+            writeln!(out, "{}", summary.compact_line())?;
+        }
+        OutputFormat::Table | OutputFormat::Markdown => {
+            if !options.summary_only {
+                writeln!(out, "{}", render_table(results, format))?;
+                writeln!(out)?;
             }
 
             // Always show summary for table format
-            println!("=== SUMMARY ===");
-            println!("Total checked: {}", summary.total_checked);
-            println!(
+            writeln!(out, "=== SUMMARY ===")?;
+            writeln!(out, "Total checked: {}", summary.total_checked)?;
+            writeln!(
+                out,
                 "Existing: {} ({}%)",
                 summary.existing,
                 (summary.existing as f32 / summary.total_checked as f32 * 100.0).round()
-            );
-            println!(
+            )?;
+            writeln!(
+                out,
                 "Missing: {} ({}%)",
                 summary.missing,
                 (summary.missing as f32 / summary.total_checked as f32 * 100.0).round()
-            );
+            )?;
+            if summary.errored > 0 {
+                writeln!(
+                    out,
+                    "Errored: {} ({}%)",
+                    summary.errored,
+                    (summary.errored as f32 / summary.total_checked as f32 * 100.0).round()
+                )?;
+            }
 
             if !summary.existing_crates.is_empty() {
-                println!("\nExisting crates:");
+                writeln!(out, "\nExisting crates:")?;
                 for crate_name in &summary.existing_crates {
-                    println!("  ✓ {}", crate_name);
+                    let line = format!("✓ {}", crate_name);
+                    writeln!(out, "  {}", paint(&line, ANSI_GREEN, options.color))?;
                 }
             }
 
             if !summary.missing_crates.is_empty() {
-                println!("\nMissing crates:");
+                writeln!(out, "\nMissing crates:")?;
                 for crate_name in &summary.missing_crates {
-                    println!("  ✗ {}", crate_name);
+                    let line = format!("✗ {}", crate_name);
+                    writeln!(out, "  {}", paint(&line, ANSI_RED, options.color))?;
+                }
+            }
+
+            if !summary.errored_crates.is_empty() {
+                writeln!(out, "\nErrored crates (existence unknown):")?;
+                for crate_name in &summary.errored_crates {
+                    let line = format!("? {}", crate_name);
+                    writeln!(out, "  {}", paint(&line, ANSI_RED, options.color))?;
                 }
             }
         }
+        OutputFormat::Compact => {
+            let entries: Vec<(String, &str)> = if options.summary_only {
+                Vec::new()
+            } else {
+                results
+                    .iter()
+                    .map(|r| match r.status.as_str() {
+                        "EXISTS" if !options.fast => (format!("{}={}", r.name, r.version), "OK"),
+                        "EXISTS" => (r.name.clone(), "OK"),
+                        status => (r.name.clone(), status),
+                    })
+                    .collect()
+            };
+            writeln!(
+                out,
+                "{}",
+                compact_status_line(&entries, summary.total_checked, summary.existing)
+            )?;
+        }
         _ => {
-            let output_data = if summary_only {
+            let output_data = if options.summary_only {
                 serde_json::to_value(&summary)?
             } else {
                 serde_json::json!({
@@ -587,12 +1693,13 @@ async fn handle_check_multiple(
                     "summary": summary
                 })
             };
-            output_result(&output_data, format)?;
+            output_result(&output_data, format, out)?;
         }
     }
 
-    // Exit with error if requested and there are missing crates
-    if fail_on_missing && !missing_crates.is_empty() {
+    // Exit with error if requested and there are missing crates; an errored check
+    // cannot rule out a missing crate
+    if options.fail_on_missing && !(missing_crates.is_empty() && errored_crates.is_empty()) {
         std::process::exit(1);
     }
 
@@ -605,91 +1712,213 @@ async fn handle_info(
     crate_name: &str,
     include_deps: bool,
     include_stats: bool,
+    numbers: NumberFormat,
     format: &OutputFormat,
+    out: &mut dyn Write,
 ) -> Result<()> {
     let info = client.get_crate_info(crate_name).await?;
 
     match format {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Markdown => {
             let display = CrateInfoDisplay {
                 name: info.name.clone(),
                 version: info.newest_version.clone(),
-                downloads: format_download_count(info.downloads),
+                downloads: numbers.downloads(info.downloads),
+                msrv: info.msrv.as_deref().unwrap_or("N/A").to_string(),
                 description: info.description.as_deref().unwrap_or("N/A").to_string(),
             };
-            println!("{}", Table::new([display]));
+            writeln!(out, "{}", render_table([display], format))?;
 
             if !info.keywords.is_empty() {
-                println!("\nKeywords: {}", info.keywords.join(", "));
+                writeln!(out, "\nKeywords: {}", info.keywords.join(", "))?;
             }
             if !info.categories.is_empty() {
-                println!("Categories: {}", info.categories.join(", "));
+                writeln!(out, "Categories: {}", info.categories.join(", "))?;
             }
             if let Some(repo) = &info.repository {
-                println!("Repository: {}", repo);
+                writeln!(out, "Repository: {}", repo)?;
             }
             if let Some(homepage) = &info.homepage {
-                println!("Homepage: {}", homepage);
+                writeln!(out, "Homepage: {}", homepage)?;
+            }
+            if let Some(license) = &info.license {
+                writeln!(out, "License: {}", license)?;
+            }
+            if let Some(edition) = &info.edition {
+                writeln!(out, "Edition: {}", edition)?;
+            }
+            match info.max_upload_size {
+                Some(size) => writeln!(out, "Max upload size: {}", format_file_size(size))?,
+                None => writeln!(
+                    out,
+                    "Max upload size: crates.io default ({})",
+                    format_file_size(CRATES_IO_DEFAULT_MAX_UPLOAD_SIZE)
+                )?,
             }
         }
         _ => {
-            let mut result = serde_json::to_value(&info)?;
-
-            if include_deps {
-                if let Ok(deps) = client
-                    .get_crate_dependencies(crate_name, &info.newest_version)
-                    .await
-                {
-                    result["dependencies"] = serde_json::to_value(deps)?;
-                }
-            }
+            let result = info_value(&client, &info, include_deps, include_stats).await?;
+            output_result(&result, format, out)?;
+        }
+    }
 
-            if include_stats {
-                if let Ok(stats) = client.get_download_stats(crate_name).await {
-                    result["download_stats"] = serde_json::to_value(stats)?;
-                }
-            }
+    Ok(())
+}
+
+/// Handle the info command for several crates, fetched concurrently
+async fn handle_info_multiple(
+    client: CrateClient,
+    crate_names: Vec<String>,
+    include_deps: bool,
+    include_stats: bool,
+    numbers: NumberFormat,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let infos = client.get_crate_infos(crate_names).await?;
 
-            output_result(&result, format)?;
+    match format {
+        OutputFormat::Table | OutputFormat::Markdown => {
+            let displays: Vec<CrateInfoDisplay> = infos
+                .iter()
+                .map(|info| CrateInfoDisplay {
+                    name: info.name.clone(),
+                    version: info.newest_version.clone(),
+                    downloads: numbers.downloads(info.downloads),
+                    msrv: info.msrv.as_deref().unwrap_or("N/A").to_string(),
+                    description: info.description.as_deref().unwrap_or("N/A").to_string(),
+                })
+                .collect();
+            writeln!(out, "{}", render_table(displays, format))?;
+        }
+        _ => {
+            let results: Vec<serde_json::Value> = stream::iter(&infos)
+                .map(|info| info_value(&client, info, include_deps, include_stats))
+                .buffered(client.max_concurrent())
+                .collect::<Vec<Result<_>>>()
+                .await
+                .into_iter()
+                .collect::<Result<_>>()?;
+            output_result(&serde_json::Value::Array(results), format, out)?;
         }
     }
 
     Ok(())
 }
 
+/// Structured info output for a crate, optionally with dependencies and download stats
+async fn info_value(
+    client: &CrateClient,
+    info: &CrateInfo,
+    include_deps: bool,
+    include_stats: bool,
+) -> Result<serde_json::Value> {
+    let mut result = serde_json::to_value(info)?;
+
+    if include_deps {
+        if let Ok(deps) = client
+            .get_crate_dependencies(&info.name, &info.newest_version)
+            .await
+        {
+            result["dependencies"] = serde_json::to_value(deps)?;
+        }
+    }
+
+    if include_stats {
+        if let Ok(stats) = client.get_download_stats(&info.name).await {
+            result["download_stats"] = serde_json::to_value(stats)?;
+        }
+    }
+
+    Ok(result)
+}
+
 /// Handle the versions command
 async fn handle_versions(
     client: CrateClient,
     crate_name: &str,
-    no_yanked: bool,
-    limit: Option<usize>,
+    filter: &VersionFilter,
+    numbers: NumberFormat,
     format: &OutputFormat,
+    out: &mut dyn Write,
 ) -> Result<()> {
-    let mut versions = client.get_all_versions(crate_name).await?;
+    let mut versions = client.list_versions(crate_name).await?;
 
-    if no_yanked {
+    if filter.no_yanked || filter.stable {
         versions.retain(|v| !v.yanked);
     }
 
-    if let Some(limit) = limit {
+    if filter.stable {
+        versions.retain(|v| semver::Version::parse(&v.num).is_ok_and(|v| v.pre.is_empty()));
+        sort_versions_desc(&mut versions);
+    }
+
+    if let Some(limit) = filter.limit {
         versions.truncate(limit);
     }
 
     match format {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Markdown => {
             let displays: Vec<VersionDisplay> = versions
                 .into_iter()
                 .map(|v| VersionDisplay {
                     version: v.num,
-                    downloads: format_download_count(v.downloads),
+                    downloads: numbers.downloads(v.downloads),
                     published: v.created_at.format("%Y-%m-%d").to_string(),
                     yanked: if v.yanked { "Yes" } else { "No" }.to_string(),
                 })
                 .collect();
-            println!("{}", Table::new(displays));
+            writeln!(out, "{}", render_table(displays, format))?;
+        }
+        _ => {
+            output_result(&serde_json::to_value(&versions)?, format, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the versions command for a single version
+async fn handle_version_info(
+    client: CrateClient,
+    crate_name: &str,
+    version: &str,
+    numbers: NumberFormat,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let version = if is_latest_keyword(version) {
+        client.get_latest_version(crate_name).await?
+    } else {
+        version.to_string()
+    };
+    let info = client.get_version(crate_name, &version).await?;
+
+    match format {
+        OutputFormat::Table => {
+            writeln!(out, "{} {}", crate_name, info.num)?;
+            writeln!(
+                out,
+                "Published: {}",
+                info.created_at.format("%Y-%m-%d %H:%M UTC")
+            )?;
+            if let Some(user) = &info.published_by {
+                writeln!(out, "Published by: {}", user.login)?;
+            }
+            writeln!(out, "Downloads: {}", numbers.downloads(info.downloads))?;
+            if let Some(size) = info.crate_size {
+                writeln!(out, "Size: {}", format_file_size(size))?;
+            }
+            if let Some(license) = &info.license {
+                writeln!(out, "License: {}", license)?;
+            }
+            if let Some(msrv) = &info.rust_version {
+                writeln!(out, "MSRV: {}", msrv)?;
+            }
+            writeln!(out, "Yanked: {}", if info.yanked { "Yes" } else { "No" })?;
         }
         _ => {
-            output_result(&serde_json::to_value(&versions)?, format)?;
+            output_result(&serde_json::to_value(&info)?, format, out)?;
         }
     }
 
@@ -702,7 +1931,9 @@ async fn handle_search(
     query: &str,
     limit: usize,
     exact: bool,
+    numbers: NumberFormat,
     format: &OutputFormat,
+    out: &mut dyn Write,
 ) -> Result<()> {
     let mut results = client.search_crates(query, Some(limit)).await?;
 
@@ -710,100 +1941,955 @@ async fn handle_search(
         results.retain(|r| r.exact_match);
     }
 
+    write_search_results(results, numbers, format, out)
+}
+
+/// Print crate listings from search, category or keyword browsing
+fn write_search_results(
+    results: Vec<CrateSearchResult>,
+    numbers: NumberFormat,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
     match format {
-        OutputFormat::Table => {
+        OutputFormat::Table | OutputFormat::Markdown => {
             let displays: Vec<SearchResultDisplay> = results
                 .into_iter()
                 .map(|r| SearchResultDisplay {
                     name: r.name,
                     version: r.newest_version,
-                    downloads: format_download_count(r.downloads),
+                    downloads: numbers.downloads(r.downloads),
                     description: truncate_text(r.description.as_deref().unwrap_or("N/A"), 50),
                 })
                 .collect();
-            println!("{}", Table::new(displays));
+            writeln!(out, "{}", render_table(displays, format))?;
         }
         _ => {
-            output_result(&serde_json::to_value(&results)?, format)?;
+            output_result(&serde_json::to_value(&results)?, format, out)?;
         }
     }
 
     Ok(())
 }
 
-/// Handle the deps command
-async fn handle_deps(
+/// Handle the tree command
+async fn handle_tree(
     client: CrateClient,
     crate_name: &str,
-    version: Option<&str>,
+    version: &str,
+    depth: usize,
     runtime_only: bool,
     format: &OutputFormat,
+    out: &mut dyn Write,
 ) -> Result<()> {
-    let version = if let Some(v) = version {
-        v.to_string()
-    } else {
-        client.get_latest_version(crate_name).await?
-    };
-
-    let mut deps = client.get_crate_dependencies(crate_name, &version).await?;
-
-    if runtime_only {
-        deps.retain(|d| d.kind == "normal");
-    }
+    let tree = client
+        .build_dependency_tree_filtered(crate_name, version, depth, runtime_only)
+        .await?;
 
     match format {
-        OutputFormat::Table => {
-            let displays: Vec<DependencyDisplay> = deps
-                .into_iter()
-                .map(|d| {
-                    DependencyDisplay {
-                        name: d.name,
-                        version: d.req, // Use req field directly
-                        kind: d.kind,
-                        optional: if d.optional { "Yes" } else { "No" }.to_string(),
-                    }
-                })
-                .collect();
-            println!("{}", Table::new(displays));
+        OutputFormat::Table => write_dependency_tree(&tree, out)?,
+        OutputFormat::Markdown => {
+            writeln!(out, "```text")?;
+            write_dependency_tree(&tree, out)?;
+            writeln!(out, "```")?;
         }
         _ => {
-            output_result(&serde_json::to_value(&deps)?, format)?;
+            output_result(&serde_json::to_value(&tree)?, format, out)?;
         }
     }
 
     Ok(())
 }
 
-/// Handle the stats command
-async fn handle_stats(
-    client: CrateClient,
-    crate_name: &str,
+/// Print a dependency tree indented like `cargo tree`, marking repeated crates with `(*)`
+fn write_dependency_tree(tree: &DepNode, out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "{}", dep_node_label(tree))?;
+    write_dependency_children(tree, "", out)
+}
+
+fn write_dependency_children(node: &DepNode, prefix: &str, out: &mut dyn Write) -> Result<()> {
+    for (index, child) in node.children.iter().enumerate() {
+        let (branch, indent) = if index + 1 == node.children.len() {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        writeln!(out, "{}{}{}", prefix, branch, dep_node_label(child))?;
+        write_dependency_children(child, &format!("{}{}", prefix, indent), out)?;
+    }
+
+    Ok(())
+}
+
+fn dep_node_label(node: &DepNode) -> String {
+    match (&node.version, &node.requirement) {
+        (Some(version), _) if node.duplicate => format!("{} v{} (*)", node.name, version),
+        (Some(version), _) => format!("{} v{}", node.name, version),
+        (None, Some(requirement)) => format!("{} (unresolved {})", node.name, requirement),
+        (None, None) => node.name.clone(),
+    }
+}
+
+/// Handle the rdeps command
+async fn handle_rdeps(
+    client: CrateClient,
+    crate_name: &str,
+    limit: usize,
+    numbers: NumberFormat,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let report = client
+        .get_reverse_dependency_report(crate_name, Some(limit))
+        .await?;
+
+    match format {
+        OutputFormat::Table | OutputFormat::Markdown => {
+            writeln!(
+                out,
+                "Dependents of {}: {} (showing {})",
+                crate_name,
+                report.total,
+                report.dependents.len()
+            )?;
+            let displays: Vec<ReverseDependencyDisplay> = report
+                .dependents
+                .into_iter()
+                .map(|d| ReverseDependencyDisplay {
+                    name: d.name,
+                    version: d.newest_version,
+                    downloads: numbers.downloads(d.downloads),
+                })
+                .collect();
+            writeln!(out, "{}", render_table(displays, format))?;
+        }
+        _ => {
+            output_result(&serde_json::to_value(&report)?, format, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the features command
+async fn handle_features(
+    client: CrateClient,
+    crate_name: &str,
+    version: &str,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let version = if is_latest_keyword(version) {
+        client.get_latest_version(crate_name).await?
+    } else {
+        version.to_string()
+    };
+    // Sorted for stable output, with `default` first
+    let mut features: Vec<(String, Vec<String>)> = client
+        .get_crate_features(crate_name, &version)
+        .await?
+        .into_iter()
+        .collect();
+    features.sort_by(|(a, _), (b, _)| (a != "default", a).cmp(&(b != "default", b)));
+
+    match format {
+        OutputFormat::Table | OutputFormat::Markdown => {
+            writeln!(
+                out,
+                "Features of {} {}: {}",
+                crate_name,
+                version,
+                features.len()
+            )?;
+            if !features.is_empty() {
+                let displays: Vec<FeatureDisplay> = features
+                    .into_iter()
+                    .map(|(name, enables)| FeatureDisplay {
+                        name,
+                        enables: enables.join(", "),
+                    })
+                    .collect();
+                writeln!(out, "{}", render_table(displays, format))?;
+            }
+        }
+        _ => {
+            let features: serde_json::Map<String, serde_json::Value> = features
+                .into_iter()
+                .map(|(name, enables)| (name, serde_json::json!(enables)))
+                .collect();
+            let result = serde_json::json!({
+                "crate": crate_name,
+                "version": version,
+                "features": features,
+            });
+            output_result(&result, format, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the deps command
+async fn handle_deps(
+    client: CrateClient,
+    crate_name: &str,
+    version: Option<&str>,
+    runtime_only: bool,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let version = match version {
+        Some(v) if semver::Version::parse(v).is_ok() => v.to_string(),
+        Some(v) => {
+            let versions = client.list_versions(crate_name).await?;
+            match resolve_version_spec(v, &versions) {
+                VersionResolution::Found(resolved) => resolved,
+                VersionResolution::NotFound { .. } => {
+                    return Err(crate::error::CrateCheckerError::VersionNotFound {
+                        crate_name: crate_name.to_string(),
+                        version: v.to_string(),
+                    })
+                }
+            }
+        }
+        None => client.get_latest_version(crate_name).await?,
+    };
+
+    let mut deps = client.get_crate_dependencies(crate_name, &version).await?;
+
+    if runtime_only {
+        deps.retain(|d| d.kind == "normal");
+    }
+
+    match format {
+        OutputFormat::Table | OutputFormat::Markdown => {
+            let displays: Vec<DependencyDisplay> = deps
+                .into_iter()
+                .map(|d| {
+                    DependencyDisplay {
+                        name: d.name,
+                        version: d.req, // Use req field directly
+                        kind: d.kind,
+                        optional: if d.optional { "Yes" } else { "No" }.to_string(),
+                    }
+                })
+                .collect();
+            writeln!(out, "{}", render_table(displays, format))?;
+        }
+        _ => {
+            output_result(&serde_json::to_value(&deps)?, format, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the stats command
+async fn handle_stats(
+    client: CrateClient,
+    crate_name: &str,
     show_versions: bool,
+    show_history: bool,
+    numbers: NumberFormat,
     format: &OutputFormat,
+    out: &mut dyn Write,
 ) -> Result<()> {
     let stats = client.get_download_stats(crate_name).await?;
+    let history = match client.get_download_history(crate_name).await {
+        Ok(history) => history,
+        Err(e) if !show_history => {
+            warn!("Download history unavailable, skipping trend: {}", e);
+            Vec::new()
+        }
+        Err(e) => return Err(e),
+    };
+    let trend = DownloadTrend::from_history(&history);
 
     match format {
         OutputFormat::Table => {
-            println!("Download Statistics for '{}':", crate_name);
-            println!("Total Downloads: {}", format_download_count(stats.total));
+            writeln!(out, "Download Statistics for '{}':", crate_name)?;
+            writeln!(out, "Total Downloads: {}", numbers.downloads(stats.total))?;
+            if let Some(trend) = &trend {
+                match trend.change_percent {
+                    Some(change) => writeln!(
+                        out,
+                        "Trend: {} ({:+.1}% over the last {} days)",
+                        trend.direction,
+                        change,
+                        DownloadTrend::WINDOW_DAYS
+                    )?,
+                    None => writeln!(out, "Trend: {}", trend.direction)?,
+                }
+            }
+            if !history.is_empty() {
+                let counts: Vec<u64> = history.iter().map(|(_, downloads)| *downloads).collect();
+                writeln!(out, "Last {} days: {}", history.len(), sparkline(&counts))?;
+            }
 
             if show_versions && !stats.versions.is_empty() {
-                println!("\nVersion Downloads:");
+                writeln!(out, "\nVersion Downloads:")?;
                 let version_displays: Vec<_> = stats
                     .versions
                     .into_iter()
                     .take(10)
-                    .map(|v| (v.version, format_download_count(v.downloads)))
+                    .map(|v| (v.version, numbers.downloads(v.downloads)))
                     .collect();
 
                 for (version, downloads) in version_displays {
-                    println!("  {}: {}", version, downloads);
+                    writeln!(out, "  {}: {}", version, downloads)?;
+                }
+            }
+
+            if show_history && !history.is_empty() {
+                writeln!(out, "\nDaily Downloads:")?;
+                for (date, downloads) in &history {
+                    writeln!(out, "  {}: {}", date, numbers.downloads(*downloads))?;
+                }
+            }
+        }
+        _ => {
+            let mut result = serde_json::to_value(&stats)?;
+            result["trend"] = serde_json::to_value(&trend)?;
+
+            if show_history {
+                result["history"] = serde_json::Value::Array(
+                    history
+                        .iter()
+                        .map(|(date, downloads)| {
+                            serde_json::json!({ "date": date, "downloads": downloads })
+                        })
+                        .collect(),
+                );
+            }
+
+            output_result(&result, format, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the popularity command
+async fn handle_popularity(
+    client: CrateClient,
+    crate_name: &str,
+    numbers: NumberFormat,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let popularity = client.popularity(crate_name).await?;
+
+    match format {
+        OutputFormat::Table => {
+            writeln!(out, "Popularity of '{}':", popularity.crate_name)?;
+            writeln!(
+                out,
+                "Downloads: {}",
+                numbers.downloads(popularity.downloads)
+            )?;
+            writeln!(out, "Percentile: ~{:.1}", popularity.percentile)?;
+            writeln!(out, "Label: {}", popularity.label)?;
+        }
+        _ => {
+            output_result(&serde_json::to_value(&popularity)?, format, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the report command
+async fn handle_report(
+    client: CrateClient,
+    mut crate_names: Vec<String>,
+    file: Option<&std::path::Path>,
+    numbers: NumberFormat,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    if let Some(path) = file {
+        crate_names.extend(parse_crate_list(&std::fs::read_to_string(path)?));
+    }
+
+    if crate_names.is_empty() {
+        return Err(crate::error::CrateCheckerError::ValidationError(
+            "At least one crate name must be provided".to_string(),
+        ));
+    }
+
+    let report = client.generate_report(crate_names).await?;
+
+    match format {
+        OutputFormat::Table | OutputFormat::Markdown => {
+            let displays: Vec<ReportEntryDisplay> = report
+                .crates
+                .iter()
+                .map(|e| ReportEntryDisplay {
+                    name: e.name.clone(),
+                    version: e.newest_version.clone(),
+                    downloads: numbers.downloads(e.downloads),
+                    license: e.license.as_deref().unwrap_or("Unknown").to_string(),
+                    age_days: e.age_days,
+                    yanked: format!("{}/{}", e.yanked_versions, e.versions),
+                })
+                .collect();
+            writeln!(out, "{}", render_table(displays, format))?;
+            writeln!(out)?;
+
+            writeln!(out, "=== REPORT ===")?;
+            writeln!(out, "Crates: {}", report.crate_count)?;
+            writeln!(
+                out,
+                "Total downloads: {}",
+                numbers.downloads(report.total_downloads)
+            )?;
+            writeln!(out, "Average age: {:.0} days", report.average_age_days)?;
+            writeln!(
+                out,
+                "Yank rate: {:.1}% ({} of {} versions)",
+                report.yank_rate * 100.0,
+                report.yanked_versions,
+                report.total_versions
+            )?;
+
+            writeln!(out, "\nLicenses:")?;
+            for (license, count) in &report.license_distribution {
+                writeln!(out, "  {}: {}", license, count)?;
+            }
+
+            if !report.failed.is_empty() {
+                writeln!(out, "\nFailed to fetch:")?;
+                for crate_name in &report.failed {
+                    writeln!(out, "  ✗ {}", crate_name)?;
+                }
+            }
+        }
+        _ => {
+            output_result(&serde_json::to_value(&report)?, format, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the analyze command
+async fn handle_analyze(
+    client: CrateClient,
+    mut crate_names: Vec<String>,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    crate_names.sort();
+    crate_names.dedup();
+
+    let report = client.analyze_dependencies(crate_names).await?;
+
+    match format {
+        OutputFormat::Table | OutputFormat::Markdown => {
+            let analyzed: Vec<String> = report
+                .crates
+                .iter()
+                .map(|(name, version)| format!("{} {}", name, version))
+                .collect();
+            writeln!(out, "Analyzed: {}", analyzed.join(", "))?;
+
+            if report.shared.is_empty() {
+                writeln!(out, "No shared dependencies")?;
+            } else {
+                let displays: Vec<SharedDependencyDisplay> = report
+                    .shared
+                    .iter()
+                    .map(|name| SharedDependencyDisplay {
+                        name: name.clone(),
+                        required_by: report.dependencies[name]
+                            .iter()
+                            .map(|r| format!("{} ({})", r.crate_name, r.req))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        conflict: if report.conflicts.contains(name) {
+                            "✗ yes"
+                        } else {
+                            "no"
+                        }
+                        .to_string(),
+                    })
+                    .collect();
+                writeln!(out, "{}", render_table(displays, format))?;
+            }
+
+            writeln!(
+                out,
+                "\n{} dependencies, {} shared, {} conflicting",
+                report.dependencies.len(),
+                report.shared.len(),
+                report.conflicts.len()
+            )?;
+
+            if !report.failed.is_empty() {
+                writeln!(out, "\nFailed to fetch:")?;
+                for crate_name in &report.failed {
+                    writeln!(out, "  ✗ {}", crate_name)?;
                 }
             }
         }
         _ => {
-            output_result(&serde_json::to_value(&stats)?, format)?;
+            output_result(&serde_json::to_value(&report)?, format, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the lock command
+async fn handle_lock(
+    client: CrateClient,
+    mut crate_names: Vec<String>,
+    out: &mut dyn Write,
+) -> Result<()> {
+    crate_names.sort();
+    crate_names.dedup();
+
+    let resolved = client.get_latest_versions(crate_names).await?;
+    let fragment = LockFragment {
+        package: resolved
+            .into_iter()
+            .map(|(name, version)| LockPackage {
+                name,
+                version,
+                source: CRATES_IO_SOURCE,
+            })
+            .collect(),
+    };
+
+    let lock = toml::to_string(&fragment).map_err(|e| {
+        crate::error::CrateCheckerError::application(format!(
+            "Failed to serialize lock entries: {}",
+            e
+        ))
+    })?;
+    write!(out, "{}", lock)?;
+
+    Ok(())
+}
+
+/// Handle the check-lock command
+async fn handle_check_lock(
+    client: CrateClient,
+    lockfile: &std::path::Path,
+    sarif: bool,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let mut packages = parse_cargo_lock(&std::fs::read_to_string(lockfile)?)?;
+    packages.sort();
+    packages.dedup();
+    let checked = packages.len();
+
+    let findings = client.check_locked_versions(packages).await?;
+
+    if sarif {
+        let log = SarifLog::from_lock_findings(&lockfile.display().to_string(), &findings);
+        writeln!(out, "{}", serde_json::to_string_pretty(&log)?)?;
+        return Ok(());
+    }
+
+    match format {
+        OutputFormat::Table | OutputFormat::Markdown => {
+            if findings.is_empty() {
+                writeln!(
+                    out,
+                    "✓ No yanked or unpublished versions ({} packages checked)",
+                    checked
+                )?;
+            } else {
+                let display: Vec<LockFindingDisplay> = findings
+                    .iter()
+                    .map(|finding| LockFindingDisplay {
+                        name: finding.crate_name.clone(),
+                        version: finding.version.clone(),
+                        problem: finding.kind.description().to_string(),
+                    })
+                    .collect();
+                writeln!(out, "{}", render_table(display, format))?;
+                writeln!(
+                    out,
+                    "✗ {} of {} packages have problems",
+                    findings.len(),
+                    checked
+                )?;
+            }
+        }
+        _ => {
+            output_result(&serde_json::to_value(&findings)?, format, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the bench command
+async fn handle_bench(
+    client: CrateClient,
+    limit: BenchLimit,
+    concurrency: Option<usize>,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let sample: Vec<String> = BENCH_CRATES.iter().map(|name| name.to_string()).collect();
+    let concurrency = concurrency.unwrap_or(client.max_concurrent());
+    let report = client.bench(&sample, concurrency, limit).await?;
+
+    match format {
+        OutputFormat::Table => {
+            writeln!(out, "=== BENCH ===")?;
+            writeln!(
+                out,
+                "Requests: {} at concurrency {}",
+                report.requests, report.concurrency
+            )?;
+            writeln!(out, "Elapsed: {:.0} ms", report.elapsed_ms)?;
+            writeln!(out, "Throughput: {:.1} req/s", report.requests_per_sec)?;
+            writeln!(
+                out,
+                "Latency: p50 {:.1} ms, p95 {:.1} ms",
+                report.p50_ms, report.p95_ms
+            )?;
+            writeln!(
+                out,
+                "Errors: {} ({:.1}%)",
+                report.errors,
+                report.error_rate * 100.0
+            )?;
+        }
+        _ => {
+            output_result(&serde_json::to_value(&report)?, format, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the outdated command
+async fn handle_outdated(
+    client: CrateClient,
+    manifest: &std::path::Path,
+    fail_on_outdated: bool,
+    github_annotations: bool,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let dependencies = parse_manifest_dependencies(&std::fs::read_to_string(manifest)?)?;
+    let results = client.check_outdated(dependencies).await;
+
+    if github_annotations {
+        let yanked: Vec<bool> =
+            stream::iter(&results)
+                .map(|dep| {
+                    let client = &client;
+                    async move {
+                        dep.error.is_none() && client.is_yanked(&dep.name).await.unwrap_or(false)
+                    }
+                })
+                .buffered(client.max_concurrent())
+                .collect()
+                .await;
+
+        for (dep, yanked) in results.iter().zip(yanked) {
+            if let Some(error) = &dep.error {
+                let message = format!("Failed to check dependency '{}': {}", dep.name, error);
+                eprintln!("{}", github_annotation("error", &message));
+                continue;
+            }
+            if yanked {
+                eprintln!(
+                    "{}",
+                    github_annotation("warning", &yanked_message(&dep.name))
+                );
+            }
+            if let (true, Some(latest), Some(update)) = (dep.is_outdated(), &dep.latest, dep.update)
+            {
+                let message = format!(
+                    "Dependency '{}' ({}) is outdated: {} is available ({} update)",
+                    dep.name, dep.requirement, latest, update
+                );
+                eprintln!("{}", github_annotation("warning", &message));
+            }
+        }
+    }
+
+    match format {
+        OutputFormat::Table | OutputFormat::Markdown => {
+            let displays: Vec<OutdatedDisplay> = results
+                .iter()
+                .map(|dep| OutdatedDisplay {
+                    name: dep.name.clone(),
+                    requirement: dep.requirement.clone(),
+                    latest: dep.latest.clone().unwrap_or_else(|| "—".to_string()),
+                    update: match (&dep.update, &dep.error) {
+                        (Some(update), _) => update.to_string(),
+                        (None, Some(error)) => format!("error: {}", error),
+                        (None, None) => "unknown".to_string(),
+                    },
+                })
+                .collect();
+            writeln!(out, "{}", render_table(displays, format))?;
+        }
+        _ => {
+            output_result(&serde_json::to_value(&results)?, format, out)?;
+        }
+    }
+
+    if fail_on_outdated && results.iter().any(|dep| dep.is_outdated()) {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Annotation message for a crate whose every version is yanked
+fn yanked_message(crate_name: &str) -> String {
+    format!("Crate '{}' has every version yanked", crate_name)
+}
+
+/// Handle the validate command
+///
+/// `path`, `git` and alternate-registry dependencies are not checked.
+async fn handle_validate(
+    client: CrateClient,
+    manifest: &std::path::Path,
+    fail_on_missing: bool,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let dependencies = parse_manifest_registry_dependencies(&std::fs::read_to_string(manifest)?)?;
+    let results = client
+        .process_crate_list(dependencies.into_iter().collect())
+        .await?;
+    let validation = ManifestValidation::from_results(results);
+
+    match format {
+        OutputFormat::Table | OutputFormat::Markdown => {
+            let displays: Vec<MultiCheckDisplay> = validation
+                .results
+                .iter()
+                .map(|r| MultiCheckDisplay {
+                    name: r.crate_name.clone(),
+                    status: match r.outcome {
+                        CheckOutcome::Exists => "EXISTS",
+                        CheckOutcome::Missing => "MISSING",
+                        CheckOutcome::Error => "ERROR",
+                    }
+                    .to_string(),
+                    version: r
+                        .latest_version
+                        .clone()
+                        .unwrap_or_else(|| "N/A".to_string()),
+                })
+                .collect();
+            writeln!(out, "{}", render_table(displays, format))?;
+            writeln!(out)?;
+
+            if validation.missing.is_empty() && validation.failed.is_empty() {
+                writeln!(
+                    out,
+                    "All {} dependencies exist on crates.io",
+                    validation.checked
+                )?;
+            }
+            if !validation.missing.is_empty() {
+                writeln!(out, "Missing crates:")?;
+                for crate_name in &validation.missing {
+                    writeln!(out, "  ✗ {}", crate_name)?;
+                }
+            }
+            if !validation.failed.is_empty() {
+                writeln!(out, "Could not check:")?;
+                for crate_name in &validation.failed {
+                    writeln!(out, "  ? {}", crate_name)?;
+                }
+            }
+        }
+        _ => {
+            output_result(&serde_json::to_value(&validation)?, format, out)?;
+        }
+    }
+
+    // A dependency whose check errored may be missing too
+    if fail_on_missing && !(validation.missing.is_empty() && validation.failed.is_empty()) {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Handle the watch command
+///
+/// The first poll reports every crate's current version; later polls report only
+/// crates whose newest version changed. Crates that fail to resolve are logged and
+/// retried on the next poll. Ctrl-C stops watching between or during polls.
+async fn handle_watch(
+    client: CrateClient,
+    crate_names: Vec<String>,
+    interval: Duration,
+    once: bool,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    if interval.is_zero() {
+        return Err(crate::error::CrateCheckerError::validation(
+            "Watch interval must be greater than zero",
+        ));
+    }
+
+    let mut last_seen: HashMap<String, String> = HashMap::new();
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        let poll = stream::iter(&crate_names)
+            .map(|crate_name| {
+                let client = &client;
+                async move {
+                    match client.get_latest_version(crate_name).await {
+                        Ok(version) => Some((crate_name.clone(), version)),
+                        Err(e) => {
+                            warn!("Failed to poll '{}': {}", crate_name, e);
+                            None
+                        }
+                    }
+                }
+            })
+            .buffered(client.max_concurrent())
+            .filter_map(std::future::ready)
+            .collect::<Vec<_>>();
+
+        let observed = tokio::select! {
+            observed = poll => observed,
+            _ = &mut ctrl_c => break,
+        };
+
+        let changes = record_version_changes(&mut last_seen, observed);
+        if !changes.is_empty() {
+            match format {
+                OutputFormat::Table => {
+                    for change in &changes {
+                        match &change.previous_version {
+                            Some(previous) => writeln!(
+                                out,
+                                "{}: {} -> {}",
+                                change.crate_name, previous, change.new_version
+                            )?,
+                            None => writeln!(out, "{}: {}", change.crate_name, change.new_version)?,
+                        }
+                    }
+                }
+                _ => {
+                    output_result(&serde_json::to_value(&changes)?, format, out)?;
+                }
+            }
+            out.flush()?;
+        }
+
+        if once {
+            break;
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = &mut ctrl_c => break,
+        }
+    }
+
+    info!("Stopped watching");
+    Ok(())
+}
+
+/// Update `last_seen` with freshly observed versions, returning the ones that changed
+fn record_version_changes(
+    last_seen: &mut HashMap<String, String>,
+    observed: Vec<(String, String)>,
+) -> Vec<VersionChange> {
+    observed
+        .into_iter()
+        .filter_map(|(crate_name, version)| {
+            let previous = last_seen.insert(crate_name.clone(), version.clone());
+            (previous.as_ref() != Some(&version)).then_some(VersionChange {
+                crate_name,
+                previous_version: previous,
+                new_version: version,
+            })
+        })
+        .collect()
+}
+
+/// Handle the manifest-diff command
+async fn handle_manifest_diff(
+    client: CrateClient,
+    old: &std::path::Path,
+    new: &std::path::Path,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let old_deps = parse_manifest_dependencies(&std::fs::read_to_string(old)?)?;
+    let new_deps = parse_manifest_dependencies(&std::fs::read_to_string(new)?)?;
+    let mut diff = ManifestDiff::between(&old_deps, &new_deps);
+
+    let counts: Vec<Option<usize>> = stream::iter(&diff.bumped)
+        .map(|bump| {
+            let client = &client;
+            async move {
+                match client.get_all_versions(&bump.name).await {
+                    Ok(versions) => {
+                        versions_between(&versions, &bump.old_requirement, &bump.new_requirement)
+                    }
+                    Err(e) => {
+                        warn!("Failed to fetch versions of '{}': {}", bump.name, e);
+                        None
+                    }
+                }
+            }
+        })
+        .buffered(client.max_concurrent())
+        .collect()
+        .await;
+    for (bump, count) in diff.bumped.iter_mut().zip(counts) {
+        bump.versions_between = count;
+    }
+
+    match format {
+        OutputFormat::Table => {
+            if diff.added.is_empty() && diff.removed.is_empty() && diff.bumped.is_empty() {
+                writeln!(out, "No dependency changes")?;
+            }
+            if !diff.added.is_empty() {
+                writeln!(out, "Added:")?;
+                for dep in &diff.added {
+                    writeln!(out, "  + {} {}", dep.name, dep.requirement)?;
+                }
+            }
+            if !diff.removed.is_empty() {
+                writeln!(out, "Removed:")?;
+                for dep in &diff.removed {
+                    writeln!(out, "  - {} {}", dep.name, dep.requirement)?;
+                }
+            }
+            if !diff.bumped.is_empty() {
+                writeln!(out, "Bumped:")?;
+                for bump in &diff.bumped {
+                    let span = bump
+                        .versions_between
+                        .map(|count| format!(" ({} versions)", count))
+                        .unwrap_or_default();
+                    writeln!(
+                        out,
+                        "  ~ {} {} -> {}{}",
+                        bump.name, bump.old_requirement, bump.new_requirement, span
+                    )?;
+                }
+            }
+        }
+        _ => {
+            output_result(&serde_json::to_value(&diff)?, format, out)?;
         }
     }
 
@@ -815,13 +2901,18 @@ async fn handle_batch(
     client: CrateClient,
     json: Option<&str>,
     file: Option<&std::path::Path>,
-    parallel: bool,
+    options: &BatchCommandOptions,
     format: &OutputFormat,
+    out: &mut dyn Write,
 ) -> Result<()> {
-    let batch_input = if let Some(json_str) = json {
+    let mut batch_input = if let Some(json_str) = json {
         parse_json_input(json_str)?
     } else if let Some(file_path) = file {
-        parse_json_file(file_path)?
+        if file_path == std::path::Path::new(STDIN_ARG) {
+            parse_stdin_batch_input()?
+        } else {
+            parse_json_file(file_path)?
+        }
     } else {
         return Err(crate::error::CrateCheckerError::ValidationError(
             "Either --json or --file must be provided".to_string(),
@@ -830,130 +2921,713 @@ async fn handle_batch(
 
     validate_batch_input(&batch_input)?;
 
+    if options.resolve {
+        let BatchInput::CrateVersionMap(map) = batch_input else {
+            return Err(crate::error::CrateCheckerError::validation(
+                "--resolve only applies to crate version maps",
+            ));
+        };
+        let plan = client.resolve_version_map(map).await;
+
+        if options.dry_run {
+            return output_version_plan(&plan, format, out);
+        }
+
+        // Unresolvable entries keep their original request and are reported as not found
+        batch_input = BatchInput::CrateVersionMap(
+            plan.into_iter()
+                .map(|entry| (entry.crate_name, entry.resolved.unwrap_or(entry.requested)))
+                .collect(),
+        );
+    }
+
     info!(
         "Processing batch request with {} mode",
-        if parallel { "parallel" } else { "sequential" }
+        if options.parallel {
+            "parallel"
+        } else {
+            "sequential"
+        }
     );
 
+    if matches!(format, OutputFormat::Jsonl) {
+        return stream_batch_jsonl(&client, batch_input, options, out).await;
+    }
+
     let result = match batch_input {
+        BatchInput::CrateVersionMap(map) if options.parallel && options.progress => {
+            let result = client
+                .process_crate_version_map_with_progress(map, |processed, total| {
+                    eprint!("\r{}", progress_indicator(processed, total, 40));
+                })
+                .await?;
+            eprintln!();
+            result
+        }
+        BatchInput::CrateVersionMap(map) if options.parallel => {
+            client.process_crate_version_map_concurrent(map).await?
+        }
         BatchInput::CrateVersionMap(map) => client.process_crate_version_map(map).await?,
         BatchInput::CrateList { crates } => {
-            let results = client.process_crate_list(crates).await?;
-            BatchResult {
-                results,
-                total_processed: 0,
-                successful: 0,
-                failed: 0,
-                processing_time_ms: 0,
-            }
+            let results = if options.parallel {
+                client.process_crate_list_concurrent(crates).await?
+            } else {
+                client.process_crate_list(crates).await?
+            };
+            let total_processed = results.len();
+            BatchResult::new(results, total_processed, 0)
         }
         BatchInput::Operations { operations } => {
-            client.process_batch_operations(operations).await?.result
+            let max_concurrent = if options.parallel {
+                client.max_concurrent()
+            } else {
+                1
+            };
+            client
+                .process_batch_operations(operations, max_concurrent)
+                .await?
+                .result
         }
     };
 
-    output_result(&serde_json::to_value(&result)?, format)?;
+    match format {
+        OutputFormat::Compact => {
+            writeln!(out, "{}", batch_compact_line(&result.results))?;
+        }
+        _ => {
+            output_result(&serde_json::to_value(&result)?, format, out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write one JSON line per crate as its check completes, in input order
+async fn stream_batch_jsonl(
+    client: &CrateClient,
+    batch_input: BatchInput,
+    options: &BatchCommandOptions,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let max_concurrent = if options.parallel {
+        client.max_concurrent()
+    } else {
+        1
+    };
+    let checks = batch_input.into_checks()?;
+    let total = checks.len();
+    let show_progress = options.parallel && options.progress;
+
+    let mut results = client.check_crates(checks, max_concurrent);
+    let mut processed = 0;
+    while let Some(result) = results.next().await {
+        writeln!(out, "{}", serde_json::to_string(&result)?)?;
+        out.flush()?;
+        processed += 1;
+        if show_progress {
+            eprint!("\r{}", progress_indicator(processed, total, 40));
+        }
+    }
+    if show_progress {
+        eprintln!();
+    }
+
+    Ok(())
+}
+
+/// Print a batch resolution plan
+fn output_version_plan(
+    plan: &[VersionPlanEntry],
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
+    match format {
+        OutputFormat::Table | OutputFormat::Markdown => {
+            let displays: Vec<VersionPlanDisplay> = plan
+                .iter()
+                .map(|entry| VersionPlanDisplay {
+                    name: entry.crate_name.clone(),
+                    requested: entry.requested.clone(),
+                    resolved: entry
+                        .resolved
+                        .clone()
+                        .unwrap_or_else(|| "unresolvable".to_string()),
+                })
+                .collect();
+            writeln!(out, "{}", render_table(displays, format))?;
+        }
+        _ => {
+            output_result(&serde_json::to_value(plan)?, format, out)?;
+        }
+    }
 
     Ok(())
 }
 
 /// Handle the config command
-fn handle_config(output: Option<&std::path::Path>) -> Result<()> {
-    let sample_config = AppConfig::create_sample_config();
+fn handle_config(
+    output: Option<&std::path::Path>,
+    format: Option<ConfigFormat>,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let config_format = format
+        .or_else(|| output.and_then(ConfigFormat::from_path))
+        .unwrap_or_default();
+    let sample_config = AppConfig::create_sample_config_as(config_format);
 
     if let Some(path) = output {
         std::fs::write(path, sample_config)?;
-        println!("Configuration written to: {}", path.display());
+        writeln!(out, "Configuration written to: {}", path.display())?;
     } else {
-        println!("{}", sample_config);
+        writeln!(out, "{}", sample_config)?;
     }
 
     Ok(())
 }
 
+/// Handle the completions command
+fn handle_completions(shell: Shell, out: &mut dyn Write) {
+    let mut command = Cli::command();
+    let bin_name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, bin_name, out);
+}
+
+/// Handle the schema command
+fn handle_schema(kind: Option<SchemaKind>, out: &mut dyn Write) -> Result<()> {
+    let schema = match kind {
+        Some(kind) => serde_json::to_value(kind.schema())?,
+        None => {
+            let mut schemas = serde_json::Map::new();
+            for kind in SchemaKind::value_variants() {
+                schemas.insert(
+                    kind.name().to_string(),
+                    serde_json::to_value(kind.schema())?,
+                );
+            }
+            serde_json::Value::Object(schemas)
+        }
+    };
+    writeln!(out, "{}", serde_json::to_string_pretty(&schema)?)?;
+    Ok(())
+}
+
 /// Handle the examples command
-fn handle_examples() {
-    println!("JSON Batch Input Examples:\n");
+fn handle_examples(out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "JSON Batch Input Examples:\n")?;
 
     let examples = create_example_batch_inputs();
     for (title, example) in examples {
-        println!("{}:", title);
-        println!("{}\n", example);
+        writeln!(out, "{}:", title)?;
+        writeln!(out, "{}\n", example)?;
     }
 
-    println!("Usage:");
-    println!("  crate-checker batch --json '<json_string>'");
-    println!("  crate-checker batch --file input.json");
+    writeln!(out, "Usage:")?;
+    writeln!(out, "  crate-checker batch --json '<json_string>'")?;
+    writeln!(out, "  crate-checker batch --file input.json")?;
+
+    Ok(())
+}
+
+/// Render rows as a table, as GitHub-flavored Markdown for the markdown format
+fn render_table<T: Tabled>(rows: impl IntoIterator<Item = T>, format: &OutputFormat) -> String {
+    let mut table = Table::new(rows);
+    if matches!(format, OutputFormat::Markdown) {
+        table.with(Style::markdown());
+    }
+    table.to_string()
 }
 
 /// Output a result in the specified format
-fn output_result(value: &serde_json::Value, format: &OutputFormat) -> Result<()> {
+fn output_result(
+    value: &serde_json::Value,
+    format: &OutputFormat,
+    out: &mut dyn Write,
+) -> Result<()> {
     match format {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(value)?);
+            writeln!(out, "{}", serde_json::to_string_pretty(value)?)?;
         }
         OutputFormat::Yaml => {
-            println!("{}", serde_yaml::to_string(value)?);
+            writeln!(out, "{}", serde_yaml::to_string(value)?)?;
         }
         OutputFormat::Compact => {
-            println!("{}", serde_json::to_string(value)?);
+            writeln!(out, "{}", serde_json::to_string(value)?)?;
         }
         OutputFormat::Csv => {
-            // Simple CSV output for basic structures
-            if let Some(array) = value.as_array() {
-                if let Some(first) = array.first() {
-                    if let Some(obj) = first.as_object() {
-                        // Print headers
-                        let headers: Vec<String> = obj.keys().map(|k| k.to_string()).collect();
-                        println!("{}", headers.join(","));
-
-                        // Print rows
-                        for item in array {
-                            if let Some(obj) = item.as_object() {
-                                let values: Vec<_> = headers
-                                    .iter()
-                                    .map(|h| obj.get(h).and_then(|v| v.as_str()).unwrap_or("N/A"))
-                                    .collect();
-                                println!("{}", values.join(","));
-                            }
-                        }
-                    }
+            write_csv(value, out)?;
+        }
+        OutputFormat::Jsonl => match value {
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    writeln!(out, "{}", serde_json::to_string(item)?)?;
                 }
-            } else {
-                warn!("CSV format is only supported for array structures");
-                println!("{}", serde_json::to_string_pretty(value)?);
             }
+            _ => writeln!(out, "{}", serde_json::to_string(value)?)?,
+        },
+        OutputFormat::Markdown => {
+            // Tabular commands render Markdown tables themselves; everything else is fenced JSON
+            writeln!(
+                out,
+                "```json\n{}\n```",
+                serde_json::to_string_pretty(value)?
+            )?;
         }
         OutputFormat::Table => {
             // Table format should be handled by the individual command handlers
-            println!("{}", serde_json::to_string_pretty(value)?);
+            writeln!(out, "{}", serde_json::to_string_pretty(value)?)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write `value` as CSV with a header row
+///
+/// Arrays become one row per element, a batch result becomes one row per entry of its
+/// `results`, and any other value is a single row. Nested objects are flattened into
+/// dotted columns (`info.newest_version`); the header is the union of all row columns.
+fn write_csv(value: &serde_json::Value, out: &mut dyn Write) -> Result<()> {
+    let rows: Vec<Vec<(String, String)>> = csv_rows(value)
+        .into_iter()
+        .map(|row| {
+            let mut columns = Vec::new();
+            flatten_csv_value("", row, &mut columns);
+            columns
+        })
+        .collect();
+
+    let mut headers: Vec<&str> = Vec::new();
+    for (column, _) in rows.iter().flatten() {
+        if !headers.contains(&column.as_str()) {
+            headers.push(column);
         }
     }
+    if headers.is_empty() {
+        return Ok(());
+    }
 
+    let header_line: Vec<_> = headers.iter().map(|h| csv_field(h)).collect();
+    writeln!(out, "{}", header_line.join(","))?;
+    for row in &rows {
+        let fields: Vec<_> = headers
+            .iter()
+            .map(|header| {
+                row.iter()
+                    .find(|(column, _)| column == header)
+                    .map(|(_, field)| csv_field(field))
+                    .unwrap_or_default()
+            })
+            .collect();
+        writeln!(out, "{}", fields.join(","))?;
+    }
     Ok(())
 }
 
-/// Initialize logging based on CLI flags
-fn init_logging(verbose: bool, quiet: bool, format: &OutputFormat) {
-    // For structured output formats (JSON, YAML, CSV), suppress logging to stdout
-    // or set to quiet mode automatically to avoid interfering with output parsing
+/// Values rendered as CSV rows
+fn csv_rows(value: &serde_json::Value) -> Vec<&serde_json::Value> {
+    match value {
+        serde_json::Value::Array(items) => items.iter().collect(),
+        serde_json::Value::Object(map) => match map.get("results") {
+            Some(serde_json::Value::Array(results)) => results.iter().collect(),
+            _ => vec![value],
+        },
+        _ => vec![value],
+    }
+}
+
+/// Flatten `value` into `(column, field)` pairs, joining nested object keys with dots
+///
+/// Arrays of scalars are joined with `;` and other arrays are embedded as JSON.
+fn flatten_csv_value(prefix: &str, value: &serde_json::Value, columns: &mut Vec<(String, String)>) {
+    let column = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        }
+    };
+    let name = if prefix.is_empty() { "value" } else { prefix };
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, nested) in map {
+                flatten_csv_value(&column(key), nested, columns);
+            }
+        }
+        serde_json::Value::Array(items)
+            if items
+                .iter()
+                .all(|item| !item.is_object() && !item.is_array()) =>
+        {
+            let joined: Vec<String> = items.iter().map(csv_scalar).collect();
+            columns.push((name.to_string(), joined.join(";")));
+        }
+        serde_json::Value::Array(_) => columns.push((name.to_string(), value.to_string())),
+        scalar => columns.push((name.to_string(), csv_scalar(scalar))),
+    }
+}
+
+/// Text of a scalar JSON value; `null` is empty
+fn csv_scalar(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Initialize logging based on CLI flags and the logging configuration
+///
+/// Logs go to stderr and, when `logging.file` is set, are also appended to that file
+/// (creating its parent directories) through a non-blocking writer. The returned guard
+/// flushes the file writer when dropped, so it must be held until the program exits.
+fn init_logging(
+    cli: &Cli,
+    logging: &LoggingConfig,
+) -> Result<Option<tracing_appender::non_blocking::WorkerGuard>> {
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    // Structured output formats are usually piped, so only errors are logged unless
+    // --verbose asks for more; logs always go to stderr either way
     let should_suppress = matches!(
-        format,
-        OutputFormat::Json | OutputFormat::Yaml | OutputFormat::Csv | OutputFormat::Compact
+        cli.format,
+        OutputFormat::Json
+            | OutputFormat::Yaml
+            | OutputFormat::Csv
+            | OutputFormat::Compact
+            | OutputFormat::Markdown
+            | OutputFormat::Jsonl
     );
 
-    let level = if quiet || should_suppress {
+    let level = if cli.quiet {
         tracing::Level::ERROR
-    } else if verbose {
+    } else if cli.verbose {
         tracing::Level::DEBUG
+    } else if should_suppress {
+        tracing::Level::ERROR
     } else {
         tracing::Level::INFO
     };
 
     // Configure logging to stderr to not interfere with stdout output
-    tracing_subscriber::fmt()
-        .with_max_level(level)
-        .with_target(false)
-        .with_writer(std::io::stderr) // Always write logs to stderr
+    let ansi = cli.color.enabled(std::io::stderr().is_terminal());
+    let mut layers = vec![log_layer(logging, std::io::stderr, ansi)];
+    let mut guard = None;
+    if let Some(path) = logging.file.as_deref() {
+        let path = std::path::Path::new(path);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        let (writer, file_guard) = tracing_appender::non_blocking(file);
+        layers.push(log_layer(logging, writer, false));
+        guard = Some(file_guard);
+    }
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::filter::LevelFilter::from_level(level))
+        .with(layers)
         .init();
+
+    Ok(guard)
+}
+
+/// Log layer writing to `writer` in the configured format
+fn log_layer<S, W>(
+    logging: &LoggingConfig,
+    writer: W,
+    ansi: bool,
+) -> Box<dyn tracing_subscriber::Layer<S> + Send + Sync>
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    W: for<'w> tracing_subscriber::fmt::MakeWriter<'w> + Send + Sync + 'static,
+{
+    use tracing_subscriber::Layer;
+
+    let layer = tracing_subscriber::fmt::layer()
+        .with_target(false)
+        .with_ansi(ansi)
+        .with_writer(writer);
+    match log_formatter(logging) {
+        LogFormatter::Json => layer.json().boxed(),
+        LogFormatter::Compact => layer.compact().boxed(),
+        LogFormatter::Pretty => layer.boxed(),
+    }
+}
+
+/// Formatter for log lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFormatter {
+    /// tracing's default human-readable single-line format
+    Pretty,
+    Compact,
+    /// One JSON object per line, for log aggregation
+    Json,
+}
+
+/// Pick the formatter for `logging.format`; `logging.structured` turns `pretty` into JSON
+fn log_formatter(logging: &LoggingConfig) -> LogFormatter {
+    match logging.format.as_str() {
+        "json" => LogFormatter::Json,
+        "compact" => LogFormatter::Compact,
+        _ if logging.structured => LogFormatter::Json,
+        _ => LogFormatter::Pretty,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn sample_crate_info() -> CrateInfo {
+        CrateInfo {
+            name: "serde".to_string(),
+            description: Some("A serialization framework".to_string()),
+            newest_version: "1.0.210".to_string(),
+            downloads: 300_000_000,
+            created_at: Utc.with_ymd_and_hms(2014, 12, 5, 0, 0, 0).unwrap(),
+            updated_at: Utc.with_ymd_and_hms(2024, 9, 6, 0, 0, 0).unwrap(),
+            homepage: Some("https://serde.rs".to_string()),
+            repository: None,
+            documentation: None,
+            keywords: vec!["serde".to_string(), "serialization".to_string()],
+            categories: vec!["encoding".to_string()],
+            max_upload_size: None,
+            license: Some("MIT OR Apache-2.0".to_string()),
+            yanked: None,
+            links: None,
+            msrv: None,
+            edition: None,
+        }
+    }
+
+    fn render(format: OutputFormat) -> String {
+        let value = serde_json::to_value(sample_crate_info()).unwrap();
+        let mut buffer = Vec::new();
+        output_result(&value, &format, &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_multi_check_compact_line() {
+        let summary = MultiCheckSummary {
+            total_checked: 5,
+            existing: 3,
+            missing: 2,
+            errored: 0,
+            existing_crates: vec!["serde".into(), "tokio".into(), "clap".into()],
+            missing_crates: vec!["nope".into(), "gone".into()],
+            errored_crates: vec![],
+        };
+        assert_eq!(summary.compact_line(), "3/5 exist (2 missing: nope, gone)");
+
+        let with_errors = MultiCheckSummary {
+            total_checked: 3,
+            existing: 1,
+            missing: 1,
+            errored: 1,
+            existing_crates: vec!["serde".into()],
+            missing_crates: vec!["nope".into()],
+            errored_crates: vec!["flaky".into()],
+        };
+        assert_eq!(
+            with_errors.compact_line(),
+            "1/3 exist (1 missing: nope) (1 errored: flaky)"
+        );
+
+        let all_present = MultiCheckSummary {
+            total_checked: 2,
+            existing: 2,
+            missing: 0,
+            errored: 0,
+            existing_crates: vec!["serde".into(), "tokio".into()],
+            missing_crates: vec![],
+            errored_crates: vec![],
+        };
+        assert_eq!(all_present.compact_line(), "2/2 exist");
+    }
+
+    #[test]
+    fn test_output_result_json() {
+        let output = render(OutputFormat::Json);
+
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["name"], "serde");
+        assert_eq!(parsed["newest_version"], "1.0.210");
+        assert_eq!(parsed["downloads"], 300_000_000);
+        assert!(output.contains("\n  \"name\": \"serde\""));
+    }
+
+    #[test]
+    fn test_output_result_yaml() {
+        let output = render(OutputFormat::Yaml);
+
+        assert!(output.contains("name: serde\n"));
+        assert!(output.contains("newest_version: 1.0.210\n"));
+        assert!(output.contains("license: MIT OR Apache-2.0\n"));
+
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&output).unwrap();
+        assert_eq!(parsed["downloads"].as_u64(), Some(300_000_000));
+    }
+
+    #[test]
+    fn test_output_result_csv_batch_result() {
+        let batch = BatchResult {
+            results: vec![
+                CrateCheckResult {
+                    crate_name: "serde".to_string(),
+                    exists: true,
+                    outcome: CheckOutcome::Exists,
+                    latest_version: Some("1.0.210".to_string()),
+                    requested_version: Some("1.0.0".to_string()),
+                    version_exists: Some(true),
+                    resolved_version: Some("1.0.0".to_string()),
+                    suggested_version: None,
+                    error: None,
+                    info: Some(sample_crate_info()),
+                    dependencies: None,
+                    download_stats: None,
+                    versions: None,
+                },
+                CrateCheckResult {
+                    crate_name: "missing".to_string(),
+                    exists: false,
+                    outcome: CheckOutcome::Error,
+                    latest_version: None,
+                    requested_version: None,
+                    version_exists: None,
+                    resolved_version: None,
+                    suggested_version: None,
+                    error: Some("Crate 'missing' not found, sorry".to_string()),
+                    info: None,
+                    dependencies: None,
+                    download_stats: None,
+                    versions: None,
+                },
+            ],
+            total_processed: 2,
+            total_crates_checked: 2,
+            successful: 1,
+            missing: 0,
+            failed: 1,
+            processing_time_ms: 12,
+        };
+        let value = serde_json::to_value(&batch).unwrap();
+        let mut buffer = Vec::new();
+        output_result(&value, &OutputFormat::Csv, &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+
+        let header: Vec<&str> = lines[0].split(',').collect();
+        let field = |line: &str, column: &str| {
+            let index = header.iter().position(|h| *h == column).unwrap();
+            line.split(',').nth(index).unwrap().to_string()
+        };
+        assert!(header.contains(&"crate_name"));
+        assert!(header.contains(&"info.newest_version"));
+        assert!(header.contains(&"info.keywords"));
+
+        assert_eq!(field(lines[1], "crate_name"), "serde");
+        assert_eq!(field(lines[1], "exists"), "true");
+        assert_eq!(field(lines[1], "info.newest_version"), "1.0.210");
+        assert_eq!(field(lines[1], "info.downloads"), "300000000");
+        assert_eq!(field(lines[1], "info.keywords"), "serde;serialization");
+        assert_eq!(field(lines[1], "error"), "");
+
+        assert_eq!(field(lines[2], "crate_name"), "missing");
+        assert!(lines[2].contains("\"Crate 'missing' not found, sorry\""));
+    }
+
+    #[test]
+    fn test_output_result_csv_single_object() {
+        let output = render(OutputFormat::Csv);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        let header: Vec<&str> = lines[0].split(',').collect();
+        let fields: Vec<&str> = lines[1].split(',').collect();
+        let field = |column: &str| fields[header.iter().position(|h| *h == column).unwrap()];
+        assert_eq!(field("downloads"), "300000000");
+        assert_eq!(field("license"), "MIT OR Apache-2.0");
+        assert_eq!(field("repository"), "");
+    }
+
+    #[test]
+    fn test_record_version_changes() {
+        let mut last_seen = HashMap::new();
+        let observed = |serde: &str| {
+            vec![
+                ("serde".to_string(), serde.to_string()),
+                ("tokio".to_string(), "1.40.0".to_string()),
+            ]
+        };
+
+        let first = record_version_changes(&mut last_seen, observed("1.0.209"));
+        assert_eq!(first.len(), 2);
+        assert!(first.iter().all(|change| change.previous_version.is_none()));
+
+        assert!(record_version_changes(&mut last_seen, observed("1.0.209")).is_empty());
+
+        let bumped = record_version_changes(&mut last_seen, observed("1.0.210"));
+        assert_eq!(
+            bumped,
+            vec![VersionChange {
+                crate_name: "serde".to_string(),
+                previous_version: Some("1.0.209".to_string()),
+                new_version: "1.0.210".to_string(),
+            }]
+        );
+        assert_eq!(last_seen["serde"], "1.0.210");
+    }
+
+    #[test]
+    fn test_output_result_compact_is_single_line() {
+        let output = render(OutputFormat::Compact);
+
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.starts_with('{'));
+    }
+
+    #[test]
+    fn test_color_choice() {
+        assert!(ColorChoice::Always.enabled(false));
+        assert!(!ColorChoice::Never.enabled(true));
+        assert!(!ColorChoice::Auto.enabled(false));
+        assert_eq!(paint("x", ANSI_RED, true), "\x1b[31mx\x1b[0m");
+        assert_eq!(paint("x", ANSI_RED, false), "x");
+    }
+
+    #[test]
+    fn test_log_formatter_follows_logging_config() {
+        let logging = |format: &str, structured: bool| LoggingConfig {
+            format: format.to_string(),
+            structured,
+            ..LoggingConfig::default()
+        };
+
+        assert_eq!(log_formatter(&logging("json", false)), LogFormatter::Json);
+        assert_eq!(
+            log_formatter(&logging("compact", false)),
+            LogFormatter::Compact
+        );
+        assert_eq!(
+            log_formatter(&logging("pretty", false)),
+            LogFormatter::Pretty
+        );
+        assert_eq!(log_formatter(&logging("pretty", true)), LogFormatter::Json);
+    }
 }
@@ -1,23 +1,343 @@
 //! HTTP client for interacting with the crates.io API
 
+use crate::config::CacheConfig;
 use crate::error::{CrateCheckerError, Result};
+use crate::index::LocalIndex;
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport, ReqwestTransport};
 use crate::types::*;
-use crate::{DEFAULT_API_URL, DEFAULT_TIMEOUT_SECS, DEFAULT_USER_AGENT};
-use reqwest::{Client, StatusCode};
-use std::collections::HashMap;
+use crate::utils::{
+    backoff_delay, is_latest_keyword, latest_stable_version, normalize_crate_name,
+    parse_retry_after, resolve_requirement, resolve_version, schema_drift,
+};
+use crate::{
+    DEFAULT_API_URL, DEFAULT_MAX_CONCURRENT, DEFAULT_RETRY_ATTEMPTS,
+    DEFAULT_SLOW_REQUEST_THRESHOLD_MS, DEFAULT_TIMEOUT_SECS, DEFAULT_USER_AGENT,
+};
+use chrono::NaiveDate;
+use dashmap::DashMap;
+use futures::stream::{self, Stream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use reqwest::{Client, Method, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
 /// HTTP client for crates.io API interactions
 #[derive(Debug, Clone)]
 pub struct CrateClient {
-    client: Client,
+    transport: Arc<dyn HttpTransport>,
     base_url: String,
+    max_concurrent: usize,
+    history_ttl: Option<Duration>,
+    api_links: Arc<DashMap<String, String>>,
+    retry_attempts: u32,
+    strict: bool,
+    include_prerelease: bool,
+    search_timeout: Option<Duration>,
+    stats_timeout: Option<Duration>,
+    counters: Arc<RequestCounters>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// Caps upstream requests awaiting a response across all clones of the client
+    in_flight: Option<Arc<tokio::sync::Semaphore>>,
+    /// Upstream requests taking longer than this are logged as slow
+    slow_request_threshold: Option<Duration>,
+    response_cache: Option<Arc<ResponseCache>>,
+    offline_index: Option<LocalIndex>,
     // Note: These fields are intentionally kept for configuration tracking and potential future use
     _user_agent: String,
     _timeout: Duration,
 }
 
+/// Whether two URLs share scheme, host and port
+fn same_origin(a: &reqwest::Url, b: &reqwest::Url) -> bool {
+    a.scheme() == b.scheme()
+        && a.host_str() == b.host_str()
+        && a.port_or_known_default() == b.port_or_known_default()
+}
+
+/// Ceiling of the jittered delay before the first retry; doubled for every further attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// Longest delay between retries, whatever the attempt number; a longer Retry-After
+/// fails the request instead of waiting
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(10);
+
+/// Most server-provided API links remembered at once; beyond it an arbitrary link is
+/// dropped, which only costs falling back to the conventional path
+const MAX_API_LINKS: usize = 4096;
+
+/// Page size crates.io uses for search when no `per_page` is given
+const DEFAULT_SEARCH_PAGE_SIZE: usize = 10;
+
+/// One page of a paginated crates.io listing
+trait Paginated: DeserializeOwned + Serialize {
+    type Item;
+
+    /// Query string (or absolute URL) of the next page, if any
+    fn next_page(&self) -> Option<&str>;
+
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+impl Paginated for VersionsResponse {
+    type Item = Version;
+
+    fn next_page(&self) -> Option<&str> {
+        self.meta.next_page.as_deref()
+    }
+
+    fn into_items(self) -> Vec<Version> {
+        self.versions
+    }
+}
+
+impl Paginated for SearchResponse {
+    type Item = CrateSearchResult;
+
+    fn next_page(&self) -> Option<&str> {
+        self.meta.next_page.as_deref()
+    }
+
+    fn into_items(self) -> Vec<CrateSearchResult> {
+        self.crates
+    }
+}
+
+/// Resolve a `meta.next_page` value (usually `?page=2&...`) against the current page URL
+fn next_page_url(current: &str, next_page: &str) -> String {
+    if next_page.starts_with("http://") || next_page.starts_with("https://") {
+        return next_page.to_string();
+    }
+    let path = current.split('?').next().unwrap_or(current);
+    format!("{}{}", path, next_page)
+}
+
+/// Upstream request counters shared by all clones of a client
+#[derive(Debug, Default)]
+struct RequestCounters {
+    requests: AtomicU64,
+    retries: AtomicU64,
+    failures: AtomicU64,
+}
+
+/// Newest version listed in a crate response, whose license and MSRV are the crate's current ones
+///
+/// Falls back to the most recent non-yanked version when the newest one is not listed.
+fn newest_version(response: &CrateResponse) -> Option<&Version> {
+    let versions = response.versions.as_deref()?;
+    versions
+        .iter()
+        .find(|v| v.num == response.crate_info.newest_version)
+        .or_else(|| versions.iter().find(|v| !v.yanked))
+}
+
+/// A dependency requirement and the version it resolved to, if any
+struct ResolvedDependency {
+    name: String,
+    requirement: String,
+    kind: String,
+    optional: bool,
+    version: Option<String>,
+}
+
+/// Turns the per-crate-version dependency lists of a tree walk into nested [`DepNode`]s
+struct TreeAssembly<'a> {
+    children: &'a HashMap<(String, String), Vec<ResolvedDependency>>,
+    max_depth: usize,
+    expanded: HashSet<(String, String)>,
+}
+
+impl TreeAssembly<'_> {
+    fn node(&mut self, name: String, version: Option<String>, depth: usize) -> DepNode {
+        let mut node = DepNode {
+            name,
+            version,
+            requirement: None,
+            kind: None,
+            optional: false,
+            duplicate: false,
+            children: Vec::new(),
+        };
+        let Some(version) = node.version.clone() else {
+            return node;
+        };
+        let key = (node.name.clone(), version);
+        let Some(deps) = self.children.get(&key).filter(|deps| !deps.is_empty()) else {
+            return node;
+        };
+        if self.expanded.contains(&key) {
+            node.duplicate = true;
+            return node;
+        }
+        if depth >= self.max_depth {
+            return node;
+        }
+        self.expanded.insert(key);
+
+        node.children = deps
+            .iter()
+            .map(|dep| {
+                let mut child = self.node(dep.name.clone(), dep.version.clone(), depth + 1);
+                child.requirement = Some(dep.requirement.clone());
+                child.kind = Some(dep.kind.clone());
+                child.optional = dep.optional;
+                child
+            })
+            .collect();
+        node
+    }
+}
+
+/// Turn a `{crate: version}` map into checks, treating "latest" and its synonyms as no version
+fn version_map_checks(input: HashMap<String, String>) -> Vec<(String, Option<String>)> {
+    input
+        .into_iter()
+        .map(|(crate_name, version)| {
+            let version = (!is_latest_keyword(&version)).then_some(version);
+            (crate_name, version)
+        })
+        .collect()
+}
+
+/// Summarize the checks of a crate version map
+fn version_map_result(results: Vec<CrateCheckResult>, start_time: Instant) -> BatchResult {
+    let total_processed = results.len();
+    let result = BatchResult::new(
+        results,
+        total_processed,
+        start_time.elapsed().as_millis() as u64,
+    );
+
+    info!(
+        "Batch processing completed: {} total, {} successful, {} missing, {} failed",
+        total_processed, result.successful, result.missing, result.failed
+    );
+
+    result
+}
+
+/// Whether a user agent names a way to reach its operator, i.e. a URL or email address
+fn has_contact_info(user_agent: &str) -> bool {
+    user_agent.contains("://") || user_agent.contains('@')
+}
+
+/// Token bucket throttling upstream requests, shared by all clones of a client
+#[derive(Debug)]
+struct RateLimiter {
+    capacity: f64,
+    tokens_per_sec: f64,
+    bucket: Mutex<TokenBucket>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    /// Available permits; negative while callers are queued for future permits
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    fn new(requests_per_minute: u32, burst_size: u32) -> Self {
+        let capacity = f64::from(burst_size.max(1));
+        Self {
+            capacity,
+            tokens_per_sec: f64::from(requests_per_minute) / 60.0,
+            bucket: Mutex::new(TokenBucket {
+                tokens: capacity,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until a permit is available
+    ///
+    /// Each caller reserves its permit up front, so concurrent callers queue in
+    /// arrival order instead of racing for the next refill.
+    async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let refill = now.duration_since(bucket.refilled_at).as_secs_f64() * self.tokens_per_sec;
+            bucket.tokens = (bucket.tokens + refill).min(self.capacity) - 1.0;
+            bucket.refilled_at = now;
+
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-bucket.tokens / self.tokens_per_sec)
+        };
+
+        debug!("Rate limit reached, waiting {:?} for a permit", wait);
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Successful GET response bodies kept by the optional client-side cache, keyed by URL
+#[derive(Debug)]
+struct ResponseCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: DashMap<String, CachedBody>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedBody {
+    body: String,
+    expires_at: Instant,
+}
+
+impl ResponseCache {
+    fn get(&self, url: &str) -> Option<String> {
+        let entry = self.entries.get(url)?.clone();
+        if entry.expires_at > Instant::now() {
+            Some(entry.body)
+        } else {
+            self.entries.remove(url);
+            None
+        }
+    }
+
+    fn insert(&self, url: &str, body: String) {
+        self.insert_for(url, body, self.ttl);
+    }
+
+    /// Cache `body` for `ttl` instead of the cache-wide TTL
+    fn insert_for(&self, url: &str, body: String, ttl: Duration) {
+        if self.max_entries == 0 || ttl.is_zero() {
+            return;
+        }
+
+        if self.entries.len() >= self.max_entries && !self.entries.contains_key(url) {
+            // Drop expired entries once the cache reaches its limit, then the entry
+            // closest to expiring if every one is still live
+            let now = Instant::now();
+            self.entries.retain(|_, entry| entry.expires_at > now);
+            if self.entries.len() >= self.max_entries {
+                let soonest = self
+                    .entries
+                    .iter()
+                    .min_by_key(|entry| entry.expires_at)
+                    .map(|entry| entry.key().clone());
+                if let Some(key) = soonest {
+                    self.entries.remove(&key);
+                }
+            }
+        }
+
+        self.entries.insert(
+            url.to_string(),
+            CachedBody {
+                body,
+                expires_at: Instant::now() + ttl,
+            },
+        );
+    }
+}
+
 impl CrateClient {
     /// Create a new client with default settings
     pub fn new() -> Self {
@@ -31,21 +351,258 @@ impl CrateClient {
         CrateClientBuilder::default()
     }
 
+    /// Maximum number of concurrent requests used by multi-crate operations
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    /// Whether version listings and latest-version selection include pre-releases
+    pub fn include_prerelease(&self) -> bool {
+        self.include_prerelease
+    }
+
+    /// Whether `version` is shown under the client's pre-release setting; versions that
+    /// are not valid semver are always shown
+    fn is_listed(&self, version: &str) -> bool {
+        self.include_prerelease
+            || semver::Version::parse(version).map_or(true, |v| v.pre.is_empty())
+    }
+
+    /// Snapshot of the upstream request, retry and failure counters
+    pub fn stats(&self) -> ClientStats {
+        ClientStats {
+            requests: self.counters.requests.load(Ordering::Relaxed),
+            retries: self.counters.retries.load(Ordering::Relaxed),
+            failures: self.counters.failures.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Send a GET request, retrying recoverable failures with exponential backoff
+    async fn send_get(&self, url: &str) -> Result<HttpResponse> {
+        self.send_request(Method::GET, url, None).await
+    }
+
+    /// Send a request, retrying recoverable failures with jittered exponential backoff
+    ///
+    /// 5xx and 429 responses are treated as failures so they can be retried; a 429
+    /// carrying `Retry-After` waits that long instead of backing off, or fails at once
+    /// when that is longer than the retry delay cap. Any other status
+    /// is returned for the caller to interpret. `timeout` overrides the client-wide
+    /// timeout for each attempt.
+    async fn send_request(
+        &self,
+        method: Method,
+        url: &str,
+        timeout: Option<Duration>,
+    ) -> Result<HttpResponse> {
+        if self.offline_index.is_some() {
+            return Err(CrateCheckerError::NetworkError(format!(
+                "{} is not available in offline mode",
+                url
+            )));
+        }
+
+        self.counters.requests.fetch_add(1, Ordering::Relaxed);
+
+        let mut attempt = 0;
+        loop {
+            if let Some(limiter) = &self.rate_limiter {
+                limiter.acquire().await;
+            }
+
+            let request = HttpRequest {
+                method: method.clone(),
+                url: url.to_string(),
+                timeout,
+            };
+            // Queue for a slot rather than fail when the concurrency limit is reached
+            let permit = match &self.in_flight {
+                Some(in_flight) => Some(in_flight.acquire().await.map_err(|_| {
+                    CrateCheckerError::application("Upstream concurrency limiter closed")
+                })?),
+                None => None,
+            };
+            let started = Instant::now();
+            let sent = self.transport.send(request).await;
+            drop(permit);
+            let elapsed = started.elapsed();
+            if self
+                .slow_request_threshold
+                .is_some_and(|threshold| elapsed > threshold)
+            {
+                warn!(
+                    "Slow upstream request: {} took {}ms",
+                    url,
+                    elapsed.as_millis()
+                );
+            }
+            let result = match sent {
+                Ok(response) if response.status == StatusCode::TOO_MANY_REQUESTS => {
+                    let retry_after = response
+                        .headers
+                        .get(reqwest::header::RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| parse_retry_after(value, chrono::Utc::now()));
+                    Err(CrateCheckerError::RateLimitExceeded { retry_after })
+                }
+                Ok(response) if response.status.is_server_error() => {
+                    Err(CrateCheckerError::from(response.status))
+                }
+                result => result,
+            };
+
+            match result {
+                Ok(response) => return Ok(response),
+                // Waiting out a long Retry-After would stall the caller for that long
+                Err(CrateCheckerError::RateLimitExceeded {
+                    retry_after: Some(retry_after),
+                }) if retry_after > RETRY_MAX_DELAY => {
+                    self.counters.failures.fetch_add(1, Ordering::Relaxed);
+                    return Err(CrateCheckerError::RateLimitExceeded {
+                        retry_after: Some(retry_after),
+                    });
+                }
+                Err(e) if e.is_recoverable() && attempt < self.retry_attempts => {
+                    // Honor the server's Retry-After over the exponential backoff
+                    let delay = match e {
+                        CrateCheckerError::RateLimitExceeded {
+                            retry_after: Some(retry_after),
+                        } => retry_after,
+                        _ => backoff_delay(attempt, RETRY_BASE_DELAY, RETRY_MAX_DELAY),
+                    };
+                    attempt += 1;
+                    self.counters.retries.fetch_add(1, Ordering::Relaxed);
+                    warn!(
+                        "Request to {} failed ({}), retrying in {:?} (attempt {}/{})",
+                        url, e, delay, attempt, self.retry_attempts
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => {
+                    self.counters.failures.fetch_add(1, Ordering::Relaxed);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// Send a GET request through the response cache, returning the status and body
+    ///
+    /// Only 200 responses are cached; without a configured cache this is a plain GET.
+    async fn get_body(&self, url: &str, timeout: Option<Duration>) -> Result<(StatusCode, String)> {
+        if let Some(body) = self
+            .response_cache
+            .as_ref()
+            .and_then(|cache| cache.get(url))
+        {
+            debug!("Serving {} from the response cache", url);
+            return Ok((StatusCode::OK, body));
+        }
+
+        let response = self.send_request(Method::GET, url, timeout).await?;
+        let HttpResponse { status, body, .. } = response;
+        if status == StatusCode::OK {
+            if let Some(cache) = &self.response_cache {
+                cache.insert(url, body.clone());
+            }
+        }
+        Ok((status, body))
+    }
+
+    /// Decode a JSON response body
+    ///
+    /// In strict mode the body is also compared with the parsed value, and any
+    /// unknown or missing field is reported as [`CrateCheckerError::SchemaMismatch`].
+    fn decode_body<T>(&self, body: &str) -> Result<T>
+    where
+        T: DeserializeOwned + Serialize,
+    {
+        if !self.strict {
+            return Ok(serde_json::from_str(body)?);
+        }
+
+        let raw: serde_json::Value = serde_json::from_str(body)?;
+        let parsed: T = serde_json::from_value(raw.clone())?;
+        let drift = schema_drift(&raw, &serde_json::to_value(&parsed)?);
+        if drift.is_empty() {
+            Ok(parsed)
+        } else {
+            Err(CrateCheckerError::SchemaMismatch(drift.join(", ")))
+        }
+    }
+
+    /// URL for an API resource, preferring a server-provided link seen in an earlier response
+    ///
+    /// `key` has the form `kind:crate` or `kind:crate@version`; `fallback` builds the
+    /// conventional path when no link is known.
+    fn api_url(&self, key: &str, fallback: impl FnOnce() -> String) -> String {
+        match self.api_links.get(key) {
+            Some(url) => url.clone(),
+            None => fallback(),
+        }
+    }
+
+    /// Record a server-provided link, resolved against the base URL
+    ///
+    /// Links to another origin are ignored: requests carry the registry token, which
+    /// must not reach a host the registry merely pointed at.
+    fn remember_link(&self, key: String, link: &Option<String>) {
+        let Some(link) = link else {
+            return;
+        };
+        let base = match reqwest::Url::parse(&self.base_url) {
+            Ok(base) => base,
+            Err(e) => {
+                debug!("Ignoring API link '{}': {}", link, e);
+                return;
+            }
+        };
+        match base.join(link) {
+            Ok(url) if same_origin(&base, &url) => {
+                if self.api_links.len() >= MAX_API_LINKS && !self.api_links.contains_key(&key) {
+                    let evicted = self.api_links.iter().next().map(|e| e.key().clone());
+                    if let Some(evicted) = evicted {
+                        self.api_links.remove(&evicted);
+                    }
+                }
+                self.api_links.insert(key, url.to_string());
+            }
+            Ok(url) => debug!("Ignoring API link to another origin: {}", url),
+            Err(e) => debug!("Ignoring unusable API link '{}': {}", link, e),
+        }
+    }
+
+    /// Record the per-version dependency links of a crate
+    fn remember_version_links(&self, crate_name: &str, versions: &[Version]) {
+        for version in versions {
+            if let Some(links) = &version.links {
+                self.remember_link(
+                    format!("dependencies:{}@{}", crate_name, version.num),
+                    &links.dependencies,
+                );
+            }
+        }
+    }
+
     /// Check if a specific crate exists on crates.io
     pub async fn crate_exists(&self, crate_name: &str) -> Result<bool> {
         self.validate_crate_name(crate_name)?;
 
+        if let Some(index) = &self.offline_index {
+            return Ok(index.versions(crate_name).is_ok());
+        }
+
         let url = format!("{}/crates/{}", self.base_url, crate_name);
         debug!("Checking if crate exists: {}", crate_name);
 
-        match self.client.get(&url).send().await {
-            Ok(response) => match response.status() {
+        match self.get_body(&url, None).await {
+            Ok((status, _)) => match status {
                 StatusCode::OK => {
-                    info!("Crate '{}' exists", crate_name);
+                    debug!("Crate '{}' exists", crate_name);
                     Ok(true)
                 }
                 StatusCode::NOT_FOUND => {
-                    info!("Crate '{}' not found", crate_name);
+                    debug!("Crate '{}' not found", crate_name);
                     Ok(false)
                 }
                 status => {
@@ -55,30 +612,163 @@ impl CrateClient {
             },
             Err(e) => {
                 error!("Failed to check crate '{}': {}", crate_name, e);
-                Err(CrateCheckerError::from(e))
+                Err(e)
             }
         }
     }
 
+    /// Check whether `name` could be used to publish a new crate
+    ///
+    /// True when the name is valid and none of its colliding forms is taken; crates.io
+    /// treats names equal under [`crate::utils::names_collide`] (such as `Foo-Bar` and
+    /// `foo_bar`) as the same name.
+    pub async fn is_name_available(&self, name: &str) -> Result<bool> {
+        if self.validate_crate_name(name).is_err() {
+            return Ok(false);
+        }
+
+        let normalized = normalize_crate_name(name);
+        let mut candidates = vec![name.to_string()];
+        for variant in [normalized.replace('_', "-"), normalized] {
+            if !candidates.contains(&variant) {
+                candidates.push(variant);
+            }
+        }
+        for candidate in candidates {
+            if self.crate_exists(&candidate).await? {
+                debug!("Name '{}' is taken by '{}'", name, candidate);
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Check if a crate exists with a single HEAD request, without fetching its metadata
+    pub async fn crate_exists_head(&self, crate_name: &str) -> Result<bool> {
+        self.validate_crate_name(crate_name)?;
+
+        let url = format!("{}/crates/{}", self.base_url, crate_name);
+        debug!("Checking if crate exists (HEAD): {}", crate_name);
+
+        let mut response = self.send_request(Method::HEAD, &url, None).await?;
+        if matches!(
+            response.status,
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+        ) {
+            // Registry does not honor HEAD; the status of a GET is all we need
+            debug!("HEAD not supported, falling back to GET: {}", crate_name);
+            response = self.send_request(Method::GET, &url, None).await?;
+        }
+        match response.status {
+            StatusCode::OK => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            status => Err(CrateCheckerError::from(status)),
+        }
+    }
+
+    /// Check which of several crates exist, issuing HEAD requests concurrently
+    pub async fn crates_exist(&self, names: &[String]) -> Result<HashMap<String, bool>> {
+        for name in names {
+            self.validate_crate_name(name)?;
+        }
+
+        stream::iter(names.to_vec())
+            .map(|name| async move {
+                let exists = if self.offline_index.is_some() {
+                    self.crate_exists(&name).await?
+                } else {
+                    self.crate_exists_head(&name).await?
+                };
+                Ok((name, exists))
+            })
+            .buffer_unordered(self.max_concurrent)
+            .collect::<Vec<Result<_>>>()
+            .await
+            .into_iter()
+            .collect()
+    }
+
     /// Get the latest version of a crate
+    ///
+    /// A pre-release is only returned when the client includes pre-releases or the
+    /// crate has no stable release.
     pub async fn get_latest_version(&self, crate_name: &str) -> Result<String> {
+        if let Some(index) = &self.offline_index {
+            self.validate_crate_name(crate_name)?;
+            return index.latest_version(crate_name);
+        }
+
         let info = self.get_crate_info(crate_name).await?;
-        Ok(info.newest_version)
+        self.listed_latest_version(crate_name, info.newest_version)
+            .await
+    }
+
+    /// `newest` unless it is a pre-release the client hides, in which case the newest
+    /// stable release (or `newest` itself when the crate has no stable release)
+    async fn listed_latest_version(&self, crate_name: &str, newest: String) -> Result<String> {
+        if self.is_listed(&newest) {
+            return Ok(newest);
+        }
+
+        let versions = self.get_all_versions(crate_name).await?;
+        Ok(latest_stable_version(&versions).unwrap_or(newest))
+    }
+
+    /// Get the highest non-prerelease, non-yanked version of a crate
+    ///
+    /// Unlike [`get_latest_version`](Self::get_latest_version), never returns a pre-release.
+    pub async fn get_latest_stable_version(&self, crate_name: &str) -> Result<String> {
+        let versions = self.get_all_versions(crate_name).await?;
+        latest_stable_version(&versions).ok_or_else(|| CrateCheckerError::VersionNotFound {
+            crate_name: crate_name.to_string(),
+            version: "latest-stable".to_string(),
+        })
     }
 
     /// Get detailed information about a crate
     pub async fn get_crate_info(&self, crate_name: &str) -> Result<CrateInfo> {
+        self.fetch_crate_info(crate_name, None).await
+    }
+
+    /// Fetch crate information, optionally overriding the request timeout
+    async fn fetch_crate_info(
+        &self,
+        crate_name: &str,
+        timeout: Option<Duration>,
+    ) -> Result<CrateInfo> {
         self.validate_crate_name(crate_name)?;
 
         let url = format!("{}/crates/{}", self.base_url, crate_name);
         debug!("Fetching crate info for: {}", crate_name);
 
-        let response = self.client.get(&url).send().await?;
+        let (status, body) = self.get_body(&url, timeout).await?;
 
-        match response.status() {
+        match status {
             StatusCode::OK => {
-                let crate_response: CrateResponse = response.json().await?;
+                let crate_response: CrateResponse = self.decode_body(&body)?;
+                if let Some(links) = &crate_response.crate_info.links {
+                    self.remember_link(format!("versions:{}", crate_name), &links.versions);
+                    self.remember_link(
+                        format!("downloads:{}", crate_name),
+                        &links.version_downloads,
+                    );
+                    self.remember_link(
+                        format!("reverse_dependencies:{}", crate_name),
+                        &links.reverse_dependencies,
+                    );
+                }
+                if let Some(versions) = &crate_response.versions {
+                    self.remember_version_links(crate_name, versions);
+                }
+                let newest = newest_version(&crate_response)
+                    .map(|v| (v.license.clone(), v.rust_version.clone(), v.edition.clone()));
                 let mut crate_info = CrateInfo::from(crate_response.crate_info);
+                if let Some((license, msrv, edition)) = newest {
+                    crate_info.license = license;
+                    crate_info.msrv = msrv;
+                    crate_info.edition = edition;
+                }
 
                 // Populate keywords and categories
                 if let Some(keywords) = crate_response.keywords {
@@ -88,7 +778,7 @@ impl CrateClient {
                     crate_info.categories = categories.into_iter().map(|c| c.category).collect();
                 }
 
-                info!("Successfully fetched info for crate '{}'", crate_name);
+                debug!("Successfully fetched info for crate '{}'", crate_name);
                 Ok(crate_info)
             }
             StatusCode::NOT_FOUND => Err(CrateCheckerError::CrateNotFound(crate_name.to_string())),
@@ -98,29 +788,81 @@ impl CrateClient {
 
     /// Get all versions of a crate
     pub async fn get_all_versions(&self, crate_name: &str) -> Result<Vec<Version>> {
+        self.fetch_all_versions(crate_name, None).await
+    }
+
+    /// Get the versions of a crate to show to users: all of them, without pre-releases
+    /// unless the client was built with [`CrateClientBuilder::include_prerelease`]
+    pub async fn list_versions(&self, crate_name: &str) -> Result<Vec<Version>> {
+        let mut versions = self.get_all_versions(crate_name).await?;
+        versions.retain(|v| self.is_listed(&v.num));
+        Ok(versions)
+    }
+
+    /// Get the metadata of a single published version of a crate
+    pub async fn get_version(&self, crate_name: &str, version: &str) -> Result<Version> {
         self.validate_crate_name(crate_name)?;
 
-        let url = format!("{}/crates/{}/versions", self.base_url, crate_name);
-        debug!("Fetching versions for crate: {}", crate_name);
+        let url = format!("{}/crates/{}/{}", self.base_url, crate_name, version);
+        debug!("Fetching version info for {}:{}", crate_name, version);
 
-        let response = self.client.get(&url).send().await?;
+        let (status, body) = self.get_body(&url, None).await?;
 
-        match response.status() {
+        match status {
             StatusCode::OK => {
-                let versions_response: VersionsResponse = response.json().await?;
-                info!(
-                    "Found {} versions for crate '{}'",
-                    versions_response.versions.len(),
-                    crate_name
-                );
-                Ok(versions_response.versions)
+                let version_response: VersionResponse = self.decode_body(&body)?;
+                Ok(version_response.version)
             }
-            StatusCode::NOT_FOUND => Err(CrateCheckerError::CrateNotFound(crate_name.to_string())),
+            StatusCode::NOT_FOUND => Err(CrateCheckerError::VersionNotFound {
+                crate_name: crate_name.to_string(),
+                version: version.to_string(),
+            }),
             status => Err(CrateCheckerError::from(status)),
         }
     }
 
+    /// Get the feature flags of a crate version, each mapped to what it enables
+    ///
+    /// The `default` feature is included when the version declares one.
+    pub async fn get_crate_features(
+        &self,
+        crate_name: &str,
+        version: &str,
+    ) -> Result<HashMap<String, Vec<String>>> {
+        let version = self.get_version(crate_name, version).await?;
+        Ok(version.features.unwrap_or_default())
+    }
+
+    /// Fetch all versions of a crate, optionally overriding the request timeout
+    async fn fetch_all_versions(
+        &self,
+        crate_name: &str,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Version>> {
+        self.validate_crate_name(crate_name)?;
+
+        let url = self.api_url(&format!("versions:{}", crate_name), || {
+            format!("{}/crates/{}/versions", self.base_url, crate_name)
+        });
+        debug!("Fetching versions for crate: {}", crate_name);
+
+        let versions = self
+            .paginate::<VersionsResponse>(url, None, timeout, || {
+                CrateCheckerError::CrateNotFound(crate_name.to_string())
+            })
+            .await?;
+        info!(
+            "Found {} versions for crate '{}'",
+            versions.len(),
+            crate_name
+        );
+        self.remember_version_links(crate_name, &versions);
+        Ok(versions)
+    }
+
     /// Search for crates by name or keywords
+    ///
+    /// Without a limit only the first page of results is returned.
     pub async fn search_crates(
         &self,
         query: &str,
@@ -142,20 +884,197 @@ impl CrateClient {
             query, limit
         );
 
-        let response = self.client.get(&url).send().await?;
+        let cap = limit.unwrap_or(DEFAULT_SEARCH_PAGE_SIZE);
+        let crates = self
+            .paginate::<SearchResponse>(url, Some(cap), self.search_timeout, || {
+                CrateCheckerError::from(StatusCode::NOT_FOUND)
+            })
+            .await?;
+        info!(
+            "Search found {} results for query '{}'",
+            crates.len(),
+            query
+        );
+        Ok(crates)
+    }
 
-        match response.status() {
-            StatusCode::OK => {
-                let search_response: SearchResponse = response.json().await?;
-                info!(
-                    "Search found {} results for query '{}'",
-                    search_response.crates.len(),
-                    query
-                );
-                Ok(search_response.crates)
+    /// List the crates in a category, most downloaded first
+    ///
+    /// `category` is a crates.io category slug such as `command-line-utilities`; an
+    /// unknown slug is reported as [`CrateCheckerError::CategoryNotFound`]. `limit`
+    /// defaults to one page of 10 crates.
+    pub async fn list_category(
+        &self,
+        category: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<CrateSearchResult>> {
+        self.list_tagged("categories", "category", category, limit, || {
+            CrateCheckerError::CategoryNotFound(category.to_string())
+        })
+        .await
+    }
+
+    /// List the crates tagged with a keyword, most downloaded first
+    ///
+    /// An unknown keyword is reported as [`CrateCheckerError::KeywordNotFound`].
+    /// `limit` defaults to one page of 10 crates.
+    pub async fn list_keyword(
+        &self,
+        keyword: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<CrateSearchResult>> {
+        self.list_tagged("keywords", "keyword", keyword, limit, || {
+            CrateCheckerError::KeywordNotFound(keyword.to_string())
+        })
+        .await
+    }
+
+    /// List the crates whose `filter` (category or keyword) is `tag`
+    ///
+    /// crates.io answers a listing for an unknown tag with no crates, so the tag is
+    /// first looked up under `/{resource}/{tag}` to tell the two apart.
+    async fn list_tagged(
+        &self,
+        resource: &str,
+        filter: &str,
+        tag: &str,
+        limit: Option<usize>,
+        not_found: impl Fn() -> CrateCheckerError,
+    ) -> Result<Vec<CrateSearchResult>> {
+        if tag.trim().is_empty() {
+            return Err(CrateCheckerError::validation(format!(
+                "The {} to list cannot be empty",
+                filter
+            )));
+        }
+        let tag = urlencoding::encode(tag);
+
+        let (status, _) = self
+            .get_body(&format!("{}/{}/{}", self.base_url, resource, tag), None)
+            .await?;
+        match status {
+            StatusCode::OK => {}
+            StatusCode::NOT_FOUND => return Err(not_found()),
+            status => return Err(CrateCheckerError::from(status)),
+        }
+
+        let cap = limit.unwrap_or(DEFAULT_SEARCH_PAGE_SIZE);
+        let url = format!(
+            "{}/crates?{}={}&sort=downloads&per_page={}",
+            self.base_url,
+            filter,
+            tag,
+            cap.clamp(1, 100)
+        );
+        debug!("Listing crates with {} '{}'", filter, tag);
+
+        let crates = self
+            .paginate::<SearchResponse>(url, Some(cap), self.search_timeout, not_found)
+            .await?;
+        info!("Found {} crates with {} '{}'", crates.len(), filter, tag);
+        Ok(crates)
+    }
+
+    /// Fetch one page of search results, with the total match count for further paging
+    ///
+    /// crates.io pages search results by page number, so an `offset` that is not a
+    /// multiple of `limit` is served by reading the overlapping pages.
+    pub async fn search_page(&self, params: &SearchParams) -> Result<SearchPage> {
+        if params.q.trim().is_empty() {
+            return Err(CrateCheckerError::validation(
+                "Search query cannot be empty",
+            ));
+        }
+
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_PAGE_SIZE)
+            .clamp(1, 100);
+        let offset = params.offset.unwrap_or(0);
+        let skip = offset % limit;
+        let mut base = format!(
+            "{}/crates?q={}&per_page={}",
+            self.base_url,
+            urlencoding::encode(&params.q),
+            limit
+        );
+        if let Some(sort) = params.sort {
+            base.push_str(&format!("&sort={}", sort.as_str()));
+        }
+        debug!(
+            "Searching crates with query: '{}', offset: {}, limit: {}",
+            params.q, offset, limit
+        );
+
+        let mut crates = Vec::new();
+        let mut total = 0;
+        for page in (offset / limit + 1).. {
+            let url = format!("{}&page={}", base, page);
+            let (status, body) = self.get_body(&url, self.search_timeout).await?;
+            let response: SearchResponse = match status {
+                StatusCode::OK => self.decode_body(&body)?,
+                status => return Err(CrateCheckerError::from(status)),
+            };
+
+            total = u64::from(response.meta.total);
+            let received = response.crates.len();
+            crates.extend(response.crates);
+            if crates.len() >= skip + limit || received < limit {
+                break;
             }
-            status => Err(CrateCheckerError::from(status)),
         }
+        let crates: Vec<CrateSearchResult> = crates.into_iter().skip(skip).take(limit).collect();
+
+        info!(
+            "Search returned {} of {} results for query '{}'",
+            crates.len(),
+            total,
+            params.q
+        );
+        Ok(SearchPage { crates, total })
+    }
+
+    /// Collect the items of a paginated listing starting at `url`, following `meta.next_page`
+    ///
+    /// Every page goes through [`Self::send_request`], so rate-limited and failed pages
+    /// are retried with backoff. Stops once `cap` items have been collected; a 404 on
+    /// any page is reported with `not_found`.
+    async fn paginate<P: Paginated>(
+        &self,
+        url: String,
+        cap: Option<usize>,
+        timeout: Option<Duration>,
+        not_found: impl Fn() -> CrateCheckerError,
+    ) -> Result<Vec<P::Item>> {
+        let mut items = Vec::new();
+        let mut next = Some(url);
+
+        while let Some(url) = next.take() {
+            let (status, body) = self.get_body(&url, timeout).await?;
+            let page: P = match status {
+                StatusCode::OK => self.decode_body(&body)?,
+                StatusCode::NOT_FOUND => return Err(not_found()),
+                status => return Err(CrateCheckerError::from(status)),
+            };
+
+            next = page
+                .next_page()
+                .map(|next_page| next_page_url(&url, next_page))
+                .filter(|next_url| *next_url != url);
+            items.extend(page.into_items());
+
+            if let Some(cap) = cap {
+                if items.len() >= cap {
+                    items.truncate(cap);
+                    break;
+                }
+            }
+            if next.is_some() {
+                debug!("Following next page of {}", url);
+            }
+        }
+
+        Ok(items)
     }
 
     /// Get dependencies for a specific crate version
@@ -166,17 +1085,19 @@ impl CrateClient {
     ) -> Result<Vec<Dependency>> {
         self.validate_crate_name(crate_name)?;
 
-        let url = format!(
-            "{}/crates/{}/{}/dependencies",
-            self.base_url, crate_name, version
-        );
+        let url = self.api_url(&format!("dependencies:{}@{}", crate_name, version), || {
+            format!(
+                "{}/crates/{}/{}/dependencies",
+                self.base_url, crate_name, version
+            )
+        });
         debug!("Fetching dependencies for {}:{}", crate_name, version);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_get(&url).await?;
 
-        match response.status() {
+        match response.status {
             StatusCode::OK => {
-                let deps_response: DependenciesResponse = response.json().await?;
+                let deps_response: DependenciesResponse = self.decode_body(&response.body)?;
                 info!(
                     "Found {} dependencies for {}:{}",
                     deps_response.dependencies.len(),
@@ -193,100 +1114,678 @@ impl CrateClient {
         }
     }
 
+    /// Get the crates that depend on a crate, most downloaded first
+    ///
+    /// `limit` defaults to one page of 10 dependents.
+    pub async fn get_reverse_dependencies(
+        &self,
+        crate_name: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<CrateSearchResult>> {
+        Ok(self
+            .get_reverse_dependency_report(crate_name, limit)
+            .await?
+            .dependents)
+    }
+
+    /// Get the crates that depend on a crate together with the total dependent count
+    ///
+    /// crates.io pages reverse dependencies by page number, so pages of up to 100
+    /// are requested until `limit` dependents (default 10) have been collected.
+    pub async fn get_reverse_dependency_report(
+        &self,
+        crate_name: &str,
+        limit: Option<usize>,
+    ) -> Result<ReverseDependencies> {
+        self.validate_crate_name(crate_name)?;
+
+        let limit = limit.unwrap_or(DEFAULT_SEARCH_PAGE_SIZE);
+        let per_page = limit.clamp(1, 100);
+        let base = self.api_url(&format!("reverse_dependencies:{}", crate_name), || {
+            format!(
+                "{}/crates/{}/reverse_dependencies",
+                self.base_url, crate_name
+            )
+        });
+        let separator = if base.contains('?') { '&' } else { '?' };
+        debug!("Fetching reverse dependencies for: {}", crate_name);
+
+        let mut dependents = Vec::new();
+        let mut total = 0;
+        for page in 1.. {
+            let url = format!("{}{}per_page={}&page={}", base, separator, per_page, page);
+            let response = self.send_get(&url).await?;
+            let page: ReverseDependenciesResponse = match response.status {
+                StatusCode::OK => self.decode_body(&response.body)?,
+                StatusCode::NOT_FOUND => {
+                    return Err(CrateCheckerError::CrateNotFound(crate_name.to_string()))
+                }
+                status => return Err(CrateCheckerError::from(status)),
+            };
+
+            total = page.meta.total.unwrap_or(0);
+            let received = page.dependencies.len();
+            let versions: HashMap<u64, &DependentVersionApi> =
+                page.versions.iter().map(|v| (v.id, v)).collect();
+            dependents.extend(page.dependencies.iter().filter_map(|dep| {
+                let version = versions.get(&dep.version_id)?;
+                Some(CrateSearchResult {
+                    name: version.crate_name.clone(),
+                    description: None,
+                    newest_version: version.num.clone(),
+                    downloads: dep.downloads,
+                    exact_match: false,
+                })
+            }));
+
+            if dependents.len() >= limit || received < per_page || dependents.len() as u64 >= total
+            {
+                break;
+            }
+        }
+        dependents.truncate(limit);
+        total = total.max(dependents.len() as u64);
+
+        info!(
+            "Found {} dependents of '{}' ({} total)",
+            dependents.len(),
+            crate_name,
+            total
+        );
+        Ok(ReverseDependencies {
+            crate_name: crate_name.to_string(),
+            total,
+            dependents,
+        })
+    }
+
     /// Get download statistics for a crate
     pub async fn get_download_stats(&self, crate_name: &str) -> Result<DownloadStats> {
         self.validate_crate_name(crate_name)?;
 
         // Get basic crate info which includes total downloads
-        let crate_info = self.get_crate_info(crate_name).await?;
+        let crate_info = self
+            .fetch_crate_info(crate_name, self.stats_timeout)
+            .await?;
         let total_downloads = crate_info.downloads;
 
-        // Get version-specific downloads from versions endpoint
-        let versions = match self.get_all_versions(crate_name).await {
-            Ok(mut versions) => {
-                // Sort by downloads descending to get most popular versions first
-                versions.sort_by(|a, b| b.downloads.cmp(&a.downloads));
+        // Get version-specific downloads from versions endpoint
+        let versions = match self
+            .fetch_all_versions(crate_name, self.stats_timeout)
+            .await
+        {
+            Ok(mut versions) => {
+                // Sort by downloads descending to get most popular versions first
+                versions.sort_by_key(|v| std::cmp::Reverse(v.downloads));
+
+                // Convert to VersionDownload format (take top 10)
+                versions
+                    .into_iter()
+                    .take(10)
+                    .map(|v| VersionDownload {
+                        version: v.num,
+                        downloads: v.downloads,
+                        date: v.created_at,
+                    })
+                    .collect()
+            }
+            Err(_) => Vec::new(),
+        };
+
+        let stats = DownloadStats {
+            total: total_downloads,
+            versions,
+        };
+
+        info!(
+            "Fetched download stats for '{}': {} total downloads, {} version entries",
+            crate_name,
+            total_downloads,
+            stats.versions.len()
+        );
+        Ok(stats)
+    }
+
+    /// Estimate how widely used a crate is from its all-time downloads
+    pub async fn popularity(&self, crate_name: &str) -> Result<Popularity> {
+        let info = self.get_crate_info(crate_name).await?;
+        Ok(Popularity::from_downloads(&info.name, info.downloads))
+    }
+
+    /// Get the daily download history of a crate, oldest day first
+    ///
+    /// The series sums per-version downloads and the `extra_downloads` bucket
+    /// crates.io reports for older versions. Responses are large, so they are
+    /// fetched gzip-encoded and, when the client has a response cache, kept there
+    /// for the [history TTL](CrateClientBuilder::history_cache_ttl).
+    pub async fn get_download_history(&self, crate_name: &str) -> Result<Vec<(NaiveDate, u64)>> {
+        self.validate_crate_name(crate_name)?;
+
+        let url = self.api_url(&format!("downloads:{}", crate_name), || {
+            format!("{}/crates/{}/downloads", self.base_url, crate_name)
+        });
+        let cached = self
+            .response_cache
+            .as_ref()
+            .and_then(|cache| cache.get(&url));
+        let (status, body) = match cached {
+            Some(body) => {
+                debug!("Using cached download history for: {}", crate_name);
+                (StatusCode::OK, body)
+            }
+            None => {
+                debug!("Fetching download history for: {}", crate_name);
+                let response = self
+                    .send_request(Method::GET, &url, self.stats_timeout)
+                    .await?;
+                if response.status == StatusCode::OK {
+                    if let Some(cache) = &self.response_cache {
+                        let ttl = self.history_ttl.unwrap_or(cache.ttl);
+                        cache.insert_for(&url, response.body.clone(), ttl);
+                    }
+                }
+                (response.status, response.body)
+            }
+        };
+
+        match status {
+            StatusCode::OK => {
+                let downloads: DownloadsResponse = self.decode_body(&body)?;
+
+                let mut daily: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+                let entries = downloads
+                    .version_downloads
+                    .iter()
+                    .map(|d| (d.date.as_str(), d.downloads))
+                    .chain(
+                        downloads
+                            .meta
+                            .extra_downloads
+                            .iter()
+                            .map(|d| (d.date.as_str(), d.downloads)),
+                    );
+                for (date, count) in entries {
+                    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| {
+                        CrateCheckerError::validation(format!(
+                            "Invalid download date '{}': {}",
+                            date, e
+                        ))
+                    })?;
+                    *daily.entry(date).or_insert(0) += count;
+                }
+
+                let series: Vec<(NaiveDate, u64)> = daily.into_iter().collect();
+                info!(
+                    "Fetched {} days of download history for '{}'",
+                    series.len(),
+                    crate_name
+                );
+
+                Ok(series)
+            }
+            StatusCode::NOT_FOUND => Err(CrateCheckerError::CrateNotFound(crate_name.to_string())),
+            status => Err(CrateCheckerError::from(status)),
+        }
+    }
+
+    /// Check the status of a crate (exists, yanked, etc.)
+    pub async fn check_crate_status(&self, crate_name: &str) -> Result<CrateStatus> {
+        Ok(self.crate_status_report(crate_name).await?.status)
+    }
+
+    /// Whether every published version of a crate is yanked
+    ///
+    /// Answers from the local index in offline mode.
+    pub async fn is_yanked(&self, crate_name: &str) -> Result<bool> {
+        if let Some(index) = &self.offline_index {
+            self.validate_crate_name(crate_name)?;
+            return Ok(index.versions(crate_name)?.iter().all(|v| v.yanked));
+        }
+
+        Ok(self.check_crate_status(crate_name).await? == CrateStatus::Yanked)
+    }
+
+    /// Check the status of a crate and list its yanked versions
+    pub async fn crate_status_report(&self, crate_name: &str) -> Result<CrateStatusReport> {
+        let versions = match self.get_all_versions(crate_name).await {
+            Ok(versions) => versions,
+            Err(CrateCheckerError::CrateNotFound(_)) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(CrateStatusReport {
+            crate_name: crate_name.to_string(),
+            status: CrateStatus::from_versions(&versions),
+            yanked_versions: versions
+                .iter()
+                .filter(|v| v.yanked)
+                .map(|v| v.num.clone())
+                .collect(),
+        })
+    }
+
+    /// Validate crate name format
+    pub fn validate_crate_name(&self, name: &str) -> Result<()> {
+        const PATTERN: &str = "^[a-zA-Z0-9_-]+$";
+
+        if name.is_empty() {
+            return Err(CrateCheckerError::InvalidCrateName(
+                name.to_string(),
+                "Crate name cannot be empty",
+            ));
+        }
+
+        if name.len() > 64 {
+            return Err(CrateCheckerError::InvalidCrateName(
+                name.to_string(),
+                "Crate name cannot be longer than 64 characters",
+            ));
+        }
+
+        // Basic validation - crate names can contain letters, numbers, hyphens, and underscores
+        if !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(CrateCheckerError::InvalidCrateName(
+                name.to_string(),
+                PATTERN,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Fetch information for several crates concurrently, preserving input order
+    pub async fn get_crate_infos(&self, crates: Vec<String>) -> Result<Vec<CrateInfo>> {
+        stream::iter(crates)
+            .map(|crate_name| async move { self.get_crate_info(&crate_name).await })
+            .buffered(self.max_concurrent)
+            .collect::<Vec<Result<_>>>()
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    /// Resolve the latest version of each crate concurrently, preserving input order
+    pub async fn get_latest_versions(&self, crates: Vec<String>) -> Result<Vec<(String, String)>> {
+        stream::iter(crates)
+            .map(|crate_name| async move {
+                let version = self.get_latest_version(&crate_name).await?;
+                Ok((crate_name, version))
+            })
+            .buffered(self.max_concurrent)
+            .collect::<Vec<Result<_>>>()
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    /// Resolve every version or requirement of a version map to a concrete version
+    ///
+    /// Crates are fetched concurrently; the plan is sorted by crate name.
+    pub async fn resolve_version_map(
+        &self,
+        versions: HashMap<String, String>,
+    ) -> Vec<VersionPlanEntry> {
+        let mut plan: Vec<VersionPlanEntry> = stream::iter(versions)
+            .map(|(crate_name, requested)| async move {
+                let (resolved, error) = match self.get_all_versions(&crate_name).await {
+                    Ok(published) => match resolve_requirement(&requested, &published) {
+                        Some(resolved) => (Some(resolved), None),
+                        None => (None, Some(format!("No version matches '{}'", requested))),
+                    },
+                    Err(e) => (None, Some(e.to_string())),
+                };
+                VersionPlanEntry {
+                    crate_name,
+                    requested,
+                    resolved,
+                    error,
+                }
+            })
+            .buffer_unordered(self.max_concurrent)
+            .collect()
+            .await;
+
+        plan.sort_by(|a, b| a.crate_name.cmp(&b.crate_name));
+        plan
+    }
+
+    /// Resolve the transitive dependency tree of a crate, `max_depth` levels deep
+    ///
+    /// `version` may be a concrete version, a channel such as `latest`, or a requirement.
+    /// Only non-optional normal and build dependencies are followed, each resolved to the
+    /// highest non-yanked version matching its requirement. The tree is fetched level by
+    /// level, bounded by `max_concurrent`, and every crate's versions and every crate
+    /// version's dependencies are fetched at most once per call. A crate version reached
+    /// more than once is expanded at its first occurrence and marked as a duplicate elsewhere.
+    pub async fn build_dependency_tree(
+        &self,
+        crate_name: &str,
+        version: &str,
+        max_depth: usize,
+    ) -> Result<DepNode> {
+        self.build_dependency_tree_filtered(crate_name, version, max_depth, false)
+            .await
+    }
+
+    /// Like [`build_dependency_tree`](Self::build_dependency_tree), following only normal
+    /// dependencies when `runtime_only` is set, so build dependencies are pruned too
+    pub async fn build_dependency_tree_filtered(
+        &self,
+        crate_name: &str,
+        version: &str,
+        max_depth: usize,
+        runtime_only: bool,
+    ) -> Result<DepNode> {
+        let published = self.get_all_versions(crate_name).await?;
+        let root_version = resolve_requirement(version, &published).ok_or_else(|| {
+            CrateCheckerError::VersionNotFound {
+                crate_name: crate_name.to_string(),
+                version: version.to_string(),
+            }
+        })?;
+        let root = (crate_name.to_string(), root_version);
+        info!(
+            "Building dependency tree for {}@{} ({} levels)",
+            root.0, root.1, max_depth
+        );
+
+        let mut versions_cache: HashMap<String, Option<Vec<Version>>> =
+            HashMap::from([(crate_name.to_string(), Some(published))]);
+        let mut children: HashMap<(String, String), Vec<ResolvedDependency>> = HashMap::new();
+        let mut queued = HashSet::from([root.clone()]);
+        let mut frontier = vec![root.clone()];
+
+        for depth in 0..max_depth {
+            if frontier.is_empty() {
+                break;
+            }
+
+            let fetched: Vec<_> = stream::iter(std::mem::take(&mut frontier))
+                .map(|key| async move {
+                    let deps = self.get_crate_dependencies(&key.0, &key.1).await;
+                    (key, deps)
+                })
+                .buffered(self.max_concurrent)
+                .collect()
+                .await;
+
+            let mut level = Vec::with_capacity(fetched.len());
+            for (key, deps) in fetched {
+                match deps {
+                    Ok(mut deps) => {
+                        let mut names = HashSet::new();
+                        deps.retain(|d| {
+                            let followed = match d.kind.as_str() {
+                                "normal" => true,
+                                "build" => !runtime_only,
+                                _ => false,
+                            };
+                            followed && !d.optional && names.insert(d.name.clone())
+                        });
+                        level.push((key, deps));
+                    }
+                    Err(e) if depth == 0 => return Err(e),
+                    Err(e) => {
+                        warn!("Failed to fetch dependencies of {}@{}: {}", key.0, key.1, e);
+                        children.insert(key, Vec::new());
+                    }
+                }
+            }
+
+            let missing: BTreeSet<String> = level
+                .iter()
+                .flat_map(|(_, deps)| deps.iter().map(|d| d.name.clone()))
+                .filter(|name| !versions_cache.contains_key(name))
+                .collect();
+            let fetched_versions: Vec<_> = stream::iter(missing)
+                .map(|name| async move {
+                    let versions = self.get_all_versions(&name).await;
+                    (name, versions)
+                })
+                .buffer_unordered(self.max_concurrent)
+                .collect()
+                .await;
+            for (name, versions) in fetched_versions {
+                if let Err(e) = &versions {
+                    warn!("Failed to fetch versions of {}: {}", name, e);
+                }
+                versions_cache.insert(name, versions.ok());
+            }
 
-                // Convert to VersionDownload format (take top 10)
-                versions
+            for (key, deps) in level {
+                let resolved: Vec<ResolvedDependency> = deps
                     .into_iter()
-                    .take(10)
-                    .map(|v| VersionDownload {
-                        version: v.num,
-                        downloads: v.downloads,
-                        date: v.created_at,
+                    .map(|dep| {
+                        let version = versions_cache
+                            .get(&dep.name)
+                            .and_then(|versions| versions.as_deref())
+                            .and_then(|versions| resolve_requirement(&dep.req, versions));
+                        ResolvedDependency {
+                            name: dep.name,
+                            requirement: dep.req,
+                            kind: dep.kind,
+                            optional: dep.optional,
+                            version,
+                        }
                     })
-                    .collect()
+                    .collect();
+
+                for dep in &resolved {
+                    if let Some(version) = &dep.version {
+                        let child = (dep.name.clone(), version.clone());
+                        if queued.insert(child.clone()) {
+                            frontier.push(child);
+                        }
+                    }
+                }
+                children.insert(key, resolved);
             }
-            Err(_) => Vec::new(),
-        };
+        }
 
-        let stats = DownloadStats {
-            total: total_downloads,
-            versions,
+        let mut assembly = TreeAssembly {
+            children: &children,
+            max_depth,
+            expanded: HashSet::new(),
         };
-
-        info!(
-            "Fetched download stats for '{}': {} total downloads, {} version entries",
-            crate_name,
-            total_downloads,
-            stats.versions.len()
-        );
-        Ok(stats)
+        Ok(assembly.node(root.0, Some(root.1), 0))
     }
 
-    /// Check the status of a crate (exists, yanked, etc.)
-    pub async fn check_crate_status(&self, crate_name: &str) -> Result<CrateStatus> {
-        match self.get_all_versions(crate_name).await {
-            Ok(versions) => {
-                if versions.is_empty() {
-                    Ok(CrateStatus::NotFound)
-                } else {
-                    let yanked_count = versions.iter().filter(|v| v.yanked).count();
-                    if yanked_count == versions.len() {
-                        Ok(CrateStatus::Yanked)
-                    } else if yanked_count > 0 {
-                        Ok(CrateStatus::PartiallyYanked)
-                    } else {
-                        Ok(CrateStatus::Exists)
-                    }
+    /// Compare `name -> requirement` manifest dependencies with their latest releases
+    ///
+    /// Crates are fetched concurrently; results keep the map's (name) order.
+    pub async fn check_outdated(
+        &self,
+        dependencies: BTreeMap<String, String>,
+    ) -> Vec<OutdatedDependency> {
+        info!("Checking {} dependencies for updates", dependencies.len());
+
+        stream::iter(dependencies)
+            .map(|(name, requirement)| async move {
+                match self.get_latest_version(&name).await {
+                    Ok(latest) => OutdatedDependency {
+                        update: UpdateKind::between(&requirement, &latest),
+                        latest: Some(latest),
+                        name,
+                        requirement,
+                        error: None,
+                    },
+                    Err(e) => OutdatedDependency {
+                        name,
+                        requirement,
+                        latest: None,
+                        update: None,
+                        error: Some(e.to_string()),
+                    },
                 }
+            })
+            .buffered(self.max_concurrent)
+            .collect()
+            .await
+    }
+
+    /// Check locked `(name, version)` pairs for yanked or unpublished versions
+    ///
+    /// Crates are fetched concurrently; findings are returned in input order.
+    pub async fn check_locked_versions(
+        &self,
+        packages: Vec<(String, String)>,
+    ) -> Result<Vec<LockFinding>> {
+        info!("Checking {} locked packages", packages.len());
+
+        let checked: Vec<Result<Option<LockFinding>>> = stream::iter(packages)
+            .map(|(crate_name, version)| async move {
+                let versions = self.get_all_versions(&crate_name).await?;
+                let kind = match versions.iter().find(|v| v.num == version) {
+                    Some(v) if v.yanked => Some(LockFindingKind::YankedVersion),
+                    Some(_) => None,
+                    None => Some(LockFindingKind::UnknownVersion),
+                };
+                Ok(kind.map(|kind| LockFinding {
+                    crate_name,
+                    version,
+                    kind,
+                }))
+            })
+            .buffered(self.max_concurrent)
+            .collect()
+            .await;
+
+        let mut findings = Vec::new();
+        for result in checked {
+            if let Some(finding) = result? {
+                findings.push(finding);
             }
-            Err(CrateCheckerError::CrateNotFound(_)) => Ok(CrateStatus::NotFound),
-            Err(e) => Err(e),
         }
+        Ok(findings)
     }
 
-    /// Validate crate name format
-    pub fn validate_crate_name(&self, name: &str) -> Result<()> {
-        const PATTERN: &str = "^[a-zA-Z0-9_-]+$";
-
-        if name.is_empty() {
-            return Err(CrateCheckerError::InvalidCrateName(
-                name.to_string(),
-                "Crate name cannot be empty",
+    /// Measure throughput and latency of existence checks against the API
+    ///
+    /// Cycles through `crates`, keeping up to `concurrency` requests in flight until
+    /// `limit` is reached. Missing crates count as successful requests.
+    pub async fn bench(
+        &self,
+        crates: &[String],
+        concurrency: usize,
+        limit: BenchLimit,
+    ) -> Result<BenchReport> {
+        if crates.is_empty() {
+            return Err(CrateCheckerError::validation(
+                "At least one crate name is required to benchmark",
             ));
         }
+        let concurrency = concurrency.max(1);
+        info!(
+            "Benchmarking with {:?} at concurrency {}",
+            limit, concurrency
+        );
 
-        if name.len() > 64 {
-            return Err(CrateCheckerError::InvalidCrateName(
-                name.to_string(),
-                "Crate name cannot be longer than 64 characters",
-            ));
+        let start = Instant::now();
+        let names = stream::iter(crates.iter().cycle());
+        let names = match limit {
+            BenchLimit::Requests(count) => names.take(count).left_stream(),
+            BenchLimit::Duration(duration) => names
+                .take_while(move |_| futures::future::ready(start.elapsed() < duration))
+                .right_stream(),
+        };
+
+        let samples: Vec<(Duration, bool)> = names
+            .map(|crate_name| async move {
+                let started = Instant::now();
+                let ok = self.crate_exists(crate_name).await.is_ok();
+                (started.elapsed(), ok)
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+        let elapsed = start.elapsed();
+
+        let errors = samples.iter().filter(|(_, ok)| !ok).count();
+        let latencies = samples.into_iter().map(|(latency, _)| latency).collect();
+        Ok(BenchReport::from_samples(
+            latencies,
+            errors,
+            concurrency,
+            elapsed,
+        ))
+    }
+
+    /// Build an aggregate report for a set of crates, fetching them concurrently
+    pub async fn generate_report(&self, crates: Vec<String>) -> Result<EcosystemReport> {
+        info!("Generating ecosystem report for {} crates", crates.len());
+
+        let fetched: Vec<_> = stream::iter(crates)
+            .map(|crate_name| async move {
+                let result = futures::try_join!(
+                    self.get_crate_info(&crate_name),
+                    self.get_all_versions(&crate_name)
+                );
+                (crate_name, result)
+            })
+            .buffered(self.max_concurrent)
+            .collect()
+            .await;
+
+        let mut crates = Vec::with_capacity(fetched.len());
+        let mut failed = Vec::new();
+        for (crate_name, result) in fetched {
+            match result {
+                Ok(entry) => crates.push(entry),
+                Err(e) => {
+                    warn!("Skipping crate '{}' in report: {}", crate_name, e);
+                    failed.push(crate_name);
+                }
+            }
         }
 
-        // Basic validation - crate names can contain letters, numbers, hyphens, and underscores
-        if !name
-            .chars()
-            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
-        {
-            return Err(CrateCheckerError::InvalidCrateName(
-                name.to_string(),
-                PATTERN,
-            ));
+        Ok(EcosystemReport::from_crates(
+            &crates,
+            failed,
+            chrono::Utc::now(),
+        ))
+    }
+
+    /// Compare the direct dependencies of several crates' newest versions
+    ///
+    /// Reports the dependencies they share and those whose requirements would pull
+    /// in semver-incompatible copies. Crates that cannot be fetched are listed in
+    /// `failed` rather than failing the whole analysis.
+    pub async fn analyze_dependencies(&self, crates: Vec<String>) -> Result<DependencyReport> {
+        info!("Analyzing dependencies of {} crates", crates.len());
+
+        let fetched: Vec<_> = stream::iter(crates)
+            .map(|crate_name| async move {
+                let result = async {
+                    let version = self.get_latest_version(&crate_name).await?;
+                    let deps = self.get_crate_dependencies(&crate_name, &version).await?;
+                    Ok::<_, CrateCheckerError>((version, deps))
+                }
+                .await;
+                (crate_name, result)
+            })
+            .buffered(self.max_concurrent)
+            .collect()
+            .await;
+
+        let mut analyzed = Vec::with_capacity(fetched.len());
+        let mut failed = Vec::new();
+        for (crate_name, result) in fetched {
+            match result {
+                Ok((version, deps)) => analyzed.push((crate_name, version, deps)),
+                Err(e) => {
+                    warn!(
+                        "Skipping crate '{}' in dependency analysis: {}",
+                        crate_name, e
+                    );
+                    failed.push(crate_name);
+                }
+            }
         }
 
-        Ok(())
+        Ok(DependencyReport::from_dependencies(analyzed, failed))
     }
 
     /// Process a batch of crate checks
@@ -307,103 +1806,193 @@ impl CrateClient {
         Ok(results)
     }
 
-    /// Process a crate version map
-    pub async fn process_crate_version_map(
+    /// Process a batch of crate checks concurrently, bounded by `max_concurrent`
+    ///
+    /// Results are returned in input order, exactly as [`Self::process_crate_list`] would.
+    pub async fn process_crate_list_concurrent(
         &self,
-        input: HashMap<String, String>,
-    ) -> Result<BatchResult> {
+        crates: Vec<String>,
+    ) -> Result<Vec<CrateCheckResult>> {
+        info!(
+            "Processing batch of {} crates ({} at a time)",
+            crates.len(),
+            self.max_concurrent
+        );
         let start_time = Instant::now();
-        let total_count = input.len();
-
-        info!("Processing crate version map with {} entries", total_count);
 
-        let mut results = Vec::with_capacity(total_count);
-        let mut successful = 0;
-        let mut failed = 0;
+        let results =
+            stream::iter(crates)
+                .map(|crate_name| async move {
+                    self.process_single_crate_check(&crate_name, None).await
+                })
+                .buffered(self.max_concurrent)
+                .collect()
+                .await;
 
-        for (crate_name, version) in input.iter() {
-            let version_opt = if version == "latest" {
-                None
-            } else {
-                Some(version.clone())
-            };
+        let duration = start_time.elapsed();
+        info!("Batch processing completed in {:?}", duration);
 
-            let result = self
-                .process_single_crate_check(crate_name, version_opt)
-                .await;
+        Ok(results)
+    }
 
-            if result.error.is_none() && result.exists {
-                successful += 1;
-            } else {
-                failed += 1;
+    /// Process a batch of crate checks concurrently, then re-run the checks that errored
+    ///
+    /// Each of up to `max_passes` retry passes re-checks only the results whose outcome is
+    /// [`CheckOutcome::Error`], replacing them in place; missing crates are a definite answer
+    /// and never retried. Passes stop early once nothing is left to retry.
+    pub async fn process_crate_list_with_retry(
+        &self,
+        crates: Vec<String>,
+        max_passes: usize,
+    ) -> Result<RetriedBatchResult> {
+        let start_time = Instant::now();
+        let total_processed = crates.len();
+        let mut results = self.process_crate_list_concurrent(crates).await?;
+        let mut recovered = 0;
+        let mut retry_passes = 0;
+
+        while retry_passes < max_passes {
+            let errored: Vec<usize> = results
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| r.outcome == CheckOutcome::Error)
+                .map(|(index, _)| index)
+                .collect();
+            if errored.is_empty() {
+                break;
+            }
+            retry_passes += 1;
+            info!(
+                "Retry pass {} of {}: re-checking {} failed crates",
+                retry_passes,
+                max_passes,
+                errored.len()
+            );
+
+            let names = errored
+                .iter()
+                .map(|&index| results[index].crate_name.clone())
+                .collect();
+            let retried = self.process_crate_list_concurrent(names).await?;
+            for (index, result) in errored.into_iter().zip(retried) {
+                if result.outcome != CheckOutcome::Error {
+                    recovered += 1;
+                }
+                results[index] = result;
             }
+        }
 
-            results.push(result);
+        Ok(RetriedBatchResult {
+            result: BatchResult::new(
+                results,
+                total_processed,
+                start_time.elapsed().as_millis() as u64,
+            ),
+            recovered,
+            retry_passes,
+        })
+    }
+
+    /// Process a crate version map
+    pub async fn process_crate_version_map(
+        &self,
+        input: HashMap<String, String>,
+    ) -> Result<BatchResult> {
+        let start_time = Instant::now();
+        info!("Processing crate version map with {} entries", input.len());
+
+        let mut results = Vec::with_capacity(input.len());
+        for (crate_name, version) in version_map_checks(input) {
+            results.push(self.process_single_crate_check(&crate_name, version).await);
         }
 
-        let processing_time_ms = start_time.elapsed().as_millis() as u64;
+        Ok(version_map_result(results, start_time))
+    }
+
+    /// Process a crate version map concurrently, bounded by `max_concurrent`
+    ///
+    /// Results are returned in the map's iteration order, exactly as
+    /// [`Self::process_crate_version_map`] would.
+    pub async fn process_crate_version_map_concurrent(
+        &self,
+        input: HashMap<String, String>,
+    ) -> Result<BatchResult> {
+        self.process_crate_version_map_with_progress(input, |_, _| {})
+            .await
+    }
 
+    /// Process a crate version map concurrently, calling `on_progress(processed, total)`
+    /// each time a crate check completes
+    ///
+    /// The callback runs on the calling task, in completion order, so it needs to be
+    /// neither `Send` nor `Sync`. Results are ordered as with
+    /// [`Self::process_crate_version_map_concurrent`].
+    pub async fn process_crate_version_map_with_progress(
+        &self,
+        input: HashMap<String, String>,
+        on_progress: impl Fn(usize, usize),
+    ) -> Result<BatchResult> {
+        let start_time = Instant::now();
+        let total = input.len();
         info!(
-            "Batch processing completed: {} total, {} successful, {} failed",
-            total_count, successful, failed
+            "Processing crate version map with {} entries ({} at a time)",
+            total, self.max_concurrent
         );
 
-        Ok(BatchResult {
-            results,
-            total_processed: total_count,
-            successful,
-            failed,
-            processing_time_ms,
-        })
+        let mut checks = stream::iter(version_map_checks(input).into_iter().enumerate())
+            .map(|(index, (crate_name, version))| async move {
+                (
+                    index,
+                    self.process_single_crate_check(&crate_name, version).await,
+                )
+            })
+            .buffer_unordered(self.max_concurrent);
+
+        let mut completed = Vec::with_capacity(total);
+        while let Some(check) = checks.next().await {
+            completed.push(check);
+            on_progress(completed.len(), total);
+        }
+        completed.sort_by_key(|(index, _)| *index);
+        let results = completed.into_iter().map(|(_, result)| result).collect();
+
+        Ok(version_map_result(results, start_time))
     }
 
-    /// Process batch operations
+    /// Process batch operations, checking up to `max_concurrent` crates at a time
+    ///
+    /// The crates of all operations are checked as one list, so a `Multiple` target
+    /// does not serialize its crates. Results keep operation and crate order;
+    /// `total_processed` counts operations and `total_crates_checked` counts crates.
     pub async fn process_batch_operations(
         &self,
         operations: Vec<BatchOperation>,
+        max_concurrent: usize,
     ) -> Result<BatchResponse> {
         let request_id = uuid::Uuid::new_v4().to_string();
         let start_time = Instant::now();
         let total_count = operations.len();
+        let checks = BatchInput::Operations { operations }.into_checks()?;
 
         info!(
-            "Processing {} batch operations (request: {})",
-            total_count, request_id
+            "Processing {} batch operations with {} crates, {} at a time (request: {})",
+            total_count,
+            checks.len(),
+            max_concurrent.max(1),
+            request_id
         );
 
-        let mut all_results = Vec::new();
-
-        for operation in &operations {
-            match &operation.target {
-                BatchTarget::Single {
-                    crate_name,
-                    version,
-                } => {
-                    let result = self
-                        .process_single_crate_check(crate_name, version.clone())
-                        .await;
-                    all_results.push(result);
-                }
-                BatchTarget::Multiple { crates } => {
-                    for crate_name in crates {
-                        let result = self.process_single_crate_check(crate_name, None).await;
-                        all_results.push(result);
-                    }
-                }
-            }
-        }
-
-        let successful = all_results.iter().filter(|r| r.error.is_none()).count();
-        let failed = all_results.len() - successful;
-        let processing_time_ms = start_time.elapsed().as_millis() as u64;
+        let all_results: Vec<CrateCheckResult> = stream::iter(checks)
+            .map(|check| self.process_batch_check(check))
+            .buffered(max_concurrent.max(1))
+            .collect()
+            .await;
 
-        let result = BatchResult {
-            results: all_results,
-            total_processed: total_count,
-            successful,
-            failed,
-            processing_time_ms,
-        };
+        let result = BatchResult::new(
+            all_results,
+            total_count,
+            start_time.elapsed().as_millis() as u64,
+        );
 
         Ok(BatchResponse {
             request_id,
@@ -412,6 +2001,79 @@ impl CrateClient {
         })
     }
 
+    /// Run batch checks up to `max_concurrent` at a time, yielding results in input order
+    /// as soon as each one and those before it have completed
+    pub fn check_crates(
+        &self,
+        checks: Vec<BatchCheck>,
+        max_concurrent: usize,
+    ) -> impl Stream<Item = CrateCheckResult> + '_ {
+        stream::iter(checks)
+            .map(move |check| self.process_batch_check(check))
+            .buffered(max_concurrent.max(1))
+    }
+
+    /// Run batch checks concurrently, yielding each result as soon as it completes
+    pub fn check_crates_unordered(
+        &self,
+        checks: Vec<BatchCheck>,
+    ) -> impl Stream<Item = CrateCheckResult> + '_ {
+        stream::iter(checks)
+            .map(move |check| self.process_batch_check(check))
+            .buffer_unordered(self.max_concurrent)
+    }
+
+    /// Check a crate, then fetch whatever else its batch operation asks for
+    ///
+    /// A failed fetch is reported in the result's `error` rather than failing the batch.
+    async fn process_batch_check(&self, check: BatchCheck) -> CrateCheckResult {
+        let mut result = self
+            .process_single_crate_check(&check.crate_name, check.version)
+            .await;
+        if !result.exists || result.error.is_some() {
+            return result;
+        }
+
+        let crate_name = check.crate_name.as_str();
+        let fetched = match check.operation {
+            BatchOperationKind::Check => Ok(()),
+            BatchOperationKind::Info => match result.info {
+                Some(_) => Ok(()),
+                None => self
+                    .get_crate_info(crate_name)
+                    .await
+                    .map(|info| result.info = Some(info)),
+            },
+            BatchOperationKind::Deps => {
+                let version = match result.requested_version {
+                    Some(_) => result.resolved_version.clone(),
+                    None => result.latest_version.clone(),
+                };
+                match version {
+                    Some(version) => self
+                        .get_crate_dependencies(crate_name, &version)
+                        .await
+                        .map(|deps| result.dependencies = Some(deps)),
+                    // The requested version does not exist; there is nothing to list
+                    None => Ok(()),
+                }
+            }
+            BatchOperationKind::Stats => self
+                .get_download_stats(crate_name)
+                .await
+                .map(|stats| result.download_stats = Some(stats)),
+            BatchOperationKind::Versions => self
+                .list_versions(crate_name)
+                .await
+                .map(|versions| result.versions = Some(versions)),
+        };
+        if let Err(e) = fetched {
+            result.outcome = CheckOutcome::Error;
+            result.error = Some(e.to_string());
+        }
+        result
+    }
+
     /// Process a single crate check (internal helper)
     async fn process_single_crate_check(
         &self,
@@ -424,54 +2086,109 @@ impl CrateClient {
                     return CrateCheckResult {
                         crate_name: crate_name.to_string(),
                         exists: false,
+                        outcome: CheckOutcome::Missing,
                         latest_version: None,
                         requested_version,
                         version_exists: None,
+                        resolved_version: None,
+                        suggested_version: None,
                         error: None,
                         info: None,
+                        dependencies: None,
+                        download_stats: None,
+                        versions: None,
                     };
                 }
 
-                // Get crate info
-                let info = match self.get_crate_info(crate_name).await {
-                    Ok(info) => Some(info.clone()),
-                    Err(_) => None,
-                };
+                // A failed follow-up fetch fails the check, so it is not mistaken
+                // for a crate without a latest or requested version
+                let checked = async {
+                    let (info, latest_version) = if self.offline_index.is_some() {
+                        // The index knows the latest version but has no crate info
+                        (None, self.get_latest_version(crate_name).await?)
+                    } else {
+                        let info = self.get_crate_info(crate_name).await?;
+                        let latest_version = self
+                            .listed_latest_version(crate_name, info.newest_version.clone())
+                            .await?;
+                        (Some(info), latest_version)
+                    };
 
-                let latest_version = info.as_ref().map(|i| i.newest_version.clone());
+                    // Check specific version if requested
+                    let resolution = match requested_version.as_deref() {
+                        Some(req_version) if is_latest_keyword(req_version) => {
+                            (Some(true), Some(latest_version.clone()), None)
+                        }
+                        Some(req_version) => {
+                            let versions = self.get_all_versions(crate_name).await?;
+                            match resolve_version(req_version, &versions) {
+                                VersionResolution::Found(resolved) => {
+                                    (Some(true), Some(resolved), None)
+                                }
+                                VersionResolution::NotFound { suggestion } => {
+                                    (Some(false), None, suggestion)
+                                }
+                            }
+                        }
+                        None => (None, None, None),
+                    };
+                    Ok::<_, CrateCheckerError>((info, latest_version, resolution))
+                }
+                .await;
 
-                // Check specific version if requested
-                let version_exists = if let Some(ref req_version) = requested_version {
-                    if req_version == "latest" {
-                        Some(true)
-                    } else {
-                        match self.get_all_versions(crate_name).await {
-                            Ok(versions) => Some(versions.iter().any(|v| v.num == *req_version)),
-                            Err(_) => None,
+                match checked {
+                    Ok((info, latest_version, resolution)) => {
+                        let (version_exists, resolved_version, suggested_version) = resolution;
+                        CrateCheckResult {
+                            crate_name: crate_name.to_string(),
+                            exists: true,
+                            outcome: CheckOutcome::Exists,
+                            latest_version: Some(latest_version),
+                            requested_version,
+                            version_exists,
+                            resolved_version,
+                            suggested_version,
+                            error: None,
+                            info,
+                            dependencies: None,
+                            download_stats: None,
+                            versions: None,
+                        }
+                    }
+                    Err(e) => {
+                        let outcome = CheckOutcome::from_error(&e);
+                        CrateCheckResult {
+                            crate_name: crate_name.to_string(),
+                            exists: outcome != CheckOutcome::Missing,
+                            outcome,
+                            latest_version: None,
+                            requested_version,
+                            version_exists: None,
+                            resolved_version: None,
+                            suggested_version: None,
+                            error: Some(e.to_string()),
+                            info: None,
+                            dependencies: None,
+                            download_stats: None,
+                            versions: None,
                         }
                     }
-                } else {
-                    None
-                };
-
-                CrateCheckResult {
-                    crate_name: crate_name.to_string(),
-                    exists: true,
-                    latest_version,
-                    requested_version,
-                    version_exists,
-                    error: None,
-                    info,
                 }
             }
             Err(e) => CrateCheckResult {
                 crate_name: crate_name.to_string(),
                 exists: false,
+                outcome: CheckOutcome::Error,
                 latest_version: None,
                 requested_version,
                 version_exists: None,
+                resolved_version: None,
+                suggested_version: None,
                 error: Some(e.to_string()),
                 info: None,
+                dependencies: None,
+                download_stats: None,
+                versions: None,
             },
         }
     }
@@ -483,32 +2200,65 @@ impl Default for CrateClient {
     }
 }
 
+/// Credential shown as `***` in `Debug` output
+#[derive(Clone)]
+struct Secret(String);
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("***")
+    }
+}
+
 /// Builder for creating a CrateClient with custom configuration
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CrateClientBuilder {
     base_url: Option<String>,
     user_agent: Option<String>,
     timeout: Option<Duration>,
-}
-
-impl Default for CrateClientBuilder {
-    fn default() -> Self {
-        Self {
-            base_url: None,
-            user_agent: None,
-            timeout: None,
-        }
-    }
+    max_concurrent: Option<usize>,
+    history_ttl: Option<Duration>,
+    retry_attempts: Option<u32>,
+    rate_limit: Option<(u32, u32)>,
+    concurrency_limit: Option<usize>,
+    slow_request_threshold: Option<Duration>,
+    transport: Option<Arc<dyn HttpTransport>>,
+    cache: Option<CacheConfig>,
+    headers: Vec<(String, String)>,
+    registry_token: Option<Secret>,
+    bearer_token: Option<Secret>,
+    strict: bool,
+    include_prerelease: bool,
+    search_timeout: Option<Duration>,
+    stats_timeout: Option<Duration>,
+    offline: bool,
+    cargo_home: Option<PathBuf>,
 }
 
 impl CrateClientBuilder {
     /// Set the base URL for the crates.io API
+    ///
+    /// Any registry or mirror (such as kellnr) serving the crates.io API v1 shape can be
+    /// used; pair it with [`Self::registry_token`] when it requires authentication. The
+    /// client uses these endpoints, relative to the base URL:
+    ///
+    /// - `GET /crates/{name}` - crate info, versions and `links` (required by every command)
+    /// - `GET /crates/{name}/versions` - version listing, paginated via `meta.next_page`
+    /// - `GET /crates?q=&per_page=&page=&sort=` - search, also filtered by `category=` or `keyword=`
+    /// - `GET /categories/{slug}`, `GET /keywords/{keyword}` - category and keyword lookup
+    /// - `GET /crates/{name}/{version}/dependencies` - dependencies, trees and `outdated`
+    /// - `GET /crates/{name}/reverse_dependencies` - dependents
+    /// - `GET /crates/{name}/downloads` - download statistics and history
     pub fn base_url<S: Into<String>>(mut self, url: S) -> Self {
         self.base_url = Some(url.into());
         self
     }
 
     /// Set the user agent string
+    ///
+    /// crates.io asks for a descriptive agent with contact information, e.g.
+    /// `my-tool/1.0 (https://example.com/my-tool)`. An empty agent fails [`Self::build`],
+    /// and one without a URL or email address logs a warning.
     pub fn user_agent<S: Into<String>>(mut self, agent: S) -> Self {
         self.user_agent = Some(agent.into());
         self
@@ -520,21 +2270,258 @@ impl CrateClientBuilder {
         self
     }
 
+    /// Set the maximum number of concurrent requests for multi-crate operations
+    pub fn max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = Some(max_concurrent);
+        self
+    }
+
+    /// Set how many times a recoverable failure (timeout, 5xx, 429, connection
+    /// error) is retried before giving up (zero disables retries)
+    pub fn retry_attempts(mut self, attempts: u32) -> Self {
+        self.retry_attempts = Some(attempts);
+        self
+    }
+
+    /// Throttle upstream requests (including retries) with a token bucket that
+    /// refills at `requests_per_minute` and holds up to `burst_size` permits
+    pub fn rate_limit(mut self, requests_per_minute: u32, burst_size: u32) -> Self {
+        self.rate_limit = Some((requests_per_minute, burst_size));
+        self
+    }
+
+    /// Allow at most `limit` upstream requests in flight at once, shared by all clones
+    /// of the client; further requests wait for a slot instead of failing
+    ///
+    /// Unlike [`Self::max_concurrent`], which sizes the fan-out of a single multi-crate
+    /// operation, this caps every request the client makes, however many callers share it.
+    pub fn concurrency_limit(mut self, limit: usize) -> Self {
+        self.concurrency_limit = Some(limit);
+        self
+    }
+
+    /// Log a warning with the URL and elapsed time for every upstream request taking
+    /// longer than `threshold` (default: 2s); [`Duration::ZERO`] disables the warning
+    pub fn slow_request_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_request_threshold = Some(threshold);
+        self
+    }
+
+    /// Send requests through `transport` instead of over the network, e.g. to test
+    /// code built on the client against canned responses
+    ///
+    /// The user agent, timeout and header settings only apply to the default
+    /// [`ReqwestTransport`], so they are ignored when a transport is given.
+    pub fn transport(mut self, transport: Arc<dyn HttpTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Cache successful crate info, versions, search and download history responses in
+    /// memory, keyed by request URL, for `ttl_seconds` (ignored when the config is
+    /// disabled); at most `max_entries` are kept, dropping the one closest to expiring
+    pub fn cache(mut self, config: CacheConfig) -> Self {
+        self.cache = Some(config);
+        self
+    }
+
+    /// Add a header sent with every request
+    pub fn header<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Send `token` as the `Authorization` header of every request, for private
+    /// registries and authenticated mirrors
+    ///
+    /// The value is sent as-is, as cargo does for registry tokens; include a `Bearer `
+    /// prefix if the registry expects one. It takes precedence over an `Authorization`
+    /// header set with [`Self::header`].
+    pub fn registry_token<S: Into<String>>(mut self, token: S) -> Self {
+        self.registry_token = Some(Secret(token.into()));
+        self
+    }
+
+    /// Send `token` as `Authorization: Bearer <token>` with every request, for
+    /// authenticated API endpoints
+    ///
+    /// A [`Self::registry_token`] takes precedence when both are set.
+    pub fn bearer_token<S: Into<String>>(mut self, token: S) -> Self {
+        self.bearer_token = Some(Secret(token.into()));
+        self
+    }
+
+    /// Set how long a fetched download history stays in the response cache enabled by
+    /// [`Self::cache`], instead of the cache's own TTL (zero disables caching histories)
+    pub fn history_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.history_ttl = Some(ttl);
+        self
+    }
+
+    /// Reject crates.io responses with unknown or missing fields instead of
+    /// silently ignoring or defaulting them (off by default)
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Include pre-releases in [`CrateClient::list_versions`] and when picking the
+    /// latest version (off by default)
+    ///
+    /// Explicitly requested versions and channels such as `latest-prerelease` are
+    /// resolved against every published version either way. In offline mode the latest
+    /// version is always the newest stable release the index knows of.
+    pub fn include_prerelease(mut self, include: bool) -> Self {
+        self.include_prerelease = include;
+        self
+    }
+
+    /// Override the request timeout for search only
+    pub fn search_timeout(mut self, timeout: Duration) -> Self {
+        self.search_timeout = Some(timeout);
+        self
+    }
+
+    /// Override the request timeout for download statistics and history only
+    pub fn stats_timeout(mut self, timeout: Duration) -> Self {
+        self.stats_timeout = Some(timeout);
+        self
+    }
+
+    /// Answer from the local cargo registry index instead of the network
+    ///
+    /// [`CrateClient::crate_exists`] and [`CrateClient::get_latest_version`] read the
+    /// index entries cargo has already fetched under `$CARGO_HOME/registry`, and so do
+    /// crate list checks (which then carry no crate info); a crate missing from them
+    /// is reported as [`CrateCheckerError::CrateNotFound`]. Every other request fails
+    /// with [`CrateCheckerError::NetworkError`] without being sent.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Cargo home whose registry index offline mode reads (defaults to `$CARGO_HOME`,
+    /// then `~/.cargo`)
+    pub fn cargo_home<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.cargo_home = Some(path.into());
+        self
+    }
+
     /// Build the CrateClient
     pub fn build(self) -> Result<CrateClient> {
         let timeout = self
             .timeout
             .unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECS));
         let user_agent = self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT);
+        if user_agent.trim().is_empty() {
+            return Err(CrateCheckerError::validation(
+                "User agent must not be empty",
+            ));
+        }
+        if !has_contact_info(user_agent) {
+            warn!(
+                "User agent '{}' has no contact information; crates.io asks for a URL or email address",
+                user_agent
+            );
+        }
 
-        let client = Client::builder()
-            .timeout(timeout)
-            .user_agent(user_agent)
-            .build()?;
+        let rate_limiter = match self.rate_limit {
+            Some((0, _)) => {
+                return Err(CrateCheckerError::validation(
+                    "Rate limit must allow at least one request per minute",
+                ))
+            }
+            Some((requests_per_minute, burst_size)) => {
+                Some(Arc::new(RateLimiter::new(requests_per_minute, burst_size)))
+            }
+            None => None,
+        };
+
+        let in_flight = match self.concurrency_limit {
+            Some(0) => {
+                return Err(CrateCheckerError::validation(
+                    "Concurrency limit must allow at least one request",
+                ))
+            }
+            Some(limit) => Some(Arc::new(tokio::sync::Semaphore::new(limit))),
+            None => None,
+        };
+
+        let offline_index = if self.offline {
+            let index = match &self.cargo_home {
+                Some(cargo_home) => Some(LocalIndex::new(cargo_home)),
+                None => LocalIndex::from_env(),
+            };
+            Some(index.ok_or_else(|| {
+                CrateCheckerError::validation(
+                    "Offline mode needs CARGO_HOME or HOME to locate the cargo registry",
+                )
+            })?)
+        } else {
+            None
+        };
+
+        let mut default_headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                CrateCheckerError::validation(format!("Invalid header name '{}': {}", name, e))
+            })?;
+            let header_value = HeaderValue::from_str(value).map_err(|e| {
+                CrateCheckerError::validation(format!("Invalid value for header '{}': {}", name, e))
+            })?;
+            default_headers.insert(header_name, header_value);
+        }
+        let authorization = match (&self.registry_token, &self.bearer_token) {
+            (Some(Secret(token)), _) => Some(token.clone()),
+            (None, Some(Secret(token))) => Some(format!("Bearer {}", token)),
+            (None, None) => None,
+        };
+        if let Some(token) = &authorization {
+            let mut header_value = HeaderValue::from_str(token).map_err(|e| {
+                CrateCheckerError::validation(format!("Invalid registry token: {}", e))
+            })?;
+            header_value.set_sensitive(true);
+            default_headers.insert(AUTHORIZATION, header_value);
+        }
+
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => Arc::new(ReqwestTransport::new(
+                Client::builder()
+                    .timeout(timeout)
+                    .user_agent(user_agent)
+                    .default_headers(default_headers)
+                    .build()?,
+            )),
+        };
 
         Ok(CrateClient {
-            client,
+            transport,
             base_url: self.base_url.unwrap_or_else(|| DEFAULT_API_URL.to_string()),
+            max_concurrent: self.max_concurrent.unwrap_or(DEFAULT_MAX_CONCURRENT).max(1),
+            history_ttl: self.history_ttl,
+            api_links: Arc::new(DashMap::new()),
+            retry_attempts: self.retry_attempts.unwrap_or(DEFAULT_RETRY_ATTEMPTS),
+            strict: self.strict,
+            include_prerelease: self.include_prerelease,
+            search_timeout: self.search_timeout,
+            stats_timeout: self.stats_timeout,
+            counters: Arc::new(RequestCounters::default()),
+            rate_limiter,
+            in_flight,
+            slow_request_threshold: Some(
+                self.slow_request_threshold
+                    .unwrap_or(Duration::from_millis(DEFAULT_SLOW_REQUEST_THRESHOLD_MS)),
+            )
+            .filter(|threshold| !threshold.is_zero()),
+            response_cache: self.cache.filter(|config| config.enabled).map(|config| {
+                Arc::new(ResponseCache {
+                    ttl: Duration::from_secs(config.ttl_seconds),
+                    max_entries: config.max_entries,
+                    entries: DashMap::new(),
+                })
+            }),
+            offline_index,
             _user_agent: user_agent.to_string(),
             _timeout: timeout,
         })
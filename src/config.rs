@@ -1,13 +1,96 @@
 //! Configuration management for the crate checker application
 
-use crate::{DEFAULT_API_URL, DEFAULT_SERVER_PORT, DEFAULT_TIMEOUT_SECS, DEFAULT_USER_AGENT};
-use config::{Config, ConfigError, Environment, File};
+use crate::{
+    DEFAULT_API_URL, DEFAULT_MAX_CONCURRENT, DEFAULT_RETRY_ATTEMPTS, DEFAULT_SERVER_PORT,
+    DEFAULT_SLOW_REQUEST_THRESHOLD_MS, DEFAULT_TIMEOUT_SECS, DEFAULT_USER_AGENT,
+};
+use config::{Config, ConfigError, Environment, File, FileFormat};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 use tracing::info;
 
+/// Environment variable cargo reads the crates.io token from, used for
+/// [`CratesIoConfig::token`] when the configuration does not set one
+pub const CARGO_REGISTRY_TOKEN_ENV: &str = "CARGO_REGISTRY_TOKEN";
+
+/// Largest config file accepted; anything bigger is almost certainly the wrong file
+pub const MAX_CONFIG_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Reject oversized or non-UTF-8 config files with a clear message before parsing
+fn check_config_file(path: &Path, format: ConfigFormat) -> Result<(), ConfigError> {
+    let io_error = |e: std::io::Error| {
+        ConfigError::Message(format!("failed to read {}: {}", path.display(), e))
+    };
+
+    let size = std::fs::metadata(path).map_err(io_error)?.len();
+    if size > MAX_CONFIG_FILE_BYTES {
+        return Err(ConfigError::Message(format!(
+            "config file too large: {} is {} bytes (limit {} bytes)",
+            path.display(),
+            size,
+            MAX_CONFIG_FILE_BYTES
+        )));
+    }
+
+    let content = std::fs::read(path).map_err(io_error)?;
+    if let Err(e) = std::str::from_utf8(&content) {
+        return Err(ConfigError::Message(format!(
+            "config file is not valid UTF-8 {}: {} ({})",
+            format.name(),
+            path.display(),
+            e
+        )));
+    }
+
+    Ok(())
+}
+
+/// File format of a configuration file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ConfigFormat {
+    #[default]
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Format named by a file's extension: `.toml`, `.json`, `.yaml` or `.yml`
+    ///
+    /// Files without an extension are read as TOML; any other extension is `None`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let Some(extension) = path.extension() else {
+            return Some(Self::Toml);
+        };
+        match extension.to_str()?.to_ascii_lowercase().as_str() {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Name of the format as written in messages
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Toml => "TOML",
+            Self::Json => "JSON",
+            Self::Yaml => "YAML",
+        }
+    }
+
+    fn file_format(self) -> FileFormat {
+        match self {
+            Self::Toml => FileFormat::Toml,
+            Self::Json => FileFormat::Json,
+            Self::Yaml => FileFormat::Yaml,
+        }
+    }
+}
+
 /// Main application configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AppConfig {
     /// Server configuration
     pub server: ServerConfig,
@@ -51,6 +134,18 @@ pub struct ServerConfig {
     /// Enable request tracing
     #[serde(default = "default_enable_tracing")]
     pub enable_tracing: bool,
+
+    /// Bearer token required by `/metrics` and `/api/config` (open when unset)
+    #[serde(default)]
+    pub metrics_token: Option<String>,
+
+    /// Bearer token required by every endpoint except `/health`, `/` and `/openapi.json` (open when unset)
+    #[serde(default)]
+    pub api_key: Option<String>,
+
+    /// Add `X-Cache` headers and honor `?explain=cache` on cacheable endpoints
+    #[serde(default)]
+    pub debug_headers: bool,
 }
 
 /// Cache configuration
@@ -67,6 +162,28 @@ pub struct CacheConfig {
     /// Maximum number of cache entries
     #[serde(default = "default_cache_max_entries")]
     pub max_entries: usize,
+
+    /// Where cache entries are stored
+    #[serde(default)]
+    pub backend: CacheBackend,
+
+    /// Directory holding cache entries for the disk backend
+    pub directory: Option<String>,
+
+    /// How often the server sweeps expired entries, in seconds (0 disables the sweep)
+    #[serde(default = "default_cache_cleanup_interval")]
+    pub cleanup_interval_seconds: u64,
+}
+
+/// Storage used for the server response cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+    /// In-process cache, lost on restart
+    #[default]
+    Memory,
+    /// JSON files under `cache.directory`, kept across restarts
+    Disk,
 }
 
 /// Logging configuration
@@ -105,7 +222,7 @@ pub struct RateLimitConfig {
 }
 
 /// Crates.io API configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CratesIoConfig {
     /// API base URL
     #[serde(default = "default_api_url")]
@@ -119,13 +236,50 @@ pub struct CratesIoConfig {
     #[serde(default = "default_api_timeout")]
     pub timeout_seconds: u64,
 
-    /// Maximum concurrent requests
+    /// Maximum concurrent requests; the server also caps its upstream requests in
+    /// flight at this, queueing the rest
     #[serde(default = "default_max_concurrent")]
     pub max_concurrent: usize,
 
     /// Retry attempts for failed requests
     #[serde(default = "default_retry_attempts")]
     pub retry_attempts: u32,
+
+    /// Log a warning for any single upstream request taking longer than this many
+    /// milliseconds (0 disables the warning)
+    #[serde(default = "default_slow_request_threshold_ms")]
+    pub slow_request_threshold_ms: u64,
+
+    /// Extra headers sent with every upstream request
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
+    /// Token sent as the `Authorization` header to a private registry
+    #[serde(default)]
+    pub registry_token: Option<String>,
+
+    /// API token sent as `Authorization: Bearer <token>`; read from
+    /// `CARGO_REGISTRY_TOKEN` when not configured otherwise
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+// Written by hand so tokens never end up in logs
+impl std::fmt::Debug for CratesIoConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let redact = |token: &Option<String>| token.as_ref().map(|_| "***");
+        f.debug_struct("CratesIoConfig")
+            .field("api_url", &self.api_url)
+            .field("user_agent", &self.user_agent)
+            .field("timeout_seconds", &self.timeout_seconds)
+            .field("max_concurrent", &self.max_concurrent)
+            .field("retry_attempts", &self.retry_attempts)
+            .field("slow_request_threshold_ms", &self.slow_request_threshold_ms)
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("registry_token", &redact(&self.registry_token))
+            .field("token", &redact(&self.token))
+            .finish()
+    }
 }
 
 // Default value functions
@@ -157,6 +311,9 @@ fn default_cache_ttl() -> u64 {
 fn default_cache_max_entries() -> usize {
     1000
 }
+fn default_cache_cleanup_interval() -> u64 {
+    60
+}
 
 fn default_log_level() -> String {
     "info".to_string()
@@ -188,22 +345,13 @@ fn default_api_timeout() -> u64 {
     DEFAULT_TIMEOUT_SECS
 }
 fn default_max_concurrent() -> usize {
-    10
+    DEFAULT_MAX_CONCURRENT
 }
 fn default_retry_attempts() -> u32 {
-    3
+    DEFAULT_RETRY_ATTEMPTS
 }
-
-impl Default for AppConfig {
-    fn default() -> Self {
-        Self {
-            server: ServerConfig::default(),
-            cache: CacheConfig::default(),
-            logging: LoggingConfig::default(),
-            rate_limiting: RateLimitConfig::default(),
-            crates_io: CratesIoConfig::default(),
-        }
-    }
+fn default_slow_request_threshold_ms() -> u64 {
+    DEFAULT_SLOW_REQUEST_THRESHOLD_MS
 }
 
 impl Default for ServerConfig {
@@ -215,6 +363,9 @@ impl Default for ServerConfig {
             request_timeout: default_request_timeout(),
             enable_cors: default_enable_cors(),
             enable_tracing: default_enable_tracing(),
+            metrics_token: None,
+            api_key: None,
+            debug_headers: false,
         }
     }
 }
@@ -225,6 +376,9 @@ impl Default for CacheConfig {
             enabled: default_cache_enabled(),
             ttl_seconds: default_cache_ttl(),
             max_entries: default_cache_max_entries(),
+            backend: CacheBackend::default(),
+            directory: None,
+            cleanup_interval_seconds: default_cache_cleanup_interval(),
         }
     }
 }
@@ -258,6 +412,10 @@ impl Default for CratesIoConfig {
             timeout_seconds: default_api_timeout(),
             max_concurrent: default_max_concurrent(),
             retry_attempts: default_retry_attempts(),
+            slow_request_threshold_ms: default_slow_request_threshold_ms(),
+            headers: BTreeMap::new(),
+            registry_token: None,
+            token: None,
         }
     }
 }
@@ -280,7 +438,14 @@ impl AppConfig {
             let path = path.as_ref();
             if path.exists() {
                 info!("Loading configuration from: {}", path.display());
-                builder = builder.add_source(File::from(path));
+                let format = ConfigFormat::from_path(path).ok_or_else(|| {
+                    ConfigError::Message(format!(
+                        "unsupported config file format: {} (expected .toml, .json, .yaml or .yml)",
+                        path.display()
+                    ))
+                })?;
+                check_config_file(path, format)?;
+                builder = builder.add_source(File::from(path).format(format.file_format()));
             }
         }
 
@@ -291,7 +456,13 @@ impl AppConfig {
                 .try_parsing(true),
         );
 
-        builder.build()?.try_deserialize()
+        let mut config: Self = builder.build()?.try_deserialize()?;
+        if config.crates_io.token.is_none() {
+            config.crates_io.token = std::env::var(CARGO_REGISTRY_TOKEN_ENV)
+                .ok()
+                .filter(|token| !token.trim().is_empty());
+        }
+        Ok(config)
     }
 
     /// Validate the configuration
@@ -308,10 +479,30 @@ impl AppConfig {
             return Err("Request timeout cannot be 0".to_string());
         }
 
+        if self
+            .server
+            .api_key
+            .as_deref()
+            .is_some_and(|key| key.trim().is_empty())
+        {
+            return Err("Server API key cannot be empty".to_string());
+        }
+
         if self.cache.enabled && self.cache.max_entries == 0 {
             return Err("Cache max entries cannot be 0 when caching is enabled".to_string());
         }
 
+        if self.cache.enabled
+            && self.cache.backend == CacheBackend::Disk
+            && self
+                .cache
+                .directory
+                .as_deref()
+                .is_none_or(|dir| dir.trim().is_empty())
+        {
+            return Err("Cache directory is required for the disk backend".to_string());
+        }
+
         if !["trace", "debug", "info", "warn", "error"].contains(&self.logging.level.as_str()) {
             return Err(format!("Invalid log level: {}", self.logging.level));
         }
@@ -328,13 +519,61 @@ impl AppConfig {
             return Err("Max concurrent requests cannot be 0".to_string());
         }
 
+        if self.crates_io.user_agent.trim().is_empty() {
+            return Err("User agent cannot be empty".to_string());
+        }
+
+        if self.rate_limiting.enabled && self.rate_limiting.requests_per_minute == 0 {
+            return Err("Rate limit requests per minute cannot be 0".to_string());
+        }
+
         Ok(())
     }
 
+    /// Copy of the configuration with secrets replaced by `***`, safe to expose
+    pub fn redacted(&self) -> Self {
+        const REDACTED: &str = "***";
+        const SENSITIVE_HEADER_PARTS: [&str; 5] = ["auth", "token", "key", "secret", "cookie"];
+
+        let mut config = self.clone();
+        if config.server.metrics_token.is_some() {
+            config.server.metrics_token = Some(REDACTED.to_string());
+        }
+        if config.server.api_key.is_some() {
+            config.server.api_key = Some(REDACTED.to_string());
+        }
+        if config.crates_io.registry_token.is_some() {
+            config.crates_io.registry_token = Some(REDACTED.to_string());
+        }
+        if config.crates_io.token.is_some() {
+            config.crates_io.token = Some(REDACTED.to_string());
+        }
+        for (name, value) in config.crates_io.headers.iter_mut() {
+            let name = name.to_ascii_lowercase();
+            if SENSITIVE_HEADER_PARTS
+                .iter()
+                .any(|part| name.contains(part))
+            {
+                *value = REDACTED.to_string();
+            }
+        }
+        config
+    }
+
     /// Create a sample configuration file
     pub fn create_sample_config() -> String {
-        toml::to_string_pretty(&AppConfig::default())
-            .unwrap_or_else(|_| "# Failed to generate sample config".to_string())
+        Self::create_sample_config_as(ConfigFormat::Toml)
+    }
+
+    /// Create a sample configuration file in `format`
+    pub fn create_sample_config_as(format: ConfigFormat) -> String {
+        let config = AppConfig::default();
+        let sample = match format {
+            ConfigFormat::Toml => toml::to_string_pretty(&config).map_err(|e| e.to_string()),
+            ConfigFormat::Json => serde_json::to_string_pretty(&config).map_err(|e| e.to_string()),
+            ConfigFormat::Yaml => serde_yaml::to_string(&config).map_err(|e| e.to_string()),
+        };
+        sample.unwrap_or_else(|_| "# Failed to generate sample config".to_string())
     }
 
     /// Get the bind address for the server
@@ -428,6 +667,78 @@ level = "debug"
         std::fs::remove_file(&temp_path).ok();
     }
 
+    #[test]
+    fn test_load_json_and_yaml_files() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let json = dir.path().join("config.json");
+        std::fs::write(
+            &json,
+            r#"{"server": {"port": 9090}, "crates_io": {"retry_attempts": 1}}"#,
+        )
+        .unwrap();
+        let config = AppConfig::load_from_file(Some(&json)).unwrap();
+        assert_eq!(config.server.port, 9090);
+        assert_eq!(config.crates_io.retry_attempts, 1);
+
+        let yaml = dir.path().join("config.yml");
+        std::fs::write(&yaml, "server:\n  port: 9191\nlogging:\n  level: warn\n").unwrap();
+        let config = AppConfig::load_from_file(Some(&yaml)).unwrap();
+        assert_eq!(config.server.port, 9191);
+        assert_eq!(config.logging.level, "warn");
+
+        let ini = dir.path().join("config.ini");
+        std::fs::write(&ini, "[server]\nport = 1\n").unwrap();
+        let err = AppConfig::load_from_file(Some(&ini)).unwrap_err();
+        assert!(err.to_string().contains("unsupported config file format"));
+    }
+
+    #[test]
+    fn test_sample_config_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        for (format, file) in [
+            (ConfigFormat::Toml, "config.toml"),
+            (ConfigFormat::Json, "config.json"),
+            (ConfigFormat::Yaml, "config.yaml"),
+        ] {
+            let path = dir.path().join(file);
+            let sample = AppConfig::create_sample_config_as(format);
+            let mut config: AppConfig = match format {
+                ConfigFormat::Toml => toml::from_str(&sample).unwrap(),
+                ConfigFormat::Json => serde_json::from_str(&sample).unwrap(),
+                ConfigFormat::Yaml => serde_yaml::from_str(&sample).unwrap(),
+            };
+            config.server.port = 7070;
+            config.crates_io.registry_token = Some("t0ken".to_string());
+            let edited = match format {
+                ConfigFormat::Toml => toml::to_string_pretty(&config).unwrap(),
+                ConfigFormat::Json => serde_json::to_string_pretty(&config).unwrap(),
+                ConfigFormat::Yaml => serde_yaml::to_string(&config).unwrap(),
+            };
+            std::fs::write(&path, edited).unwrap();
+
+            let loaded = AppConfig::load_from_file(Some(&path)).unwrap();
+            assert_eq!(loaded.server.port, 7070, "{}", file);
+            assert_eq!(loaded.crates_io.registry_token.as_deref(), Some("t0ken"));
+            assert_eq!(loaded.server.metrics_token, None, "{}", file);
+            assert_eq!(loaded.cache.backend, config.cache.backend);
+            assert!(loaded.validate().is_ok(), "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_disk_cache_backend() {
+        let mut config = AppConfig {
+            cache: toml::from_str(r#"backend = "disk""#).unwrap(),
+            ..AppConfig::default()
+        };
+        assert_eq!(config.cache.backend, CacheBackend::Disk);
+        assert!(config.validate().is_err());
+
+        config.cache.directory = Some("/var/cache/crate-checker".to_string());
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_environment_overrides() {
         let env_config = EnvironmentConfig {
@@ -452,6 +763,39 @@ level = "debug"
             .contains(&config.server.port.to_string()));
     }
 
+    #[test]
+    fn test_redacted_hides_secrets() {
+        let mut config = AppConfig::default();
+        config.server.metrics_token = Some("s3cret".to_string());
+        config.server.api_key = Some("k3y".to_string());
+        config.crates_io.registry_token = Some("t0ken".to_string());
+        config.crates_io.token = Some("b3arer".to_string());
+        config
+            .crates_io
+            .headers
+            .insert("Authorization".to_string(), "Bearer abc".to_string());
+        config
+            .crates_io
+            .headers
+            .insert("x-deployment".to_string(), "staging".to_string());
+
+        let redacted = config.redacted();
+        assert_eq!(redacted.server.metrics_token.as_deref(), Some("***"));
+        assert_eq!(redacted.server.api_key.as_deref(), Some("***"));
+        assert_eq!(redacted.crates_io.registry_token.as_deref(), Some("***"));
+        assert_eq!(redacted.crates_io.token.as_deref(), Some("***"));
+        assert_eq!(redacted.crates_io.headers["Authorization"], "***");
+        assert_eq!(redacted.crates_io.headers["x-deployment"], "staging");
+        assert_eq!(redacted.server.port, config.server.port);
+
+        let debug = format!("{:?}", config.crates_io);
+        assert!(
+            !debug.contains("t0ken") && !debug.contains("b3arer"),
+            "{}",
+            debug
+        );
+    }
+
     #[test]
     fn test_create_sample_config() {
         let sample = AppConfig::create_sample_config();
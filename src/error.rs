@@ -36,13 +36,24 @@ pub enum CrateCheckerError {
     #[error("Version '{version}' not found for crate '{crate_name}'")]
     VersionNotFound { crate_name: String, version: String },
 
+    /// Category not found
+    #[error("Category '{0}' not found; see https://crates.io/categories for the available slugs")]
+    CategoryNotFound(String),
+
+    /// Keyword not found
+    #[error("Keyword '{0}' not found")]
+    KeywordNotFound(String),
+
     /// Invalid crate name
     #[error("Invalid crate name: '{0}'. Crate names must match the pattern: {1}")]
     InvalidCrateName(String, &'static str),
 
     /// API rate limit exceeded
     #[error("API rate limit exceeded. Please try again later")]
-    RateLimitExceeded,
+    RateLimitExceeded {
+        /// Delay requested by the server's `Retry-After` header, if any
+        retry_after: Option<std::time::Duration>,
+    },
 
     /// Server error from crates.io API
     #[error("Server error: {status} - {message}")]
@@ -79,6 +90,10 @@ pub enum CrateCheckerError {
     /// Service unavailable
     #[error("Service temporarily unavailable: {0}")]
     ServiceUnavailable(String),
+
+    /// Upstream response did not match the expected schema (strict mode)
+    #[error("Unexpected crates.io response schema: {0}")]
+    SchemaMismatch(String),
 }
 
 impl CrateCheckerError {
@@ -110,7 +125,7 @@ impl CrateCheckerError {
                 | Self::NetworkError(_)
                 | Self::Timeout(_)
                 | Self::ServiceUnavailable(_)
-                | Self::RateLimitExceeded
+                | Self::RateLimitExceeded { .. }
         )
     }
 
@@ -118,8 +133,11 @@ impl CrateCheckerError {
     pub fn status_code(&self) -> Option<u16> {
         match self {
             Self::ServerError { status, .. } => Some(*status),
-            Self::CrateNotFound(_) | Self::VersionNotFound { .. } => Some(404),
-            Self::RateLimitExceeded => Some(429),
+            Self::CrateNotFound(_)
+            | Self::VersionNotFound { .. }
+            | Self::CategoryNotFound(_)
+            | Self::KeywordNotFound(_) => Some(404),
+            Self::RateLimitExceeded { .. } => Some(429),
             Self::AuthError(_) => Some(401),
             Self::ValidationError(_) | Self::InvalidCrateName(_, _) => Some(400),
             Self::ServiceUnavailable(_) => Some(503),
@@ -127,6 +145,29 @@ impl CrateCheckerError {
         }
     }
 
+    /// Stable machine-readable code identifying the kind of error, e.g. `CRATE_NOT_FOUND`
+    ///
+    /// Reported in the `code` field of API server error bodies; unlike the message it
+    /// does not change between releases.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::CrateNotFound(_) => "CRATE_NOT_FOUND",
+            Self::VersionNotFound { .. } => "VERSION_NOT_FOUND",
+            Self::CategoryNotFound(_) => "CATEGORY_NOT_FOUND",
+            Self::KeywordNotFound(_) => "KEYWORD_NOT_FOUND",
+            Self::RateLimitExceeded { .. } => "RATE_LIMITED",
+            Self::InvalidCrateName(_, _)
+            | Self::ValidationError(_)
+            | Self::InvalidBatchInput(_) => "VALIDATION",
+            Self::Timeout(_) => "TIMEOUT",
+            Self::HttpError(_) | Self::NetworkError(_) => "NETWORK",
+            Self::ServiceUnavailable(_) => "SERVICE_UNAVAILABLE",
+            Self::ServerError { .. } | Self::SchemaMismatch(_) => "UPSTREAM",
+            Self::AuthError(_) => "UNAUTHORIZED",
+            _ => "INTERNAL",
+        }
+    }
+
     /// Convert to a user-friendly message
     pub fn user_message(&self) -> String {
         match self {
@@ -148,7 +189,15 @@ impl CrateCheckerError {
                     name, pattern
                 )
             }
-            Self::RateLimitExceeded => {
+            Self::RateLimitExceeded {
+                retry_after: Some(delay),
+            } => {
+                format!(
+                    "You've exceeded the API rate limit. Please wait {} seconds before trying again.",
+                    delay.as_secs().max(1)
+                )
+            }
+            Self::RateLimitExceeded { retry_after: None } => {
                 "You've exceeded the API rate limit. Please wait a moment before trying again."
                     .to_string()
             }
@@ -169,7 +218,7 @@ impl From<reqwest::StatusCode> for CrateCheckerError {
     fn from(status: reqwest::StatusCode) -> Self {
         match status.as_u16() {
             404 => Self::ValidationError("Resource not found".to_string()),
-            429 => Self::RateLimitExceeded,
+            429 => Self::RateLimitExceeded { retry_after: None },
             500..=599 => Self::ServiceUnavailable(format!("Server error: {}", status)),
             _ => Self::ServerError {
                 status: status.as_u16(),
@@ -0,0 +1,138 @@
+//! Reader for the local cargo registry index, used by the client's offline mode
+//!
+//! Cargo keeps the index entries it has fetched under `$CARGO_HOME/registry/index`:
+//! sparse registries cache them in `<registry>/.cache/<prefix>/<name>` and git
+//! registries may check the index out as `<registry>/<prefix>/<name>`. Both hold one
+//! JSON object per published version; the cache files separate them with NUL bytes
+//! behind a small binary header, the checked-out files with newlines.
+
+use crate::error::{CrateCheckerError, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// One published version as recorded in the registry index
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IndexVersion {
+    pub name: String,
+    pub vers: String,
+    #[serde(default)]
+    pub yanked: bool,
+}
+
+/// Local cargo registry index across every registry cargo has used
+#[derive(Debug, Clone)]
+pub struct LocalIndex {
+    root: PathBuf,
+}
+
+impl LocalIndex {
+    /// Index under `cargo_home`, i.e. `<cargo_home>/registry/index`
+    pub fn new(cargo_home: impl AsRef<Path>) -> Self {
+        Self {
+            root: cargo_home.as_ref().join("registry").join("index"),
+        }
+    }
+
+    /// Index of the current user's cargo home: `$CARGO_HOME`, or `~/.cargo`
+    pub fn from_env() -> Option<Self> {
+        let cargo_home = match std::env::var_os("CARGO_HOME") {
+            Some(home) if !home.is_empty() => PathBuf::from(home),
+            _ => {
+                let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+                PathBuf::from(home).join(".cargo")
+            }
+        };
+        Some(Self::new(cargo_home))
+    }
+
+    /// All versions of `crate_name` found in the local index
+    ///
+    /// Returns [`CrateCheckerError::CrateNotFound`] when no registry has an entry for it.
+    pub fn versions(&self, crate_name: &str) -> Result<Vec<IndexVersion>> {
+        let relative = index_path(crate_name);
+        let registries = std::fs::read_dir(&self.root)
+            .into_iter()
+            .flatten()
+            .flatten();
+
+        for registry in registries {
+            let dir = registry.path();
+            for path in [dir.join(".cache").join(&relative), dir.join(&relative)] {
+                let Ok(content) = std::fs::read(&path) else {
+                    continue;
+                };
+                let versions = parse_index_file(&content);
+                if !versions.is_empty() {
+                    return Ok(versions);
+                }
+            }
+        }
+
+        Err(CrateCheckerError::CrateNotFound(crate_name.to_string()))
+    }
+
+    /// Newest non-yanked version, preferring stable releases (as `latest` does elsewhere)
+    pub fn latest_version(&self, crate_name: &str) -> Result<String> {
+        let versions = self.versions(crate_name)?;
+        let published: Vec<semver::Version> = versions
+            .iter()
+            .filter(|v| !v.yanked)
+            .filter_map(|v| semver::Version::parse(&v.vers).ok())
+            .collect();
+
+        published
+            .iter()
+            .filter(|v| v.pre.is_empty())
+            .max()
+            .or_else(|| published.iter().max())
+            .map(|v| v.to_string())
+            .or_else(|| versions.last().map(|v| v.vers.clone()))
+            .ok_or_else(|| CrateCheckerError::CrateNotFound(crate_name.to_string()))
+    }
+}
+
+/// Path of a crate's file relative to the index root, following cargo's layout
+pub fn index_path(crate_name: &str) -> PathBuf {
+    let name = crate_name.to_ascii_lowercase();
+    match name.len() {
+        0..=2 => PathBuf::from(name.len().to_string()).join(&name),
+        3 => PathBuf::from("3").join(&name[..1]).join(&name),
+        _ => PathBuf::from(&name[..2]).join(&name[2..4]).join(&name),
+    }
+}
+
+/// Extract the version entries of an index or index cache file
+fn parse_index_file(content: &[u8]) -> Vec<IndexVersion> {
+    content
+        .split(|&b| b == 0 || b == b'\n')
+        .filter(|chunk| chunk.first() == Some(&b'{'))
+        .filter_map(|chunk| serde_json::from_slice(chunk).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_path() {
+        assert_eq!(index_path("a"), PathBuf::from("1/a"));
+        assert_eq!(index_path("cc"), PathBuf::from("2/cc"));
+        assert_eq!(index_path("syn"), PathBuf::from("3/s/syn"));
+        assert_eq!(index_path("Serde"), PathBuf::from("se/rd/serde"));
+    }
+
+    #[test]
+    fn test_parse_index_file() {
+        let cache = b"\x03\x02\x00\x00\x00etag: \"abc\"\x00\
+            0.1.0\x00{\"name\":\"demo\",\"vers\":\"0.1.0\",\"yanked\":false}\x00\
+            0.2.0\x00{\"name\":\"demo\",\"vers\":\"0.2.0\",\"yanked\":true}\x00";
+        let versions = parse_index_file(cache);
+        assert_eq!(versions.len(), 2);
+        assert!(versions[1].yanked);
+
+        let checkout =
+            b"{\"name\":\"demo\",\"vers\":\"0.1.0\"}\n{\"name\":\"demo\",\"vers\":\"0.2.0\"}\n";
+        assert_eq!(parse_index_file(checkout).len(), 2);
+    }
+}
@@ -58,7 +58,7 @@
 //! let client = CrateClient::builder()
 //!     .base_url("https://crates.io/api/v1")
 //!     .timeout(Duration::from_secs(30))
-//!     .user_agent("my-app/1.0")
+//!     .user_agent("my-app/1.0 (admin@example.com)")
 //!     .build()?;
 //! # Ok(())
 //! # }
@@ -80,7 +80,10 @@
 //!
 //! let result = client.process_crate_version_map(versions).await?;
 //! println!("Processed {} crates", result.total_processed);
-//! println!("Successful: {}, Failed: {}", result.successful, result.failed);
+//! println!(
+//!     "Successful: {}, Missing: {}, Failed: {}",
+//!     result.successful, result.missing, result.failed
+//! );
 //! # Ok(())
 //! # }
 //! ```
@@ -109,6 +112,39 @@
 //! # }
 //! ```
 //!
+//! ### Testing Without the Network
+//!
+//! Inject an [`transport::HttpTransport`] to answer the client's requests yourself:
+//!
+//! ```rust
+//! use crate_checker::transport::{HttpRequest, HttpResponse, HttpTransport};
+//! use crate_checker::{CrateClient, Result};
+//! use futures::future::BoxFuture;
+//! use reqwest::StatusCode;
+//! use std::sync::Arc;
+//!
+//! #[derive(Debug)]
+//! struct OnlySerde;
+//!
+//! impl HttpTransport for OnlySerde {
+//!     fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+//!         let status = if request.url.ends_with("/crates/serde") {
+//!             StatusCode::OK
+//!         } else {
+//!             StatusCode::NOT_FOUND
+//!         };
+//!         Box::pin(async move { Ok(HttpResponse::new(status, "{}")) })
+//!     }
+//! }
+//!
+//! # async fn example() -> Result<()> {
+//! let client = CrateClient::builder().transport(Arc::new(OnlySerde)).build()?;
+//! assert!(client.crate_exists("serde").await?);
+//! assert!(!client.crate_exists("sedre").await?);
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ## CLI Usage
 //!
 //! The crate-checker binary provides a comprehensive command-line interface:
@@ -178,11 +214,16 @@
 //! - `monitor_updates.rs` - Version monitoring
 //! - `custom_client.rs` - Advanced configuration
 
+pub mod cache;
 pub mod cli;
 pub mod client;
 pub mod config;
 pub mod error;
+pub mod index;
+pub mod openapi;
+pub mod sarif;
 pub mod server;
+pub mod transport;
 pub mod types;
 pub mod utils;
 
@@ -190,9 +231,12 @@ pub mod utils;
 pub use client::{CrateClient, CrateClientBuilder};
 pub use error::{CrateCheckerError, Result};
 pub use types::{
-    BatchInput, BatchOperation, BatchRequest, BatchResponse, BatchResult, BatchTarget,
-    CrateCheckResult, CrateInfo, CrateSearchResult, CrateStatus, Dependency, DownloadStats, Owner,
-    Version, VersionDownload,
+    BatchCheck, BatchInput, BatchOperation, BatchOperationKind, BatchRequest, BatchResponse,
+    BatchResult, BatchTarget, BenchReport, CheckOutcome, ClientStats, CrateCheckResult, CrateInfo,
+    CrateSearchResult, CrateStatus, CrateStatusReport, DepNode, Dependency, DownloadStats,
+    EcosystemReport, LockFinding, LockFindingKind, OutdatedDependency, Owner, RetriedBatchResult,
+    ReverseDependencies, SearchPage, SearchParams, SearchSort, UpdateKind, Version,
+    VersionDownload,
 };
 
 // Re-export configuration types for server users
@@ -201,12 +245,34 @@ pub use config::{AppConfig, EnvironmentConfig};
 /// Default crates.io API base URL
 pub const DEFAULT_API_URL: &str = "https://crates.io/api/v1";
 
-/// Default user agent for requests
-pub const DEFAULT_USER_AGENT: &str = "crate-checker/0.1.0";
+/// Default user agent for requests: name, version and a contact URL, as crates.io asks
+pub const DEFAULT_USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_REPOSITORY"),
+    ")"
+);
 
 /// Default request timeout in seconds
 pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
 
+/// Default number of concurrent requests for multi-crate operations
+pub const DEFAULT_MAX_CONCURRENT: usize = 10;
+
+/// Default number of retries for recoverable request failures
+pub const DEFAULT_RETRY_ATTEMPTS: u32 = 3;
+
+/// Default time after which a single upstream request is logged as slow, in milliseconds
+pub const DEFAULT_SLOW_REQUEST_THRESHOLD_MS: u64 = 2000;
+
+/// Source recorded for crates.io packages in Cargo.lock
+pub const CRATES_IO_SOURCE: &str = "registry+https://github.com/rust-lang/crates.io-index";
+
+/// Source recorded for crates.io packages resolved through the sparse index
+pub const CRATES_IO_SPARSE_SOURCE: &str = "sparse+https://index.crates.io/";
+
 /// Default server port
 pub const DEFAULT_SERVER_PORT: u16 = 3000;
 
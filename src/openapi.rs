@@ -0,0 +1,480 @@
+//! Hand-built OpenAPI 3.0 description of the API server, served at `/openapi.json`
+//!
+//! Schemas mirror the serialized form of the response types in [`crate::types`], so
+//! they must be kept in step when a field is added or renamed there.
+
+use serde_json::{json, Value};
+
+/// OpenAPI specification version emitted
+pub const OPENAPI_VERSION: &str = "3.0.3";
+
+/// The full OpenAPI document for the server's routes
+pub fn spec() -> Value {
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": "Crate Checker API",
+            "version": crate::VERSION,
+            "description": "Check crate existence, versions, dependencies and download \
+                statistics against crates.io. When the server has an `api_key`, every \
+                endpoint except `/health`, `/` and `/openapi.json` requires it as a bearer token.",
+        },
+        "paths": paths(),
+        "components": {
+            "schemas": schemas(),
+            "securitySchemes": {
+                "bearerAuth": { "type": "http", "scheme": "bearer" }
+            },
+        },
+    })
+}
+
+fn paths() -> Value {
+    let name = path_param("name", "Crate name");
+    let version = path_param(
+        "version",
+        "Version, release channel (`latest`, `latest-stable`, ...) or semver requirement",
+    );
+    let explain = query_param(
+        "explain",
+        "string",
+        "Set to `cache` to include cache details",
+    );
+
+    json!({
+        "/health": {
+            "get": operation("Server health status", vec![], json_response("Server is healthy", schema_ref("HealthResponse"))),
+        },
+        "/openapi.json": {
+            "get": operation("This OpenAPI document", vec![], json_response("OpenAPI document", json!({ "type": "object" }))),
+        },
+        "/api/crates/{name}": {
+            "get": operation("Get crate information", vec![name.clone(), explain.clone()], json_response("Crate information", schema_ref("CrateInfo"))),
+        },
+        "/api/crates/{name}/{version}": {
+            "get": operation("Check a specific version", vec![name.clone(), version.clone(), explain], json_response("Version check result", schema_ref("CrateCheckResult"))),
+        },
+        "/api/crates/{name}/{version}/deps": {
+            "get": operation("Get the dependencies of a version", vec![name.clone(), version.clone()], json_response("Dependencies", array_of(schema_ref("Dependency")))),
+        },
+        "/api/crates/{name}/{version}/info": {
+            "get": operation("Get the metadata of a single version", vec![name.clone(), version], json_response("Version metadata", schema_ref("Version"))),
+        },
+        "/api/crates/{name}/stats": {
+            "get": operation("Get download statistics", vec![name.clone()], json_response("Download statistics", schema_ref("DownloadStats"))),
+        },
+        "/api/crates/{name}/status": {
+            "get": operation("Get yank status", vec![name.clone()], json_response("Yank status", schema_ref("CrateStatusReport"))),
+        },
+        "/api/crates/{name}/reverse": {
+            "get": operation(
+                "Get crates that depend on a crate",
+                vec![name, query_param("limit", "integer", "Maximum number of dependents")],
+                json_response("Dependent crates", schema_ref("ReverseDependencies")),
+            ),
+        },
+        "/api/search": {
+            "get": operation(
+                "Search crates",
+                vec![
+                    required(query_param("q", "string", "Search query")),
+                    query_param("per_page", "integer", "Results per page (alias: `limit`)"),
+                    query_param("page", "integer", "Page number, starting at 1"),
+                    json!({
+                        "name": "sort",
+                        "in": "query",
+                        "description": "Result ordering",
+                        "schema": { "type": "string", "enum": ["relevance", "downloads", "recent-downloads", "recent-updates"] },
+                    }),
+                ],
+                json!({
+                    "description": "Matching crates",
+                    "headers": {
+                        "X-Total-Count": {
+                            "description": "Total number of matching crates",
+                            "schema": { "type": "integer" },
+                        }
+                    },
+                    "content": { "application/json": { "schema": array_of(schema_ref("CrateSearchResult")) } },
+                }),
+            ),
+        },
+        "/api/batch": {
+            "post": with_body(
+                operation(
+                    "Check multiple crates",
+                    vec![query_param("stream", "string", "Set to `ndjson` to stream one result per line")],
+                    json_response("Batch result", schema_ref("BatchResult")),
+                ),
+                schema_ref("BatchRequest"),
+            ),
+        },
+        "/api/batch/stream": {
+            "get": {
+                "summary": "Run a batch over a WebSocket",
+                "description": "After the upgrade, send one text message with the `/api/batch` request \
+                    body. Each `CrateCheckResult` is sent as it completes, followed by \
+                    `{\"summary\": BatchResult}`, or `{\"error\": ...}` for an invalid batch.",
+                "responses": {
+                    "101": { "description": "Switching to the WebSocket protocol" },
+                },
+            },
+        },
+        "/api/yanked-check": {
+            "post": with_body(
+                operation("Check whether exact versions are yanked", vec![], json_response("Yank check result", schema_ref("YankedCheckResponse"))),
+                string_map(),
+            ),
+        },
+        "/api/exists": {
+            "post": with_body(
+                operation("Check which crates exist", vec![], json_response("Existence per crate", schema_ref("ExistsResponse"))),
+                schema_ref("ExistsRequest"),
+            ),
+        },
+        "/metrics": {
+            "get": operation("Server metrics", vec![], json_response("Server metrics", schema_ref("MetricsResponse"))),
+        },
+        "/metrics/prometheus": {
+            "get": operation("Server metrics in the Prometheus text format", vec![], json!({
+                "description": "Prometheus exposition",
+                "content": { "text/plain": { "schema": { "type": "string" } } },
+            })),
+        },
+        "/api/config": {
+            "get": operation("Effective configuration with secrets redacted", vec![], json_response("Configuration", json!({ "type": "object" }))),
+        },
+        "/api/cache/stats": {
+            "get": operation("Response cache statistics", vec![], json_response("Cache statistics", schema_ref("CacheStatsResponse"))),
+        },
+        "/api/cache": {
+            "delete": operation("Remove every cached response", vec![], json_response("Removed entries", schema_ref("CacheInvalidationResponse"))),
+        },
+        "/api/cache/{key_prefix}": {
+            "delete": operation(
+                "Remove cached responses whose key starts with a prefix",
+                vec![path_param("key_prefix", "Cache key prefix, e.g. `crate:serde`")],
+                json_response("Removed entries", schema_ref("CacheInvalidationResponse")),
+            ),
+        },
+    })
+}
+
+fn schemas() -> Value {
+    let string = json!({ "type": "string" });
+    let nullable_string = json!({ "type": "string", "nullable": true });
+    let integer = json!({ "type": "integer", "format": "int64", "minimum": 0 });
+    let nullable_integer = json!({ "type": "integer", "format": "int64", "nullable": true });
+    let boolean = json!({ "type": "boolean" });
+    let timestamp = json!({ "type": "string", "format": "date-time" });
+    let strings = array_of(string.clone());
+
+    json!({
+        "ErrorResponse": object(&["error", "code", "timestamp"], json!({
+            "error": string,
+            "code": {
+                "type": "string",
+                "enum": ["CRATE_NOT_FOUND", "VERSION_NOT_FOUND", "RATE_LIMITED", "VALIDATION", "TIMEOUT", "NETWORK", "SERVICE_UNAVAILABLE", "UPSTREAM", "UNAUTHORIZED", "INTERNAL"],
+            },
+            "details": {
+                "type": "object",
+                "description": "Missing `crate` (and `version`) of a 404",
+                "properties": { "crate": string, "version": string },
+            },
+            "timestamp": timestamp,
+        })),
+        "HealthResponse": object(&["status", "timestamp", "version", "uptime_seconds"], json!({
+            "status": string,
+            "timestamp": timestamp,
+            "version": string,
+            "uptime_seconds": integer,
+        })),
+        "CrateInfo": object(&["name", "newest_version", "downloads", "created_at", "updated_at", "keywords", "categories"], json!({
+            "name": string,
+            "description": nullable_string,
+            "newest_version": string,
+            "downloads": integer,
+            "created_at": timestamp,
+            "updated_at": timestamp,
+            "homepage": nullable_string,
+            "repository": nullable_string,
+            "documentation": nullable_string,
+            "keywords": strings,
+            "categories": strings,
+            "max_upload_size": nullable_integer,
+            "license": nullable_string,
+            "yanked": { "type": "boolean", "nullable": true },
+            "links": { "type": "object", "nullable": true, "additionalProperties": nullable_string },
+            "msrv": nullable_string,
+            "edition": nullable_string,
+        })),
+        "User": object(&["id", "login"], json!({
+            "id": integer,
+            "login": string,
+            "name": nullable_string,
+            "avatar": nullable_string,
+            "url": nullable_string,
+        })),
+        "Version": object(&["num", "created_at", "updated_at", "downloads", "yanked"], json!({
+            "num": string,
+            "created_at": timestamp,
+            "updated_at": timestamp,
+            "downloads": integer,
+            "yanked": boolean,
+            "id": nullable_integer,
+            "crate_size": nullable_integer,
+            "published_by": nullable(schema_ref("User")),
+            "license": nullable_string,
+            "features": { "type": "object", "nullable": true, "additionalProperties": strings },
+            "rust_version": nullable_string,
+            "edition": nullable_string,
+        })),
+        "Dependency": object(&["crate_id", "req", "optional", "default_features", "kind"], json!({
+            "crate_id": string,
+            "req": string,
+            "features": strings,
+            "optional": boolean,
+            "default_features": boolean,
+            "target": nullable_string,
+            "kind": { "type": "string", "enum": ["normal", "dev", "build"] },
+            "downloads": nullable_integer,
+        })),
+        "DownloadStats": object(&["total", "versions"], json!({
+            "total": integer,
+            "versions": array_of(object(&["version", "downloads", "date"], json!({
+                "version": string,
+                "downloads": integer,
+                "date": timestamp,
+            }))),
+        })),
+        "CrateStatusReport": object(&["crate", "status", "yanked_versions"], json!({
+            "crate": string,
+            "status": { "type": "string", "enum": ["exists", "not_found", "yanked", "partially_yanked"] },
+            "yanked_versions": strings,
+        })),
+        "CrateSearchResult": object(&["name", "newest_version", "downloads", "exact_match"], json!({
+            "name": string,
+            "description": nullable_string,
+            "newest_version": string,
+            "downloads": integer,
+            "exact_match": boolean,
+        })),
+        "ReverseDependencies": object(&["crate_name", "total", "dependents"], json!({
+            "crate_name": string,
+            "total": integer,
+            "dependents": array_of(schema_ref("CrateSearchResult")),
+        })),
+        "CrateCheckResult": object(&["crate_name", "exists", "outcome"], json!({
+            "crate_name": string,
+            "exists": boolean,
+            "outcome": { "type": "string", "enum": ["exists", "missing", "error"] },
+            "latest_version": nullable_string,
+            "requested_version": nullable_string,
+            "version_exists": { "type": "boolean", "nullable": true },
+            "resolved_version": nullable_string,
+            "suggested_version": nullable_string,
+            "error": nullable_string,
+            "info": nullable(schema_ref("CrateInfo")),
+            "dependencies": array_of(schema_ref("Dependency")),
+            "download_stats": schema_ref("DownloadStats"),
+            "versions": array_of(schema_ref("Version")),
+        })),
+        "BatchRequest": {
+            "description": "A `{name: version}` map, `{\"crates\": [...]}`, or `{\"operations\": [...]}`, with optional `options`",
+            "type": "object",
+            "properties": {
+                "crates": strings,
+                "operations": array_of(json!({
+                    "type": "object",
+                    "required": ["operation"],
+                    "properties": {
+                        "crate": string,
+                        "version": string,
+                        "crates": strings,
+                        "operation": { "type": "string", "enum": ["check", "info", "deps", "stats", "versions"] },
+                    },
+                })),
+                "options": object(&[], json!({
+                    "include_details": boolean,
+                    "parallel": boolean,
+                    "timeout_seconds": integer,
+                    "max_concurrent": integer,
+                })),
+            },
+            "additionalProperties": string,
+        },
+        "BatchResult": object(&["results", "total_processed", "successful", "failed", "processing_time_ms"], json!({
+            "results": array_of(schema_ref("CrateCheckResult")),
+            "total_processed": integer,
+            "total_crates_checked": integer,
+            "successful": integer,
+            "missing": integer,
+            "failed": integer,
+            "processing_time_ms": integer,
+        })),
+        "YankedCheckResponse": object(&["results", "any_yanked"], json!({
+            "results": array_of(object(&["crate_name", "version", "exists", "yanked"], json!({
+                "crate_name": string,
+                "version": string,
+                "exists": boolean,
+                "yanked": boolean,
+                "error": nullable_string,
+            }))),
+            "any_yanked": boolean,
+        })),
+        "ExistsRequest": object(&["crates"], json!({ "crates": strings })),
+        "ExistsResponse": object(&["results", "all_exist"], json!({
+            "results": { "type": "object", "additionalProperties": boolean },
+            "all_exist": boolean,
+        })),
+        "MetricsResponse": object(&["requests_total", "requests_successful", "requests_failed", "average_response_time_ms", "cache_hits", "cache_misses", "uptime_seconds"], json!({
+            "requests_total": integer,
+            "requests_successful": integer,
+            "requests_failed": integer,
+            "average_response_time_ms": { "type": "number" },
+            "cache_hits": integer,
+            "cache_misses": integer,
+            "uptime_seconds": integer,
+            "upstream": object(&["requests", "retries", "failures"], json!({
+                "requests": integer,
+                "retries": integer,
+                "failures": integer,
+            })),
+            "by_endpoint": {
+                "type": "object",
+                "additionalProperties": object(&["requests", "failed", "total_response_time_ms"], json!({
+                    "requests": integer,
+                    "failed": integer,
+                    "total_response_time_ms": integer,
+                })),
+            },
+        })),
+        "CacheStatsResponse": object(&["enabled", "backend", "entries", "approximate_size_bytes", "hits", "misses", "hit_ratio"], json!({
+            "enabled": boolean,
+            "backend": { "type": "string", "enum": ["memory", "disk"] },
+            "entries": integer,
+            "approximate_size_bytes": integer,
+            "hits": integer,
+            "misses": integer,
+            "hit_ratio": { "type": "number" },
+        })),
+        "CacheInvalidationResponse": object(&["removed"], json!({
+            "removed": integer,
+        })),
+    })
+}
+
+/// Operation with a success response and the error responses every route can return
+fn operation(summary: &str, parameters: Vec<Value>, success: Value) -> Value {
+    let error = json_response("Error", schema_ref("ErrorResponse"));
+    let mut operation = json!({
+        "summary": summary,
+        "responses": {
+            "200": success,
+            "400": error,
+            "404": error,
+            "500": error,
+            "504": error,
+        },
+    });
+    if !parameters.is_empty() {
+        operation["parameters"] = Value::Array(parameters);
+    }
+    operation
+}
+
+fn with_body(mut operation: Value, schema: Value) -> Value {
+    operation["requestBody"] = json!({
+        "required": true,
+        "content": { "application/json": { "schema": schema } },
+    });
+    operation
+}
+
+fn json_response(description: &str, schema: Value) -> Value {
+    json!({
+        "description": description,
+        "content": { "application/json": { "schema": schema } },
+    })
+}
+
+fn path_param(name: &str, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "path",
+        "required": true,
+        "description": description,
+        "schema": { "type": "string" },
+    })
+}
+
+fn query_param(name: &str, kind: &str, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "query",
+        "description": description,
+        "schema": { "type": kind },
+    })
+}
+
+fn required(mut parameter: Value) -> Value {
+    parameter["required"] = Value::Bool(true);
+    parameter
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+/// Reference that may be `null` (OpenAPI 3.0 cannot mark a `$ref` itself nullable)
+fn nullable(schema: Value) -> Value {
+    json!({ "nullable": true, "allOf": [schema] })
+}
+
+fn array_of(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn string_map() -> Value {
+    json!({ "type": "object", "additionalProperties": { "type": "string" } })
+}
+
+fn object(required: &[&str], properties: Value) -> Value {
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_schema_reference_resolves() {
+        let spec = spec();
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+
+        fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(target)) = map.get("$ref") {
+                        refs.push(target.clone());
+                    }
+                    map.values().for_each(|v| collect_refs(v, refs));
+                }
+                Value::Array(items) => items.iter().for_each(|v| collect_refs(v, refs)),
+                _ => {}
+            }
+        }
+        let mut refs = Vec::new();
+        collect_refs(&spec, &mut refs);
+
+        assert!(!refs.is_empty());
+        for target in refs {
+            let name = target.strip_prefix("#/components/schemas/").unwrap();
+            assert!(schemas.contains_key(name), "dangling reference {}", target);
+        }
+    }
+}
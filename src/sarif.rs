@@ -0,0 +1,151 @@
+//! Minimal SARIF 2.1.0 serializer for lockfile findings
+//!
+//! Only the subset of the format consumed by code-scanning tools is modelled:
+//! one run, the rules that fired, and one result per finding located at the
+//! lockfile and at the `crate@version` it concerns.
+
+use crate::types::{LockFinding, LockFindingKind};
+use serde::Serialize;
+
+/// SARIF specification version emitted
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// JSON schema of the emitted SARIF version
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Top-level SARIF log
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<SarifRun>,
+}
+
+/// A single analysis run
+#[derive(Debug, Serialize)]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub information_uri: &'static str,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: &'static str,
+    pub short_description: SarifMessage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+/// A single finding
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: &'static str,
+    pub level: &'static str,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+    pub logical_locations: Vec<SarifLogicalLocation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLogicalLocation {
+    pub fully_qualified_name: String,
+    pub kind: &'static str,
+}
+
+impl SarifLog {
+    /// Build a report for findings in the lockfile at `lockfile_uri`
+    pub fn from_lock_findings(lockfile_uri: &str, findings: &[LockFinding]) -> Self {
+        let mut fired: Vec<LockFindingKind> = Vec::new();
+        for finding in findings {
+            if !fired.contains(&finding.kind) {
+                fired.push(finding.kind);
+            }
+        }
+
+        let rules = fired
+            .iter()
+            .map(|kind| SarifRule {
+                id: kind.rule_id(),
+                short_description: SarifMessage {
+                    text: kind.description().to_string(),
+                },
+            })
+            .collect();
+
+        let results = findings
+            .iter()
+            .map(|finding| SarifResult {
+                rule_id: finding.kind.rule_id(),
+                level: "error",
+                message: SarifMessage {
+                    text: finding.message(),
+                },
+                locations: vec![SarifLocation {
+                    physical_location: SarifPhysicalLocation {
+                        artifact_location: SarifArtifactLocation {
+                            uri: lockfile_uri.to_string(),
+                        },
+                    },
+                    logical_locations: vec![SarifLogicalLocation {
+                        fully_qualified_name: format!("{}@{}", finding.crate_name, finding.version),
+                        kind: "package",
+                    }],
+                }],
+            })
+            .collect();
+
+        Self {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: crate::NAME,
+                        version: crate::VERSION,
+                        information_uri: "https://github.com/sandlbn/crate-checker",
+                        rules,
+                    },
+                },
+                results,
+            }],
+        }
+    }
+}
@@ -1,21 +1,30 @@
 //! HTTP server implementation for the crate checker API
 
+use crate::cache::{Cache, CacheEntry};
 use crate::client::CrateClient;
 use crate::config::AppConfig;
 use crate::error::{CrateCheckerError, Result};
 use crate::types::*;
-use crate::utils::validate_batch_input;
+use crate::utils::{is_latest_keyword, resolve_version, validate_batch_input};
 use axum::{
-    extract::{Path, Query, State},
-    http::{Method, StatusCode},
-    response::Json,
-    routing::{get, post},
+    body::Body,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        MatchedPath, Path, Query, State,
+    },
+    http::{header, HeaderMap, Method, Request, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, post},
     Router,
 };
 use chrono::Utc;
 use dashmap::DashMap;
+use futures::stream::{self, StreamExt};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::future::Future;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -32,17 +41,10 @@ pub struct AppState {
     pub client: CrateClient,
     pub config: AppConfig,
     pub metrics: Arc<ServerMetrics>,
-    pub cache: Arc<DashMap<String, CacheEntry>>,
+    pub cache: Arc<dyn Cache>,
     pub start_time: Instant,
 }
 
-/// Cached response entry
-#[derive(Clone)]
-pub struct CacheEntry {
-    pub data: Value,
-    pub expires_at: Instant,
-}
-
 /// Server metrics
 #[derive(Default)]
 pub struct ServerMetrics {
@@ -52,13 +54,42 @@ pub struct ServerMetrics {
     pub cache_hits: AtomicU64,
     pub cache_misses: AtomicU64,
     pub total_response_time_ms: AtomicU64,
+    /// Requests per response-time bucket of [`RESPONSE_TIME_BUCKETS_MS`] (not cumulative);
+    /// the extra last slot counts slower requests
+    pub response_time_buckets: [AtomicU64; RESPONSE_TIME_BUCKETS_MS.len() + 1],
+    /// Counters per route pattern
+    pub by_endpoint: DashMap<String, EndpointMetrics>,
 }
 
+/// Upper bounds of the response-time histogram buckets, in milliseconds
+pub const RESPONSE_TIME_BUCKETS_MS: [u64; 10] = [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+/// Content type of the Prometheus text exposition format
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Response header carrying the total number of search matches across all pages
+pub const SEARCH_TOTAL_HEADER: &str = "x-total-count";
+
 impl ServerMetrics {
-    pub fn record_request(&self, success: bool, response_time_ms: u64) {
+    /// Record a finished request to `endpoint`, the route pattern it was served by
+    pub fn record_request(&self, endpoint: &str, success: bool, response_time_ms: u64) {
+        {
+            let mut stats = self.by_endpoint.entry(endpoint.to_string()).or_default();
+            stats.requests += 1;
+            stats.total_response_time_ms += response_time_ms;
+            if !success {
+                stats.failed += 1;
+            }
+        }
+
         self.requests_total.fetch_add(1, Ordering::Relaxed);
         self.total_response_time_ms
             .fetch_add(response_time_ms, Ordering::Relaxed);
+        let bucket = RESPONSE_TIME_BUCKETS_MS
+            .iter()
+            .position(|&le| response_time_ms <= le)
+            .unwrap_or(RESPONSE_TIME_BUCKETS_MS.len());
+        self.response_time_buckets[bucket].fetch_add(1, Ordering::Relaxed);
 
         if success {
             self.requests_successful.fetch_add(1, Ordering::Relaxed);
@@ -91,33 +122,169 @@ impl ServerMetrics {
             cache_hits: self.cache_hits.load(Ordering::Relaxed),
             cache_misses: self.cache_misses.load(Ordering::Relaxed),
             uptime_seconds: 0, // Will be set by the handler
+            upstream: ClientStats::default(),
+            by_endpoint: self
+                .by_endpoint
+                .iter()
+                .map(|entry| (entry.key().clone(), entry.value().clone()))
+                .collect(),
+        }
+    }
+
+    /// Render the metrics in the Prometheus text exposition format
+    pub fn render_prometheus(&self, uptime_seconds: u64, upstream: &ClientStats) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: u64| {
+            out.push_str(&format!(
+                "# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n"
+            ));
+        };
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+
+        metric(
+            "crate_checker_requests_total",
+            "counter",
+            "API requests handled.",
+            load(&self.requests_total),
+        );
+        metric(
+            "crate_checker_requests_successful_total",
+            "counter",
+            "API requests that succeeded.",
+            load(&self.requests_successful),
+        );
+        metric(
+            "crate_checker_requests_failed_total",
+            "counter",
+            "API requests that failed.",
+            load(&self.requests_failed),
+        );
+        metric(
+            "crate_checker_cache_hits_total",
+            "counter",
+            "Response cache hits.",
+            load(&self.cache_hits),
+        );
+        metric(
+            "crate_checker_cache_misses_total",
+            "counter",
+            "Response cache misses.",
+            load(&self.cache_misses),
+        );
+        metric(
+            "crate_checker_upstream_requests_total",
+            "counter",
+            "Requests sent to crates.io.",
+            upstream.requests,
+        );
+        metric(
+            "crate_checker_upstream_retries_total",
+            "counter",
+            "Retried requests to crates.io.",
+            upstream.retries,
+        );
+        metric(
+            "crate_checker_upstream_failures_total",
+            "counter",
+            "Requests to crates.io that failed after all retries.",
+            upstream.failures,
+        );
+        metric(
+            "crate_checker_uptime_seconds",
+            "gauge",
+            "Seconds since the server started.",
+            uptime_seconds,
+        );
+
+        let name = "crate_checker_response_time_seconds";
+        out.push_str(&format!(
+            "# HELP {name} API response time.\n# TYPE {name} histogram\n"
+        ));
+        let mut cumulative = 0;
+        for (index, le_ms) in RESPONSE_TIME_BUCKETS_MS.iter().enumerate() {
+            cumulative += load(&self.response_time_buckets[index]);
+            out.push_str(&format!(
+                "{name}_bucket{{le=\"{}\"}} {cumulative}\n",
+                *le_ms as f64 / 1000.0
+            ));
         }
+        cumulative += load(&self.response_time_buckets[RESPONSE_TIME_BUCKETS_MS.len()]);
+        out.push_str(&format!("{name}_bucket{{le=\"+Inf\"}} {cumulative}\n"));
+        out.push_str(&format!(
+            "{name}_sum {}\n",
+            load(&self.total_response_time_ms) as f64 / 1000.0
+        ));
+        out.push_str(&format!("{name}_count {cumulative}\n"));
+
+        out
     }
 }
 
 /// Start the HTTP server
+///
+/// Runs until Ctrl-C (SIGINT) or SIGTERM, then stops accepting connections and lets
+/// in-flight requests finish.
 pub async fn start_server(config: AppConfig) -> Result<()> {
+    start_server_with_shutdown(config, shutdown_signal()).await
+}
+
+/// Start the HTTP server, shutting down gracefully once `shutdown` resolves
+pub async fn start_server_with_shutdown(
+    config: AppConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
     info!("Starting server on {}", config.bind_address());
 
     // Validate configuration
     config.validate().map_err(CrateCheckerError::validation)?;
 
     // Create client with configuration
-    let client = CrateClient::builder()
+    let mut client_builder = CrateClient::builder()
         .base_url(&config.crates_io.api_url)
         .user_agent(&config.crates_io.user_agent)
         .timeout(Duration::from_secs(config.crates_io.timeout_seconds))
-        .build()?;
+        .max_concurrent(config.crates_io.max_concurrent)
+        // Cap upstream calls across all inbound requests, not just within one batch
+        .concurrency_limit(config.crates_io.max_concurrent)
+        .retry_attempts(config.crates_io.retry_attempts)
+        .slow_request_threshold(Duration::from_millis(
+            config.crates_io.slow_request_threshold_ms,
+        ));
+    for (name, value) in &config.crates_io.headers {
+        client_builder = client_builder.header(name, value);
+    }
+    if let Some(token) = &config.crates_io.registry_token {
+        client_builder = client_builder.registry_token(token);
+    }
+    if let Some(token) = &config.crates_io.token {
+        client_builder = client_builder.bearer_token(token);
+    }
+    if config.rate_limiting.enabled {
+        client_builder = client_builder.rate_limit(
+            config.rate_limiting.requests_per_minute,
+            config.rate_limiting.burst_size,
+        );
+    }
+    let client = client_builder.build()?;
 
     // Create shared state
     let state = AppState {
         client,
         config: config.clone(),
         metrics: Arc::new(ServerMetrics::default()),
-        cache: Arc::new(DashMap::new()),
+        cache: crate::cache::from_config(&config.cache)?,
         start_time: Instant::now(),
     };
 
+    // Sweep expired cache entries in the background until shutdown
+    let cache_cleanup =
+        (config.cache.enabled && config.cache.cleanup_interval_seconds > 0).then(|| {
+            crate::cache::spawn_cleanup(
+                state.cache.clone(),
+                Duration::from_secs(config.cache.cleanup_interval_seconds),
+            )
+        });
+
     // Build the application router
     let app = create_router(state);
 
@@ -129,18 +296,56 @@ pub async fn start_server(config: AppConfig) -> Result<()> {
     info!("API docs: http://{}/", config.bind_address());
 
     // Start server
-    axum::serve(listener, app).await?;
+    let served = axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            shutdown.await;
+            info!("Shutdown requested, waiting for in-flight requests to finish");
+        })
+        .await;
+
+    if let Some(cache_cleanup) = cache_cleanup {
+        cache_cleanup.abort();
+    }
+    served?;
 
+    info!("Server shut down");
     Ok(())
 }
 
+/// Resolve on Ctrl-C or, on Unix, SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
 /// Create the application router
 fn create_router(state: AppState) -> Router {
     let mut app = Router::new()
-        // Health check
-        .route("/health", get(health_check))
-        // API documentation
-        .route("/", get(api_docs))
         // Core API endpoints
         .route("/api/crates/:name", get(get_crate))
         .route("/api/crates/:name/:version", get(get_crate_version))
@@ -148,13 +353,52 @@ fn create_router(state: AppState) -> Router {
             "/api/crates/:name/:version/deps",
             get(get_crate_dependencies),
         )
+        .route("/api/crates/:name/:version/info", get(get_version_info))
         .route("/api/crates/:name/stats", get(get_crate_stats))
+        .route("/api/crates/:name/status", get(get_crate_status))
+        .route("/api/crates/:name/reverse", get(get_reverse_dependencies))
         .route("/api/search", get(search_crates))
         .route("/api/batch", post(handle_batch))
-        // Metrics and monitoring
+        .route("/api/batch/stream", get(batch_stream))
+        .route("/api/yanked-check", post(yanked_check))
+        .route("/api/exists", post(crates_exist))
+        // Cache management
+        .route("/api/cache/stats", get(get_cache_stats))
+        .route("/api/cache", delete(flush_cache))
+        .route("/api/cache/:key_prefix", delete(invalidate_cache));
+
+    // Metrics and monitoring
+    let monitoring = Router::new()
         .route("/metrics", get(get_metrics))
+        .route("/metrics/prometheus", get(get_prometheus_metrics))
+        .route("/api/config", get(get_config));
+
+    // Monitoring endpoints guarded by their own metrics token skip the API key check
+    if state.config.server.metrics_token.is_none() {
+        app = app.merge(monitoring.clone());
+    }
+    if state.config.server.api_key.is_some() {
+        app = app.route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_key,
+        ));
+    }
+    if state.config.server.metrics_token.is_some() {
+        app = app.merge(monitoring);
+    }
+
+    let mut app = app
+        // Health check
+        .route("/health", get(health_check))
+        // API documentation
+        .route("/", get(api_docs))
+        .route("/openapi.json", get(openapi_spec))
         // Add state
-        .with_state(state.clone());
+        .with_state(state.clone())
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            enforce_request_timeout,
+        ));
 
     // Add middleware
     let service = ServiceBuilder::new().layer(TraceLayer::new_for_http());
@@ -165,7 +409,7 @@ fn create_router(state: AppState) -> Router {
     if state.config.server.enable_cors {
         app = app.layer(
             CorsLayer::new()
-                .allow_methods([Method::GET, Method::POST])
+                .allow_methods([Method::GET, Method::POST, Method::DELETE])
                 .allow_headers(Any)
                 .allow_origin(Any),
         );
@@ -176,10 +420,11 @@ fn create_router(state: AppState) -> Router {
 
 /// Health check endpoint
 async fn health_check(State(state): State<AppState>) -> Json<HealthResponse> {
+    state.metrics.record_request("/health", true, 0);
     Json(HealthResponse {
         status: "healthy".to_string(),
         timestamp: Utc::now(),
-        version: "1.0.0".to_string(),
+        version: crate::VERSION.to_string(),
         uptime_seconds: state.start_time.elapsed().as_secs(),
     })
 }
@@ -193,20 +438,41 @@ async fn api_docs() -> &'static str {
 ### Health Check
 - `GET /health` - Server health status
 
+### API Description
+- `GET /openapi.json` - OpenAPI 3.0 document describing these endpoints
+
 ### Crate Information
 - `GET /api/crates/{name}` - Get crate information
 - `GET /api/crates/{name}/{version}` - Check specific version
 - `GET /api/crates/{name}/{version}/deps` - Get dependencies
+- `GET /api/crates/{name}/{version}/info` - Get metadata of a single version
 - `GET /api/crates/{name}/stats` - Get download statistics
+- `GET /api/crates/{name}/status` - Get yank status (`exists`, `yanked`, `partially_yanked` or `not_found`)
+- `GET /api/crates/{name}/reverse?limit=10` - Get crates that depend on a crate
 
 ### Search
 - `GET /api/search?q={query}&limit={limit}` - Search crates
 
 ### Batch Operations
 - `POST /api/batch` - Process multiple crates
+- `POST /api/batch?stream=ndjson` - Stream results as newline-delimited JSON
+- `GET /api/batch/stream` - WebSocket: send a batch body, receive each result as it completes
+- `POST /api/yanked-check` - Check whether exact versions (`{name: version}`) are yanked
+- `POST /api/exists` - Check which crates (`{"crates": [...]}`) exist
 
 ### Monitoring
 - `GET /metrics` - Server metrics
+- `GET /metrics/prometheus` - Server metrics in the Prometheus text format
+- `GET /api/config` - Effective configuration with secrets redacted
+
+### Cache Management
+- `GET /api/cache/stats` - Entry count, approximate size and hit/miss ratio of the response cache
+- `DELETE /api/cache` - Remove every cached response
+- `DELETE /api/cache/{key_prefix}` - Remove cached responses whose key starts with a prefix (e.g. `crate:serde`)
+
+### Authentication
+When `[server] api_key` is set, every endpoint except `/health`, `/` and `/openapi.json` requires
+`Authorization: Bearer <api_key>`. Monitoring endpoints use `metrics_token` instead when it is set.
 
 ## Examples
 
@@ -225,21 +491,30 @@ curl -X POST http://localhost:3000/api/batch \
 "#
 }
 
+/// OpenAPI description of the API
+async fn openapi_spec() -> Json<Value> {
+    Json(crate::openapi::spec())
+}
+
 /// Get crate information
 async fn get_crate(
     State(state): State<AppState>,
     Path(name): Path<String>,
-) -> std::result::Result<Json<CrateInfo>, AppError> {
+    Query(query): Query<ExplainQuery>,
+    headers: HeaderMap,
+) -> std::result::Result<Response, AppError> {
     let start_time = Instant::now();
 
     // Check cache first
     let cache_key = format!("crate:{}", name);
     if let Some(cached) = get_from_cache(&state, &cache_key) {
         state.metrics.record_cache_hit();
-        state
-            .metrics
-            .record_request(true, start_time.elapsed().as_millis() as u64);
-        return Ok(Json(serde_json::from_value(cached.data)?));
+        state.metrics.record_request(
+            "/api/crates/:name",
+            true,
+            start_time.elapsed().as_millis() as u64,
+        );
+        return cacheable_response(&state, &headers, &query, &cache_key, true, cached.data);
     }
 
     state.metrics.record_cache_miss();
@@ -251,16 +526,27 @@ async fn get_crate(
                 set_cache(&state, &cache_key, serde_json::to_value(&info)?);
             }
 
-            state
-                .metrics
-                .record_request(true, start_time.elapsed().as_millis() as u64);
-            Ok(Json(info))
+            state.metrics.record_request(
+                "/api/crates/:name",
+                true,
+                start_time.elapsed().as_millis() as u64,
+            );
+            cacheable_response(
+                &state,
+                &headers,
+                &query,
+                &cache_key,
+                false,
+                serde_json::to_value(&info)?,
+            )
         }
         Err(e) => {
             error!("Failed to get crate info for '{}': {}", name, e);
-            state
-                .metrics
-                .record_request(false, start_time.elapsed().as_millis() as u64);
+            state.metrics.record_request(
+                "/api/crates/:name",
+                false,
+                start_time.elapsed().as_millis() as u64,
+            );
             Err(AppError::from(e))
         }
     }
@@ -270,70 +556,114 @@ async fn get_crate(
 async fn get_crate_version(
     State(state): State<AppState>,
     Path((name, version)): Path<(String, String)>,
-) -> std::result::Result<Json<CrateCheckResult>, AppError> {
+    Query(query): Query<ExplainQuery>,
+    headers: HeaderMap,
+) -> std::result::Result<Response, AppError> {
     let start_time = Instant::now();
 
     let cache_key = format!("crate:{}:{}", name, version);
     if let Some(cached) = get_from_cache(&state, &cache_key) {
         state.metrics.record_cache_hit();
-        state
-            .metrics
-            .record_request(true, start_time.elapsed().as_millis() as u64);
-        return Ok(Json(serde_json::from_value(cached.data)?));
+        state.metrics.record_request(
+            "/api/crates/:name/:version",
+            true,
+            start_time.elapsed().as_millis() as u64,
+        );
+        return cacheable_response(&state, &headers, &query, &cache_key, true, cached.data);
     }
 
     state.metrics.record_cache_miss();
 
-    let result = if version == "latest" {
-        match state.client.get_crate_info(&name).await {
-            Ok(info) => CrateCheckResult {
+    let result = if is_latest_keyword(&version) {
+        // The client picks the latest version, so pre-releases are hidden as elsewhere
+        let checked = async {
+            let info = state.client.get_crate_info(&name).await?;
+            let latest = state.client.get_latest_version(&name).await?;
+            Ok::<_, CrateCheckerError>((info, latest))
+        }
+        .await;
+        match checked {
+            Ok((info, latest)) => CrateCheckResult {
                 crate_name: name.clone(),
                 exists: true,
-                latest_version: Some(info.newest_version.clone()),
+                outcome: CheckOutcome::Exists,
+                latest_version: Some(latest.clone()),
                 requested_version: Some("latest".to_string()),
                 version_exists: Some(true),
+                resolved_version: Some(latest),
+                suggested_version: None,
                 error: None,
                 info: Some(info),
+                dependencies: None,
+                download_stats: None,
+                versions: None,
             },
             Err(e) => CrateCheckResult {
                 crate_name: name.clone(),
                 exists: false,
+                outcome: CheckOutcome::from_error(&e),
                 latest_version: None,
                 requested_version: Some(version),
                 version_exists: None,
+                resolved_version: None,
+                suggested_version: None,
                 error: Some(e.to_string()),
                 info: None,
+                dependencies: None,
+                download_stats: None,
+                versions: None,
             },
         }
     } else {
         // Check specific version
         match state.client.get_all_versions(&name).await {
             Ok(versions) => {
-                let version_exists = versions.iter().any(|v| v.num == version);
+                let (resolved_version, suggested_version) =
+                    match resolve_version(&version, &versions) {
+                        VersionResolution::Found(resolved) => (Some(resolved), None),
+                        VersionResolution::NotFound { suggestion } => (None, suggestion),
+                    };
+                let version_exists = resolved_version.is_some();
                 let info = if version_exists {
                     state.client.get_crate_info(&name).await.ok()
                 } else {
                     None
                 };
+                let latest_version = match &info {
+                    Some(_) => state.client.get_latest_version(&name).await.ok(),
+                    None => None,
+                };
 
                 CrateCheckResult {
                     crate_name: name.clone(),
                     exists: true,
-                    latest_version: info.as_ref().map(|i| i.newest_version.clone()),
+                    outcome: CheckOutcome::Exists,
+                    latest_version,
                     requested_version: Some(version),
                     version_exists: Some(version_exists),
+                    resolved_version,
+                    suggested_version,
                     error: None,
                     info,
+                    dependencies: None,
+                    download_stats: None,
+                    versions: None,
                 }
             }
             Err(e) => CrateCheckResult {
                 crate_name: name.clone(),
                 exists: false,
+                outcome: CheckOutcome::from_error(&e),
                 latest_version: None,
                 requested_version: Some(version),
                 version_exists: None,
+                resolved_version: None,
+                suggested_version: None,
                 error: Some(e.to_string()),
                 info: None,
+                dependencies: None,
+                download_stats: None,
+                versions: None,
             },
         }
     };
@@ -343,10 +673,19 @@ async fn get_crate_version(
         set_cache(&state, &cache_key, serde_json::to_value(&result)?);
     }
 
-    state
-        .metrics
-        .record_request(true, start_time.elapsed().as_millis() as u64);
-    Ok(Json(result))
+    state.metrics.record_request(
+        "/api/crates/:name/:version",
+        true,
+        start_time.elapsed().as_millis() as u64,
+    );
+    cacheable_response(
+        &state,
+        &headers,
+        &query,
+        &cache_key,
+        false,
+        serde_json::to_value(&result)?,
+    )
 }
 
 /// Get crate dependencies
@@ -356,13 +695,15 @@ async fn get_crate_dependencies(
 ) -> std::result::Result<Json<Vec<Dependency>>, AppError> {
     let start_time = Instant::now();
 
-    let actual_version = if version == "latest" {
+    let actual_version = if is_latest_keyword(&version) {
         match state.client.get_latest_version(&name).await {
             Ok(v) => v,
             Err(e) => {
-                state
-                    .metrics
-                    .record_request(false, start_time.elapsed().as_millis() as u64);
+                state.metrics.record_request(
+                    "/api/crates/:name/:version/deps",
+                    false,
+                    start_time.elapsed().as_millis() as u64,
+                );
                 return Err(AppError::from(e));
             }
         }
@@ -376,9 +717,11 @@ async fn get_crate_dependencies(
         .await
     {
         Ok(deps) => {
-            state
-                .metrics
-                .record_request(true, start_time.elapsed().as_millis() as u64);
+            state.metrics.record_request(
+                "/api/crates/:name/:version/deps",
+                true,
+                start_time.elapsed().as_millis() as u64,
+            );
             Ok(Json(deps))
         }
         Err(e) => {
@@ -386,9 +729,58 @@ async fn get_crate_dependencies(
                 "Failed to get dependencies for '{}:{}': {}",
                 name, actual_version, e
             );
-            state
-                .metrics
-                .record_request(false, start_time.elapsed().as_millis() as u64);
+            state.metrics.record_request(
+                "/api/crates/:name/:version/deps",
+                false,
+                start_time.elapsed().as_millis() as u64,
+            );
+            Err(AppError::from(e))
+        }
+    }
+}
+
+/// Get the metadata of a single crate version
+async fn get_version_info(
+    State(state): State<AppState>,
+    Path((name, version)): Path<(String, String)>,
+) -> std::result::Result<Json<Version>, AppError> {
+    let start_time = Instant::now();
+
+    let actual_version = if is_latest_keyword(&version) {
+        match state.client.get_latest_version(&name).await {
+            Ok(v) => v,
+            Err(e) => {
+                state.metrics.record_request(
+                    "/api/crates/:name/:version/info",
+                    false,
+                    start_time.elapsed().as_millis() as u64,
+                );
+                return Err(AppError::from(e));
+            }
+        }
+    } else {
+        version
+    };
+
+    match state.client.get_version(&name, &actual_version).await {
+        Ok(version) => {
+            state.metrics.record_request(
+                "/api/crates/:name/:version/info",
+                true,
+                start_time.elapsed().as_millis() as u64,
+            );
+            Ok(Json(version))
+        }
+        Err(e) => {
+            error!(
+                "Failed to get version info for '{}:{}': {}",
+                name, actual_version, e
+            );
+            state.metrics.record_request(
+                "/api/crates/:name/:version/info",
+                false,
+                start_time.elapsed().as_millis() as u64,
+            );
             Err(AppError::from(e))
         }
     }
@@ -403,16 +795,86 @@ async fn get_crate_stats(
 
     match state.client.get_download_stats(&name).await {
         Ok(stats) => {
-            state
-                .metrics
-                .record_request(true, start_time.elapsed().as_millis() as u64);
+            state.metrics.record_request(
+                "/api/crates/:name/stats",
+                true,
+                start_time.elapsed().as_millis() as u64,
+            );
             Ok(Json(stats))
         }
         Err(e) => {
             error!("Failed to get stats for '{}': {}", name, e);
-            state
-                .metrics
-                .record_request(false, start_time.elapsed().as_millis() as u64);
+            state.metrics.record_request(
+                "/api/crates/:name/stats",
+                false,
+                start_time.elapsed().as_millis() as u64,
+            );
+            Err(AppError::from(e))
+        }
+    }
+}
+
+/// Get the yank status of a crate and its yanked versions
+async fn get_crate_status(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> std::result::Result<Json<CrateStatusReport>, AppError> {
+    let start_time = Instant::now();
+
+    match state.client.crate_status_report(&name).await {
+        Ok(report) => {
+            state.metrics.record_request(
+                "/api/crates/:name/status",
+                true,
+                start_time.elapsed().as_millis() as u64,
+            );
+            Ok(Json(report))
+        }
+        Err(e) => {
+            error!("Failed to get status for '{}': {}", name, e);
+            state.metrics.record_request(
+                "/api/crates/:name/status",
+                false,
+                start_time.elapsed().as_millis() as u64,
+            );
+            Err(AppError::from(e))
+        }
+    }
+}
+
+/// Get the crates that depend on a crate
+async fn get_reverse_dependencies(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> std::result::Result<Json<ReverseDependencies>, AppError> {
+    let start_time = Instant::now();
+
+    let limit = params
+        .get("limit")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(10);
+
+    match state
+        .client
+        .get_reverse_dependency_report(&name, Some(limit))
+        .await
+    {
+        Ok(report) => {
+            state.metrics.record_request(
+                "/api/crates/:name/reverse",
+                true,
+                start_time.elapsed().as_millis() as u64,
+            );
+            Ok(Json(report))
+        }
+        Err(e) => {
+            error!("Failed to get reverse dependencies for '{}': {}", name, e);
+            state.metrics.record_request(
+                "/api/crates/:name/reverse",
+                false,
+                start_time.elapsed().as_millis() as u64,
+            );
             Err(AppError::from(e))
         }
     }
@@ -422,30 +884,57 @@ async fn get_crate_stats(
 async fn search_crates(
     State(state): State<AppState>,
     Query(params): Query<HashMap<String, String>>,
-) -> std::result::Result<Json<Vec<CrateSearchResult>>, AppError> {
+) -> std::result::Result<Response, AppError> {
     let start_time = Instant::now();
 
     let query = params
         .get("q")
         .ok_or_else(|| AppError::BadRequest("Missing 'q' parameter".to_string()))?;
 
-    let limit = params
-        .get("limit")
+    // `limit` is the original name of `per_page` and is still accepted
+    let per_page: usize = params
+        .get("per_page")
+        .or_else(|| params.get("limit"))
         .and_then(|l| l.parse().ok())
         .unwrap_or(10);
+    let page: usize = params
+        .get("page")
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(1)
+        .max(1);
+    let sort = params
+        .get("sort")
+        .map(|s| s.parse::<SearchSort>())
+        .transpose()
+        .map_err(AppError::from)?;
+
+    let search = SearchParams {
+        q: query.clone(),
+        limit: Some(per_page),
+        offset: Some((page - 1) * per_page.clamp(1, 100)),
+        sort,
+    };
 
-    match state.client.search_crates(query, Some(limit)).await {
+    match state.client.search_page(&search).await {
         Ok(results) => {
-            state
-                .metrics
-                .record_request(true, start_time.elapsed().as_millis() as u64);
-            Ok(Json(results))
+            state.metrics.record_request(
+                "/api/search",
+                true,
+                start_time.elapsed().as_millis() as u64,
+            );
+            Ok((
+                [(SEARCH_TOTAL_HEADER, results.total.to_string())],
+                Json(results.crates),
+            )
+                .into_response())
         }
         Err(e) => {
             error!("Failed to search for '{}': {}", query, e);
-            state
-                .metrics
-                .record_request(false, start_time.elapsed().as_millis() as u64);
+            state.metrics.record_request(
+                "/api/search",
+                false,
+                start_time.elapsed().as_millis() as u64,
+            );
             Err(AppError::from(e))
         }
     }
@@ -454,32 +943,62 @@ async fn search_crates(
 /// Handle batch operations
 async fn handle_batch(
     State(state): State<AppState>,
+    Query(query): Query<BatchQuery>,
     Json(request): Json<BatchRequest>,
-) -> std::result::Result<Json<BatchResponse>, AppError> {
+) -> std::result::Result<Response, AppError> {
     let start_time = Instant::now();
 
     validate_batch_input(&request.input).map_err(AppError::from)?;
 
+    match query.stream.as_deref() {
+        None => {}
+        Some("ndjson") => {
+            let checks = request.input.into_checks()?;
+            return Ok(stream_batch_ndjson(state, checks));
+        }
+        Some(other) => {
+            return Err(AppError::BadRequest(format!(
+                "Unsupported stream format '{}', expected 'ndjson'",
+                other
+            )))
+        }
+    }
+
+    let parallel = request.options.parallel;
     let result = match request.input {
+        BatchInput::CrateVersionMap(map) if parallel => {
+            state
+                .client
+                .process_crate_version_map_concurrent(map)
+                .await?
+        }
         BatchInput::CrateVersionMap(map) => state.client.process_crate_version_map(map).await?,
         BatchInput::CrateList { crates } => {
-            let results = state.client.process_crate_list(crates).await?;
-            let successful = results.iter().filter(|r| r.error.is_none()).count();
-            let failed = results.len() - successful;
+            let results = if parallel {
+                state.client.process_crate_list_concurrent(crates).await?
+            } else {
+                state.client.process_crate_list(crates).await?
+            };
             let total_processed = results.len();
-
-            BatchResult {
+            BatchResult::new(
                 results,
                 total_processed,
-                successful,
-                failed,
-                processing_time_ms: start_time.elapsed().as_millis() as u64,
-            }
+                start_time.elapsed().as_millis() as u64,
+            )
         }
         BatchInput::Operations { operations } => {
+            // Requests may lower, but not raise, the configured concurrency
+            let max_concurrent = if parallel {
+                request
+                    .options
+                    .max_concurrent
+                    .min(state.client.max_concurrent())
+            } else {
+                1
+            };
             state
                 .client
-                .process_batch_operations(operations)
+                .process_batch_operations(operations, max_concurrent)
                 .await?
                 .result
         }
@@ -493,15 +1012,394 @@ async fn handle_batch(
 
     state
         .metrics
-        .record_request(true, start_time.elapsed().as_millis() as u64);
-    Ok(Json(response))
+        .record_request("/api/batch", true, start_time.elapsed().as_millis() as u64);
+    Ok(Json(response).into_response())
+}
+
+/// Stream batch results as newline-delimited JSON, one result per line as each check
+/// completes, followed by a `{"summary": ...}` line
+fn stream_batch_ndjson(state: AppState, checks: Vec<BatchCheck>) -> Response {
+    let start_time = Instant::now();
+    let (tx, rx) = futures::channel::mpsc::unbounded::<std::result::Result<String, Infallible>>();
+
+    tokio::spawn(async move {
+        let total_processed = checks.len();
+        let (mut successful, mut missing, mut failed) = (0, 0, 0);
+        let mut results = state.client.check_crates_unordered(checks);
+
+        while let Some(result) = results.next().await {
+            match result.outcome {
+                CheckOutcome::Exists => successful += 1,
+                CheckOutcome::Missing => missing += 1,
+                CheckOutcome::Error => failed += 1,
+            }
+            let line = serde_json::to_string(&result).unwrap_or_default() + "\n";
+            if tx.unbounded_send(Ok(line)).is_err() {
+                // Client went away; stop issuing upstream requests
+                return;
+            }
+        }
+
+        let summary = BatchSummary {
+            request_id: uuid::Uuid::new_v4().to_string(),
+            status: "completed".to_string(),
+            total_processed,
+            successful,
+            missing,
+            failed,
+            processing_time_ms: start_time.elapsed().as_millis() as u64,
+        };
+        let line = serde_json::json!({ "summary": summary }).to_string() + "\n";
+        let _ = tx.unbounded_send(Ok(line));
+
+        state
+            .metrics
+            .record_request("/api/batch", true, start_time.elapsed().as_millis() as u64);
+    });
+
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(rx),
+    )
+        .into_response()
+}
+
+/// Run a batch over a WebSocket, sending each result as it completes
+///
+/// The client sends one text message holding the same body as `POST /api/batch`.
+async fn batch_stream(State(state): State<AppState>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| stream_batch_ws(state, socket))
+}
+
+/// Serve one WebSocket batch: a message per crate, then `{"summary": BatchResult}`
+async fn stream_batch_ws(state: AppState, mut socket: WebSocket) {
+    let start_time = Instant::now();
+
+    let request = match socket.recv().await {
+        Some(Ok(Message::Text(text))) => serde_json::from_str::<BatchRequest>(&text)
+            .map_err(CrateCheckerError::from)
+            .and_then(|request| {
+                validate_batch_input(&request.input)?;
+                request.input.into_checks()
+            }),
+        // Closed before sending a batch
+        _ => return,
+    };
+    let checks = match request {
+        Ok(checks) => checks,
+        Err(e) => {
+            let message = serde_json::json!({ "error": e.to_string() }).to_string();
+            let _ = socket.send(Message::Text(message)).await;
+            let _ = socket.send(Message::Close(None)).await;
+            state.metrics.record_request(
+                "/api/batch/stream",
+                false,
+                start_time.elapsed().as_millis() as u64,
+            );
+            return;
+        }
+    };
+
+    let total_processed = checks.len();
+    let mut results = Vec::with_capacity(total_processed);
+    let mut checked = state.client.check_crates_unordered(checks);
+    while let Some(result) = checked.next().await {
+        let message = serde_json::to_string(&result).unwrap_or_default();
+        if socket.send(Message::Text(message)).await.is_err() {
+            // Client went away; stop issuing upstream requests
+            return;
+        }
+        results.push(result);
+    }
+
+    let summary = BatchResult::new(
+        results,
+        total_processed,
+        start_time.elapsed().as_millis() as u64,
+    );
+    let message = serde_json::json!({ "summary": summary }).to_string();
+    let _ = socket.send(Message::Text(message)).await;
+    let _ = socket.send(Message::Close(None)).await;
+
+    state.metrics.record_request(
+        "/api/batch/stream",
+        true,
+        start_time.elapsed().as_millis() as u64,
+    );
+}
+
+/// Report which of the requested crates exist, using HEAD requests
+async fn crates_exist(
+    State(state): State<AppState>,
+    Json(request): Json<ExistsRequest>,
+) -> std::result::Result<Json<ExistsResponse>, AppError> {
+    let start_time = Instant::now();
+
+    validate_batch_input(&BatchInput::CrateList {
+        crates: request.crates.clone(),
+    })
+    .map_err(AppError::from)?;
+
+    let result = state.client.crates_exist(&request.crates).await;
+    state.metrics.record_request(
+        "/api/exists",
+        result.is_ok(),
+        start_time.elapsed().as_millis() as u64,
+    );
+
+    let results: BTreeMap<String, bool> = result.map_err(AppError::from)?.into_iter().collect();
+    let all_exist = results.values().all(|&exists| exists);
+    Ok(Json(ExistsResponse { results, all_exist }))
+}
+
+/// Report whether each exact `{name: version}` entry is yanked
+async fn yanked_check(
+    State(state): State<AppState>,
+    Json(request): Json<HashMap<String, String>>,
+) -> std::result::Result<Json<YankedCheckResponse>, AppError> {
+    let start_time = Instant::now();
+
+    validate_batch_input(&BatchInput::CrateVersionMap(request.clone())).map_err(AppError::from)?;
+
+    let mut results: Vec<YankedCheckEntry> = stream::iter(request)
+        .map(|(crate_name, version)| {
+            let state = &state;
+            async move {
+                match get_versions_cached(state, &crate_name).await {
+                    Ok(versions) => {
+                        let found = versions.iter().find(|v| v.num == version);
+                        YankedCheckEntry {
+                            exists: found.is_some(),
+                            yanked: found.is_some_and(|v| v.yanked),
+                            crate_name,
+                            version,
+                            error: None,
+                        }
+                    }
+                    Err(e) => YankedCheckEntry {
+                        crate_name,
+                        version,
+                        exists: false,
+                        yanked: false,
+                        error: Some(e.to_string()),
+                    },
+                }
+            }
+        })
+        .buffer_unordered(state.config.crates_io.max_concurrent)
+        .collect()
+        .await;
+    results.sort_by(|a, b| a.crate_name.cmp(&b.crate_name));
+
+    let any_yanked = results.iter().any(|r| r.yanked);
+
+    state.metrics.record_request(
+        "/api/yanked-check",
+        true,
+        start_time.elapsed().as_millis() as u64,
+    );
+    Ok(Json(YankedCheckResponse {
+        results,
+        any_yanked,
+    }))
+}
+
+/// Fetch all versions of a crate, going through the response cache
+async fn get_versions_cached(state: &AppState, name: &str) -> Result<Vec<Version>> {
+    let cache_key = format!("versions:{}", name);
+    if let Some(cached) = get_from_cache(state, &cache_key) {
+        state.metrics.record_cache_hit();
+        return Ok(serde_json::from_value(cached.data)?);
+    }
+
+    state.metrics.record_cache_miss();
+
+    let versions = state.client.get_all_versions(name).await?;
+    set_cache(state, &cache_key, serde_json::to_value(&versions)?);
+    Ok(versions)
 }
 
 /// Get server metrics
-async fn get_metrics(State(state): State<AppState>) -> Json<MetricsResponse> {
+async fn get_metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> std::result::Result<Json<MetricsResponse>, AppError> {
+    require_metrics_token(&state, &headers)?;
+
     let mut metrics = state.metrics.get_metrics();
     metrics.uptime_seconds = state.start_time.elapsed().as_secs();
-    Json(metrics)
+    metrics.upstream = state.client.stats();
+    Ok(Json(metrics))
+}
+
+/// Get server metrics in the Prometheus text exposition format
+async fn get_prometheus_metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> std::result::Result<Response, AppError> {
+    require_metrics_token(&state, &headers)?;
+
+    let body = state
+        .metrics
+        .render_prometheus(state.start_time.elapsed().as_secs(), &state.client.stats());
+    Ok(([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], body).into_response())
+}
+
+/// Get the effective configuration with secrets redacted
+async fn get_config(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> std::result::Result<Json<AppConfig>, AppError> {
+    require_metrics_token(&state, &headers)?;
+
+    Ok(Json(state.config.redacted()))
+}
+
+/// Report the size and hit ratio of the response cache
+async fn get_cache_stats(State(state): State<AppState>) -> Json<CacheStatsResponse> {
+    let start_time = Instant::now();
+
+    let hits = state.metrics.cache_hits.load(Ordering::Relaxed);
+    let misses = state.metrics.cache_misses.load(Ordering::Relaxed);
+    let lookups = hits + misses;
+    let stats = CacheStatsResponse {
+        enabled: state.config.cache.enabled,
+        backend: state.config.cache.backend,
+        entries: state.cache.len(),
+        approximate_size_bytes: state.cache.approximate_size(),
+        hits,
+        misses,
+        hit_ratio: if lookups > 0 {
+            hits as f64 / lookups as f64
+        } else {
+            0.0
+        },
+    };
+
+    state.metrics.record_request(
+        "/api/cache/stats",
+        true,
+        start_time.elapsed().as_millis() as u64,
+    );
+    Json(stats)
+}
+
+/// Remove every cached response
+async fn flush_cache(State(state): State<AppState>) -> Json<CacheInvalidationResponse> {
+    let start_time = Instant::now();
+
+    let removed = state.cache.clear();
+    info!("Flushed {} cache entries", removed);
+
+    state
+        .metrics
+        .record_request("/api/cache", true, start_time.elapsed().as_millis() as u64);
+    Json(CacheInvalidationResponse { removed })
+}
+
+/// Remove cached responses whose key starts with `key_prefix`, e.g. `crate:serde`
+async fn invalidate_cache(
+    State(state): State<AppState>,
+    Path(key_prefix): Path<String>,
+) -> std::result::Result<Json<CacheInvalidationResponse>, AppError> {
+    let start_time = Instant::now();
+
+    if key_prefix.trim().is_empty() {
+        state.metrics.record_request(
+            "/api/cache/:key_prefix",
+            false,
+            start_time.elapsed().as_millis() as u64,
+        );
+        return Err(AppError::BadRequest(
+            "Key prefix must not be empty, use DELETE /api/cache to flush everything".to_string(),
+        ));
+    }
+
+    let removed = state.cache.remove_prefix(&key_prefix);
+    info!(
+        "Invalidated {} cache entries with prefix {}",
+        removed, key_prefix
+    );
+
+    state.metrics.record_request(
+        "/api/cache/:key_prefix",
+        true,
+        start_time.elapsed().as_millis() as u64,
+    );
+    Ok(Json(CacheInvalidationResponse { removed }))
+}
+
+/// Middleware rejecting requests that do not carry the configured API key as a bearer token
+async fn require_api_key(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> std::result::Result<Response, AppError> {
+    let expected = state.config.server.api_key.as_deref();
+    if expected.is_some() && bearer_token(request.headers()) != expected {
+        return Err(AppError::Unauthorized(
+            "A valid API key bearer token is required".to_string(),
+        ));
+    }
+    Ok(next.run(request).await)
+}
+
+/// Middleware answering `504 Gateway Timeout` once a request runs longer than
+/// `server.request_timeout` seconds
+///
+/// This bounds slow upstream calls independently of the client's own timeout. The
+/// abandoned handler never records its outcome, so the request is counted as failed
+/// here. (`tower_http`'s `TimeoutLayer` would answer 408 and bypass the metrics.)
+async fn enforce_request_timeout(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> std::result::Result<Response, AppError> {
+    let start_time = Instant::now();
+    let timeout = Duration::from_secs(state.config.server.request_timeout);
+    let endpoint = request.extensions().get::<MatchedPath>().map_or_else(
+        || request.uri().path().to_string(),
+        |path| path.as_str().to_string(),
+    );
+
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => Ok(response),
+        Err(_) => {
+            state
+                .metrics
+                .record_request(&endpoint, false, start_time.elapsed().as_millis() as u64);
+            Err(AppError::GatewayTimeout(format!(
+                "Request did not complete within {} seconds",
+                state.config.server.request_timeout
+            )))
+        }
+    }
+}
+
+/// Token of an `Authorization: Bearer <token>` header, if present
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+/// Reject the request unless it carries the configured metrics bearer token
+fn require_metrics_token(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> std::result::Result<(), AppError> {
+    let Some(expected) = &state.config.server.metrics_token else {
+        return Ok(());
+    };
+
+    if bearer_token(headers) == Some(expected.as_str()) {
+        Ok(())
+    } else {
+        Err(AppError::Unauthorized(
+            "A valid metrics bearer token is required".to_string(),
+        ))
+    }
 }
 
 /// Helper function to get from cache
@@ -510,16 +1408,7 @@ fn get_from_cache(state: &AppState, key: &str) -> Option<CacheEntry> {
         return None;
     }
 
-    if let Some(entry) = state.cache.get(key) {
-        if entry.expires_at > Instant::now() {
-            return Some(entry.clone());
-        } else {
-            // Entry expired, remove it
-            state.cache.remove(key);
-        }
-    }
-
-    None
+    state.cache.get(key)
 }
 
 /// Helper function to set cache
@@ -528,33 +1417,123 @@ fn set_cache(state: &AppState, key: &str, data: Value) {
         return;
     }
 
-    // Clean up expired entries periodically
-    if state.cache.len() > state.config.cache.max_entries {
-        let now = Instant::now();
-        state.cache.retain(|_, entry| entry.expires_at > now);
+    state.cache.set(
+        key,
+        data,
+        Duration::from_secs(state.config.cache.ttl_seconds),
+    );
+}
+
+/// Build the JSON response of a cacheable endpoint
+///
+/// The response carries `Cache-Control: max-age=<cache.ttl_seconds>` and an `ETag`
+/// hashed from the body; a request whose `If-None-Match` matches it gets an empty
+/// `304 Not Modified`. With `server.debug_headers` enabled the response also carries
+/// `X-Cache: HIT|MISS`, and `?explain=cache` adds a `_cache` object with the key and
+/// remaining TTL.
+fn cacheable_response(
+    state: &AppState,
+    headers: &HeaderMap,
+    query: &ExplainQuery,
+    cache_key: &str,
+    hit: bool,
+    mut body: Value,
+) -> std::result::Result<Response, AppError> {
+    let status = if hit { "HIT" } else { "MISS" };
+    if state.config.server.debug_headers {
+        match query.explain.as_deref() {
+            None => {}
+            Some("cache") => {
+                let expires_in_seconds = state
+                    .cache
+                    .get(cache_key)
+                    .map(|entry| entry.expires_in().as_secs());
+                if let Some(object) = body.as_object_mut() {
+                    object.insert(
+                        "_cache".to_string(),
+                        serde_json::json!({
+                            "status": status,
+                            "key": cache_key,
+                            "enabled": state.config.cache.enabled,
+                            "expires_in_seconds": expires_in_seconds,
+                        }),
+                    );
+                }
+            }
+            Some(other) => {
+                return Err(AppError::BadRequest(format!(
+                    "Unsupported explain value '{}'; expected 'cache'",
+                    other
+                )));
+            }
+        }
     }
 
-    let entry = CacheEntry {
-        data,
-        expires_at: Instant::now() + Duration::from_secs(state.config.cache.ttl_seconds),
+    let bytes = serde_json::to_vec(&body)?;
+    let etag = body_etag(&bytes);
+    let not_modified = if_none_match(headers, &etag);
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        ([(header::CONTENT_TYPE, "application/json")], bytes).into_response()
     };
+    let response_headers = response.headers_mut();
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        header::HeaderValue::from_str(&format!("max-age={}", state.config.cache.ttl_seconds))
+            .expect("max-age is a valid header value"),
+    );
+    response_headers.insert(
+        header::ETAG,
+        header::HeaderValue::from_str(&etag).expect("ETag is a valid header value"),
+    );
+    if state.config.server.debug_headers {
+        response_headers.insert("X-Cache", header::HeaderValue::from_static(status));
+    }
 
-    state.cache.insert(key.to_string(), entry);
+    Ok(response)
+}
+
+/// Strong entity tag for a response body
+fn body_etag(body: &[u8]) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    body.hash(&mut hasher);
+    format!("\"{:016x}\"", hasher.finish())
+}
+
+/// Whether the request's `If-None-Match` header matches `etag`
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
 }
 
 /// Application error wrapper for HTTP responses
+///
+/// Error bodies are `{"error", "code", "timestamp"}`, where `code` is the stable
+/// [`CrateCheckerError::code`] of the underlying error; 404s also carry the missing
+/// crate (and version) under `details`.
 #[derive(Debug)]
 pub enum AppError {
     Internal(CrateCheckerError),
     BadRequest(String),
-    NotFound(String),
+    NotFound(CrateCheckerError),
+    Unauthorized(String),
+    GatewayTimeout(String),
 }
 
 impl From<CrateCheckerError> for AppError {
     fn from(err: CrateCheckerError) -> Self {
         match err {
             CrateCheckerError::CrateNotFound(_) | CrateCheckerError::VersionNotFound { .. } => {
-                Self::NotFound(err.to_string())
+                Self::NotFound(err)
             }
             CrateCheckerError::ValidationError(_) | CrateCheckerError::InvalidBatchInput(_) => {
                 Self::BadRequest(err.to_string())
@@ -573,22 +1552,44 @@ impl From<serde_json::Error> for AppError {
 /// Convert AppError to HTTP response
 impl axum::response::IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
-        let (status, message) = match self {
+        let (status, code, message, details) = match self {
             AppError::Internal(e) => {
                 error!("Internal error: {}", e);
+                let message = match e.code() {
+                    "INTERNAL" => "Internal server error".to_string(),
+                    _ => e.user_message(),
+                };
+                (StatusCode::INTERNAL_SERVER_ERROR, e.code(), message, None)
+            }
+            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, "VALIDATION", msg, None),
+            AppError::NotFound(e) => {
+                let details = match &e {
+                    CrateCheckerError::VersionNotFound {
+                        crate_name,
+                        version,
+                    } => serde_json::json!({ "crate": crate_name, "version": version }),
+                    CrateCheckerError::CrateNotFound(name) => serde_json::json!({ "crate": name }),
+                    _ => serde_json::json!({}),
+                };
                 (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "Internal server error".to_string(),
+                    StatusCode::NOT_FOUND,
+                    e.code(),
+                    e.to_string(),
+                    Some(details),
                 )
             }
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED", msg, None),
+            AppError::GatewayTimeout(msg) => (StatusCode::GATEWAY_TIMEOUT, "TIMEOUT", msg, None),
         };
 
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "error": message,
+            "code": code,
             "timestamp": Utc::now().to_rfc3339()
         });
+        if let Some(details) = details {
+            body["details"] = details;
+        }
 
         (status, Json(body)).into_response()
     }
@@ -608,9 +1609,9 @@ mod tests {
         let config = AppConfig::default();
         let state = AppState {
             client,
+            cache: crate::cache::from_config(&config.cache).unwrap(),
             config,
             metrics: Arc::new(ServerMetrics::default()),
-            cache: Arc::new(DashMap::new()),
             start_time: Instant::now(),
         };
 
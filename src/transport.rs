@@ -0,0 +1,194 @@
+//! HTTP transport behind [`crate::client::CrateClient`]
+//!
+//! Every upstream request of the client goes through an [`HttpTransport`]. The default
+//! [`ReqwestTransport`] talks to the network; code built on the client can inject a
+//! fake with [`crate::client::CrateClientBuilder::transport`] to test without one.
+//! Retries, rate limiting, caching and status handling stay in the client, so a fake
+//! only has to answer requests.
+
+use crate::error::Result;
+use futures::future::BoxFuture;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+/// A request for a transport to send
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    /// Overrides the transport's own timeout for this request
+    pub timeout: Option<Duration>,
+}
+
+impl HttpRequest {
+    /// GET request for `url`
+    pub fn get(url: impl Into<String>) -> Self {
+        Self {
+            method: Method::GET,
+            url: url.into(),
+            timeout: None,
+        }
+    }
+}
+
+/// A response with its body read in full
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Response with `status` and `body` and no headers
+    pub fn new(status: StatusCode, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+}
+
+/// Sends the HTTP requests of a [`crate::client::CrateClient`]
+///
+/// Any response status is returned as `Ok`; errors are for requests that got no
+/// response at all, such as connection failures and timeouts.
+pub trait HttpTransport: Send + Sync + std::fmt::Debug {
+    /// Send `request` and read the whole response
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>>;
+
+    /// Send a GET request for `url`
+    fn get(&self, url: &str) -> BoxFuture<'_, Result<HttpResponse>> {
+        self.send(HttpRequest::get(url))
+    }
+}
+
+/// Transport sending requests over the network with `reqwest`
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Transport using `client`, whose default headers, user agent and timeout apply
+    /// to every request
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        Box::pin(async move {
+            let mut builder = self.client.request(request.method, &request.url);
+            if let Some(timeout) = request.timeout {
+                builder = builder.timeout(timeout);
+            }
+            // reqwest advertises and transparently decodes gzip once the feature is enabled
+            let response = builder.send().await?;
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.text().await?;
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::CrateClient;
+    use std::sync::Mutex;
+
+    /// Transport answering from a fixed `url -> (status, body)` table, recording requests
+    #[derive(Debug, Default)]
+    struct MockTransport {
+        responses: Vec<(String, StatusCode, String)>,
+        requests: Mutex<Vec<HttpRequest>>,
+    }
+
+    impl MockTransport {
+        fn respond(mut self, url: &str, status: StatusCode, body: &str) -> Self {
+            self.responses
+                .push((url.to_string(), status, body.to_string()));
+            self
+        }
+    }
+
+    impl HttpTransport for MockTransport {
+        fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+            let response = self
+                .responses
+                .iter()
+                .find(|(url, _, _)| *url == request.url)
+                .map(|(_, status, body)| HttpResponse::new(*status, body.as_str()))
+                .unwrap_or_else(|| HttpResponse::new(StatusCode::NOT_FOUND, ""));
+            self.requests.lock().unwrap().push(request);
+            Box::pin(async move { Ok(response) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crate_exists_with_mock_transport() {
+        let transport = std::sync::Arc::new(MockTransport::default().respond(
+            "https://registry.test/api/v1/crates/serde",
+            StatusCode::OK,
+            r#"{"crate": {"name": "serde"}}"#,
+        ));
+        let client = CrateClient::builder()
+            .base_url("https://registry.test/api/v1")
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        assert!(client.crate_exists("serde").await.unwrap());
+        assert!(!client.crate_exists("no-such-crate").await.unwrap());
+
+        let requests = transport.requests.lock().unwrap();
+        let urls: Vec<&str> = requests.iter().map(|r| r.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://registry.test/api/v1/crates/serde",
+                "https://registry.test/api/v1/crates/no-such-crate",
+            ]
+        );
+        assert!(requests.iter().all(|r| r.method == Method::GET));
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_retried_through_the_transport() {
+        #[derive(Debug, Default)]
+        struct Flaky(Mutex<u32>);
+
+        impl HttpTransport for Flaky {
+            fn send(&self, _request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+                let mut calls = self.0.lock().unwrap();
+                *calls += 1;
+                let status = if *calls == 1 {
+                    StatusCode::SERVICE_UNAVAILABLE
+                } else {
+                    StatusCode::OK
+                };
+                Box::pin(async move { Ok(HttpResponse::new(status, "{}")) })
+            }
+        }
+
+        let transport = std::sync::Arc::new(Flaky::default());
+        let client = CrateClient::builder()
+            .transport(transport.clone())
+            .retry_attempts(1)
+            .build()
+            .unwrap();
+
+        assert!(client.crate_exists("serde").await.unwrap());
+        assert_eq!(*transport.0.lock().unwrap(), 2);
+        assert_eq!(client.stats().retries, 1);
+    }
+}
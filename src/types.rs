@@ -1,11 +1,12 @@
 //! Data types and structures for the crate checker application
 
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 /// Main crate information structure
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct CrateInfo {
     /// Crate name
     pub name: String,
@@ -51,20 +52,30 @@ pub struct CrateInfo {
 
     /// Links to various resources
     pub links: Option<CrateLinks>,
+
+    /// Minimum supported Rust version declared by the newest version
+    #[serde(default)]
+    pub msrv: Option<String>,
+
+    /// Rust edition of the newest version
+    #[serde(default)]
+    pub edition: Option<String>,
 }
 
 /// Links associated with a crate
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct CrateLinks {
     pub owner_team: Option<String>,
     pub owner_user: Option<String>,
+    #[serde(default)]
+    pub owners: Option<String>,
     pub reverse_dependencies: Option<String>,
     pub version_downloads: Option<String>,
     pub versions: Option<String>,
 }
 
 /// Version information
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct Version {
     /// Version number
     pub num: String,
@@ -96,12 +107,24 @@ pub struct Version {
     /// License information
     pub license: Option<String>,
 
+    /// Feature flags mapped to the features and optional dependencies they enable
+    #[serde(default)]
+    pub features: Option<HashMap<String, Vec<String>>>,
+
     /// Links for this version
     pub links: Option<VersionLinks>,
+
+    /// Minimum supported Rust version (`package.rust-version`)
+    #[serde(default)]
+    pub rust_version: Option<String>,
+
+    /// Rust edition (`package.edition`)
+    #[serde(default)]
+    pub edition: Option<String>,
 }
 
 /// User information
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct User {
     pub id: u64,
     pub login: String,
@@ -111,7 +134,7 @@ pub struct User {
 }
 
 /// Audit action information
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct AuditAction {
     pub action: String,
     pub user: User,
@@ -119,7 +142,7 @@ pub struct AuditAction {
 }
 
 /// Links for a specific version
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct VersionLinks {
     pub dependencies: Option<String>,
     pub version_downloads: Option<String>,
@@ -127,7 +150,8 @@ pub struct VersionLinks {
 }
 
 /// Crate status enumeration
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 pub enum CrateStatus {
     /// Crate exists and is available
     Exists,
@@ -139,8 +163,45 @@ pub enum CrateStatus {
     PartiallyYanked,
 }
 
-/// Search result for crates
+impl CrateStatus {
+    /// Status of a crate given all of its published versions
+    pub fn from_versions(versions: &[Version]) -> Self {
+        let yanked_count = versions.iter().filter(|v| v.yanked).count();
+        if versions.is_empty() {
+            Self::NotFound
+        } else if yanked_count == versions.len() {
+            Self::Yanked
+        } else if yanked_count > 0 {
+            Self::PartiallyYanked
+        } else {
+            Self::Exists
+        }
+    }
+}
+
+impl std::fmt::Display for CrateStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Exists => "exists",
+            Self::NotFound => "not_found",
+            Self::Yanked => "yanked",
+            Self::PartiallyYanked => "partially_yanked",
+        })
+    }
+}
+
+/// Status of a crate together with the versions that are yanked
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CrateStatusReport {
+    #[serde(rename = "crate")]
+    pub crate_name: String,
+    pub status: CrateStatus,
+    /// Yanked versions, newest first
+    pub yanked_versions: Vec<String>,
+}
+
+/// Search result for crates
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct CrateSearchResult {
     pub name: String,
     pub description: Option<String>,
@@ -149,8 +210,19 @@ pub struct CrateSearchResult {
     pub exact_match: bool,
 }
 
-/// Dependency information
+/// Crates that depend on a crate, as reported by crates.io
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReverseDependencies {
+    pub crate_name: String,
+    /// Total number of dependent crates, including those beyond `dependents`
+    pub total: u64,
+    /// Dependent crates, most downloaded first; `newest_version` is the dependent's
+    /// version that declares the dependency
+    pub dependents: Vec<CrateSearchResult>,
+}
+
+/// Dependency information
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct Dependency {
     #[serde(rename = "crate_id")]
     pub name: String,
@@ -178,7 +250,7 @@ impl Dependency {
 }
 
 /// Download statistics
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct DownloadStats {
     /// Total all-time downloads
     pub total: u64,
@@ -186,8 +258,82 @@ pub struct DownloadStats {
     pub versions: Vec<VersionDownload>,
 }
 
-/// Download stats for a specific version
+/// Direction of recent download activity
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrendDirection {
+    Rising,
+    Falling,
+    Flat,
+}
+
+impl std::fmt::Display for TrendDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let direction = match self {
+            TrendDirection::Rising => "rising",
+            TrendDirection::Falling => "falling",
+            TrendDirection::Flat => "flat",
+        };
+        f.write_str(direction)
+    }
+}
+
+/// Downloads of the latest 30 days compared with the 30 days before
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DownloadTrend {
+    pub direction: TrendDirection,
+    /// Relative change in percent; absent when the prior window had no downloads
+    pub change_percent: Option<f64>,
+    pub recent_downloads: u64,
+    pub previous_downloads: u64,
+}
+
+impl DownloadTrend {
+    /// Length of each compared window in days
+    pub const WINDOW_DAYS: i64 = 30;
+
+    /// Changes smaller than this many percent are reported as flat
+    pub const FLAT_THRESHOLD_PERCENT: f64 = 5.0;
+
+    /// Compute the trend from a daily download series, windows ending at its last day
+    pub fn from_history(history: &[(chrono::NaiveDate, u64)]) -> Option<Self> {
+        let last = history.iter().map(|(date, _)| *date).max()?;
+        let recent_start = last - chrono::Duration::days(Self::WINDOW_DAYS);
+        let previous_start = recent_start - chrono::Duration::days(Self::WINDOW_DAYS);
+
+        let sum = |from: chrono::NaiveDate, to: chrono::NaiveDate| -> u64 {
+            history
+                .iter()
+                .filter(|(date, _)| *date > from && *date <= to)
+                .map(|(_, downloads)| downloads)
+                .sum()
+        };
+        let recent_downloads = sum(recent_start, last);
+        let previous_downloads = sum(previous_start, recent_start);
+
+        let change_percent = (previous_downloads > 0).then(|| {
+            (recent_downloads as f64 - previous_downloads as f64) / previous_downloads as f64
+                * 100.0
+        });
+        let direction = match change_percent {
+            Some(change) if change >= Self::FLAT_THRESHOLD_PERCENT => TrendDirection::Rising,
+            Some(change) if change <= -Self::FLAT_THRESHOLD_PERCENT => TrendDirection::Falling,
+            Some(_) => TrendDirection::Flat,
+            None if recent_downloads > 0 => TrendDirection::Rising,
+            None => TrendDirection::Flat,
+        };
+
+        Some(Self {
+            direction,
+            change_percent,
+            recent_downloads,
+            previous_downloads,
+        })
+    }
+}
+
+/// Download stats for a specific version
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct VersionDownload {
     pub version: String,
     pub downloads: u64,
@@ -206,6 +352,549 @@ pub struct Owner {
     pub kind: String, // "user" or "team"
 }
 
+/// Coarse popularity bucket derived from a download percentile
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PopularityLabel {
+    /// Below the 75th percentile
+    Niche,
+    /// Between the 75th and 99th percentile
+    Popular,
+    /// At or above the 99th percentile
+    Ubiquitous,
+}
+
+impl std::fmt::Display for PopularityLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            PopularityLabel::Niche => "niche",
+            PopularityLabel::Popular => "popular",
+            PopularityLabel::Ubiquitous => "ubiquitous",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Approximate standing of a crate's all-time downloads among all crates
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Popularity {
+    pub crate_name: String,
+    pub downloads: u64,
+    /// Approximate percentile (0-100) of all crates with fewer downloads
+    pub percentile: f64,
+    pub label: PopularityLabel,
+}
+
+impl Popularity {
+    /// Place a download count on the embedded reference distribution
+    pub fn from_downloads(crate_name: &str, downloads: u64) -> Self {
+        let percentile = crate::utils::download_percentile(downloads);
+        let label = if percentile >= 99.0 {
+            PopularityLabel::Ubiquitous
+        } else if percentile >= 75.0 {
+            PopularityLabel::Popular
+        } else {
+            PopularityLabel::Niche
+        };
+
+        Self {
+            crate_name: crate_name.to_string(),
+            downloads,
+            percentile,
+            label,
+        }
+    }
+}
+
+/// Kind of problem found for a locked package
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LockFindingKind {
+    /// The locked version has been yanked from crates.io
+    YankedVersion,
+    /// The locked version was never published on crates.io
+    UnknownVersion,
+}
+
+impl LockFindingKind {
+    /// Stable rule identifier used in machine-readable reports
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            LockFindingKind::YankedVersion => "yanked-version",
+            LockFindingKind::UnknownVersion => "unknown-version",
+        }
+    }
+
+    /// One-line description of the rule
+    pub fn description(&self) -> &'static str {
+        match self {
+            LockFindingKind::YankedVersion => "Locked version has been yanked from crates.io",
+            LockFindingKind::UnknownVersion => "Locked version is not published on crates.io",
+        }
+    }
+}
+
+/// A problem with a single package pinned in a Cargo.lock
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LockFinding {
+    pub crate_name: String,
+    pub version: String,
+    pub kind: LockFindingKind,
+}
+
+impl LockFinding {
+    /// Human-readable description of the finding
+    pub fn message(&self) -> String {
+        match self.kind {
+            LockFindingKind::YankedVersion => {
+                format!("{}@{} has been yanked", self.crate_name, self.version)
+            }
+            LockFindingKind::UnknownVersion => {
+                format!(
+                    "{}@{} is not published on crates.io",
+                    self.crate_name, self.version
+                )
+            }
+        }
+    }
+}
+
+/// How long a benchmark keeps issuing requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchLimit {
+    /// Stop after this many requests
+    Requests(usize),
+    /// Keep starting requests until this much time has passed
+    Duration(std::time::Duration),
+}
+
+/// Throughput and latency measured against the API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BenchReport {
+    pub requests: usize,
+    pub errors: usize,
+    pub concurrency: usize,
+    pub elapsed_ms: f64,
+    pub requests_per_sec: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    /// Fraction of requests that failed (0.0 - 1.0)
+    pub error_rate: f64,
+}
+
+impl BenchReport {
+    /// Summarize per-request latencies collected over `elapsed`
+    pub fn from_samples(
+        mut latencies: Vec<std::time::Duration>,
+        errors: usize,
+        concurrency: usize,
+        elapsed: std::time::Duration,
+    ) -> Self {
+        latencies.sort();
+        // Nearest-rank percentile in milliseconds
+        let percentile = |pct: f64| -> f64 {
+            if latencies.is_empty() {
+                return 0.0;
+            }
+            let rank = ((pct / 100.0) * latencies.len() as f64).ceil() as usize;
+            latencies[rank.clamp(1, latencies.len()) - 1].as_secs_f64() * 1000.0
+        };
+
+        let requests = latencies.len();
+        let seconds = elapsed.as_secs_f64();
+        Self {
+            requests,
+            errors,
+            concurrency,
+            elapsed_ms: seconds * 1000.0,
+            requests_per_sec: if seconds > 0.0 {
+                requests as f64 / seconds
+            } else {
+                0.0
+            },
+            p50_ms: percentile(50.0),
+            p95_ms: percentile(95.0),
+            error_rate: if requests > 0 {
+                errors as f64 / requests as f64
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// A dependency declared in a manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestDependency {
+    pub name: String,
+    pub requirement: String,
+}
+
+/// A dependency whose requirement changed between two manifests
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DependencyBump {
+    pub name: String,
+    pub old_requirement: String,
+    pub new_requirement: String,
+    /// Published versions between the two requirements; absent if either is unresolvable
+    pub versions_between: Option<usize>,
+}
+
+/// Dependency changes between two manifests
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ManifestDiff {
+    pub added: Vec<ManifestDependency>,
+    pub removed: Vec<ManifestDependency>,
+    pub bumped: Vec<DependencyBump>,
+}
+
+impl ManifestDiff {
+    /// Compare two `name -> requirement` maps; version counts are left for the caller
+    pub fn between(old: &BTreeMap<String, String>, new: &BTreeMap<String, String>) -> Self {
+        let mut diff = Self::default();
+
+        for (name, requirement) in new {
+            match old.get(name) {
+                None => diff.added.push(ManifestDependency {
+                    name: name.clone(),
+                    requirement: requirement.clone(),
+                }),
+                Some(old_requirement) if old_requirement != requirement => {
+                    diff.bumped.push(DependencyBump {
+                        name: name.clone(),
+                        old_requirement: old_requirement.clone(),
+                        new_requirement: requirement.clone(),
+                        versions_between: None,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for (name, requirement) in old {
+            if !new.contains_key(name) {
+                diff.removed.push(ManifestDependency {
+                    name: name.clone(),
+                    requirement: requirement.clone(),
+                });
+            }
+        }
+
+        diff
+    }
+}
+
+/// Existence check of the crates.io dependencies of a manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestValidation {
+    pub checked: usize,
+    /// Dependencies that do not exist on crates.io, usually typos
+    pub missing: Vec<String>,
+    /// Dependencies whose check failed, so their existence is unknown
+    pub failed: Vec<String>,
+    pub results: Vec<CrateCheckResult>,
+}
+
+impl ManifestValidation {
+    /// Summarize the check results of a manifest's dependencies
+    pub fn from_results(results: Vec<CrateCheckResult>) -> Self {
+        let names = |outcome: CheckOutcome| {
+            results
+                .iter()
+                .filter(|r| r.outcome == outcome)
+                .map(|r| r.crate_name.clone())
+                .collect()
+        };
+        Self {
+            checked: results.len(),
+            missing: names(CheckOutcome::Missing),
+            failed: names(CheckOutcome::Error),
+            results,
+        }
+    }
+}
+
+/// How far a dependency requirement trails the latest release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdateKind {
+    UpToDate,
+    Patch,
+    Minor,
+    Major,
+}
+
+impl UpdateKind {
+    /// Compare the lowest version a requirement allows with the latest release
+    ///
+    /// `None` when either side can't be parsed; a requirement without a lower
+    /// bound (such as `*`) is always up to date.
+    pub fn between(requirement: &str, latest: &str) -> Option<Self> {
+        use semver::Op;
+
+        let latest = semver::Version::parse(latest).ok()?;
+        let requirement = semver::VersionReq::parse(requirement.trim()).ok()?;
+        let Some(base) = requirement.comparators.iter().find(|c| {
+            matches!(
+                c.op,
+                Op::Exact | Op::Greater | Op::GreaterEq | Op::Tilde | Op::Caret | Op::Wildcard
+            )
+        }) else {
+            return Some(Self::UpToDate);
+        };
+        let (minor, patch) = (base.minor.unwrap_or(0), base.patch.unwrap_or(0));
+
+        Some(if latest.major > base.major {
+            Self::Major
+        } else if latest.major == base.major && latest.minor > minor {
+            Self::Minor
+        } else if latest.major == base.major && latest.minor == minor && latest.patch > patch {
+            Self::Patch
+        } else {
+            Self::UpToDate
+        })
+    }
+}
+
+impl std::fmt::Display for UpdateKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::UpToDate => "up to date",
+            Self::Patch => "patch",
+            Self::Minor => "minor",
+            Self::Major => "major",
+        })
+    }
+}
+
+/// A manifest dependency compared with the latest release on crates.io
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutdatedDependency {
+    pub name: String,
+    /// Requirement as written in the manifest
+    pub requirement: String,
+    pub latest: Option<String>,
+    /// Newest kind of release available beyond the requirement; absent on errors
+    pub update: Option<UpdateKind>,
+    pub error: Option<String>,
+}
+
+impl OutdatedDependency {
+    /// Whether a newer patch, minor or major release is available
+    pub fn is_outdated(&self) -> bool {
+        self.update
+            .is_some_and(|update| update != UpdateKind::UpToDate)
+    }
+}
+
+/// A newest version observed by the watch command that differs from the last one seen
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VersionChange {
+    pub crate_name: String,
+    /// Previously observed version; absent on the first observation of a crate
+    pub previous_version: Option<String>,
+    pub new_version: String,
+}
+
+/// A crate in a resolved dependency tree
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DepNode {
+    pub name: String,
+    /// Resolved version; absent when the requirement could not be resolved
+    pub version: Option<String>,
+    /// Requirement declared by the parent; absent for the root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<String>,
+    /// Dependency kind declared by the parent (`normal` or `build`); absent for the root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Whether the parent declares the dependency as optional
+    #[serde(default)]
+    pub optional: bool,
+    /// Already expanded earlier in the tree, so its children are not repeated here
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub duplicate: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DepNode>,
+}
+
+/// Aggregate report over a set of crates
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EcosystemReport {
+    /// Number of crates included in the aggregates
+    pub crate_count: usize,
+    /// Combined all-time downloads
+    pub total_downloads: u64,
+    /// Mean crate age in days
+    pub average_age_days: f64,
+    /// Number of crates per license expression of their newest version
+    pub license_distribution: BTreeMap<String, usize>,
+    /// Total number of published versions
+    pub total_versions: usize,
+    /// Number of yanked versions
+    pub yanked_versions: usize,
+    /// Fraction of versions that are yanked (0.0 - 1.0)
+    pub yank_rate: f64,
+    /// Per-crate breakdown
+    pub crates: Vec<CrateReportEntry>,
+    /// Crates that could not be fetched
+    pub failed: Vec<String>,
+}
+
+/// One crate's requirement on a dependency
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DependencyRequirement {
+    pub crate_name: String,
+    pub req: String,
+}
+
+/// Direct dependencies of a set of crates, grouped by dependency
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DependencyReport {
+    /// Analyzed crates and the version whose dependencies were read
+    pub crates: BTreeMap<String, String>,
+    /// Normal and build dependencies, each with the crates requiring it
+    pub dependencies: BTreeMap<String, Vec<DependencyRequirement>>,
+    /// Dependencies required by more than one of the analyzed crates
+    pub shared: Vec<String>,
+    /// Shared dependencies whose requirements select semver-incompatible versions,
+    /// so the build would contain more than one copy of them
+    pub conflicts: Vec<String>,
+    /// Crates that could not be fetched
+    pub failed: Vec<String>,
+}
+
+impl DependencyReport {
+    /// Build a report from each crate's version and its dependencies
+    pub fn from_dependencies(
+        analyzed: Vec<(String, String, Vec<Dependency>)>,
+        failed: Vec<String>,
+    ) -> Self {
+        let mut crates = BTreeMap::new();
+        let mut dependencies: BTreeMap<String, Vec<DependencyRequirement>> = BTreeMap::new();
+        for (crate_name, version, deps) in analyzed {
+            // Dev-dependencies never reach the dependents' builds
+            for dep in deps.into_iter().filter(|d| d.kind != "dev") {
+                let requirement = DependencyRequirement {
+                    crate_name: crate_name.clone(),
+                    req: dep.req,
+                };
+                let requirements = dependencies.entry(dep.name).or_default();
+                if !requirements.contains(&requirement) {
+                    requirements.push(requirement);
+                }
+            }
+            crates.insert(crate_name, version);
+        }
+        for requirements in dependencies.values_mut() {
+            requirements.sort();
+        }
+
+        let shared: Vec<String> = dependencies
+            .iter()
+            .filter(|(_, reqs)| {
+                reqs.iter()
+                    .map(|r| &r.crate_name)
+                    .collect::<BTreeSet<_>>()
+                    .len()
+                    > 1
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        let conflicts = shared
+            .iter()
+            .filter(|name| {
+                dependencies[*name]
+                    .iter()
+                    .filter_map(|r| crate::utils::semver_compat_series(&r.req))
+                    .collect::<BTreeSet<_>>()
+                    .len()
+                    > 1
+            })
+            .cloned()
+            .collect();
+
+        Self {
+            crates,
+            dependencies,
+            shared,
+            conflicts,
+            failed,
+        }
+    }
+}
+
+/// Single crate entry in an ecosystem report
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CrateReportEntry {
+    pub name: String,
+    pub newest_version: String,
+    pub downloads: u64,
+    pub license: Option<String>,
+    pub age_days: i64,
+    pub versions: usize,
+    pub yanked_versions: usize,
+}
+
+impl EcosystemReport {
+    /// Build a report from already fetched crate info and version lists
+    pub fn from_crates(
+        crates: &[(CrateInfo, Vec<Version>)],
+        failed: Vec<String>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let entries: Vec<CrateReportEntry> = crates
+            .iter()
+            .map(|(info, versions)| {
+                // Prefer the license of the newest version, it reflects the current terms
+                let license = versions
+                    .iter()
+                    .find(|v| v.num == info.newest_version)
+                    .and_then(|v| v.license.clone())
+                    .or_else(|| info.license.clone());
+
+                CrateReportEntry {
+                    name: info.name.clone(),
+                    newest_version: info.newest_version.clone(),
+                    downloads: info.downloads,
+                    license,
+                    age_days: (now - info.created_at).num_days(),
+                    versions: versions.len(),
+                    yanked_versions: versions.iter().filter(|v| v.yanked).count(),
+                }
+            })
+            .collect();
+
+        let mut license_distribution = BTreeMap::new();
+        for entry in &entries {
+            let license = entry.license.as_deref().unwrap_or("Unknown").to_string();
+            *license_distribution.entry(license).or_insert(0) += 1;
+        }
+
+        let total_versions: usize = entries.iter().map(|e| e.versions).sum();
+        let yanked_versions: usize = entries.iter().map(|e| e.yanked_versions).sum();
+
+        Self {
+            crate_count: entries.len(),
+            total_downloads: entries.iter().map(|e| e.downloads).sum(),
+            average_age_days: if entries.is_empty() {
+                0.0
+            } else {
+                entries.iter().map(|e| e.age_days as f64).sum::<f64>() / entries.len() as f64
+            },
+            license_distribution,
+            total_versions,
+            yanked_versions,
+            yank_rate: if total_versions == 0 {
+                0.0
+            } else {
+                yanked_versions as f64 / total_versions as f64
+            },
+            crates: entries,
+            failed,
+        }
+    }
+}
+
 // Batch processing types
 
 /// Batch input format - supports multiple input types
@@ -222,6 +911,72 @@ pub enum BatchInput {
     Operations { operations: Vec<BatchOperation> },
 }
 
+impl BatchInput {
+    /// Flatten the input into individual crate checks, treating "latest" and its synonyms as no version
+    ///
+    /// Crate lists and version maps are plain checks; operations keep their operation type,
+    /// and an unknown operation type is an error.
+    pub fn into_checks(self) -> crate::error::Result<Vec<BatchCheck>> {
+        let requested =
+            |version: String| (!crate::utils::is_latest_keyword(&version)).then_some(version);
+
+        match self {
+            BatchInput::CrateVersionMap(map) => Ok(map
+                .into_iter()
+                .map(|(name, version)| BatchCheck::new(name, requested(version)))
+                .collect()),
+            BatchInput::CrateList { crates } => Ok(crates
+                .into_iter()
+                .map(|name| BatchCheck::new(name, None))
+                .collect()),
+            BatchInput::Operations { operations } => {
+                let mut checks = Vec::new();
+                for operation in operations {
+                    let kind = operation.kind()?;
+                    match operation.target {
+                        BatchTarget::Single {
+                            crate_name,
+                            version,
+                        } => checks.push(BatchCheck {
+                            crate_name,
+                            version,
+                            operation: kind,
+                        }),
+                        BatchTarget::Multiple { crates } => {
+                            checks.extend(crates.into_iter().map(|crate_name| BatchCheck {
+                                crate_name,
+                                version: None,
+                                operation: kind,
+                            }))
+                        }
+                    }
+                }
+                Ok(checks)
+            }
+        }
+    }
+}
+
+/// A single crate to check as part of a batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchCheck {
+    pub crate_name: String,
+    /// Requested version, or `None` for the latest
+    pub version: Option<String>,
+    pub operation: BatchOperationKind,
+}
+
+impl BatchCheck {
+    /// A plain existence/version check
+    pub fn new(crate_name: String, version: Option<String>) -> Self {
+        Self {
+            crate_name,
+            version,
+            operation: BatchOperationKind::Check,
+        }
+    }
+}
+
 /// A single batch operation
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BatchOperation {
@@ -229,10 +984,66 @@ pub struct BatchOperation {
     #[serde(flatten)]
     pub target: BatchTarget,
 
-    /// The operation to perform
+    /// The operation to perform: `check`, `info`, `deps`, `stats` or `versions`
     pub operation: String,
 }
 
+impl BatchOperation {
+    /// Parse the operation type
+    pub fn kind(&self) -> crate::error::Result<BatchOperationKind> {
+        self.operation.parse()
+    }
+}
+
+/// What a batch operation fetches for each of its crates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchOperationKind {
+    /// Existence and version check only
+    #[default]
+    Check,
+    /// Check, always including the crate info
+    Info,
+    /// Check plus the dependencies of the requested (or latest) version
+    Deps,
+    /// Check plus download statistics
+    Stats,
+    /// Check plus the published versions
+    Versions,
+}
+
+impl BatchOperationKind {
+    /// Name used in batch input
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Check => "check",
+            Self::Info => "info",
+            Self::Deps => "deps",
+            Self::Stats => "stats",
+            Self::Versions => "versions",
+        }
+    }
+}
+
+impl std::str::FromStr for BatchOperationKind {
+    type Err = crate::error::CrateCheckerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            // The older check names remain accepted
+            "check" | "batch_check" | "check_version" | "check_latest" => Ok(Self::Check),
+            "info" => Ok(Self::Info),
+            "deps" => Ok(Self::Deps),
+            "stats" => Ok(Self::Stats),
+            "versions" => Ok(Self::Versions),
+            other => Err(crate::error::CrateCheckerError::validation(format!(
+                "Unknown batch operation '{}' (expected check, info, deps, stats or versions)",
+                other
+            ))),
+        }
+    }
+}
+
 /// Target for batch operations
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(untagged)]
@@ -249,27 +1060,115 @@ pub enum BatchTarget {
 }
 
 /// Result for checking a single crate
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct CrateCheckResult {
     pub crate_name: String,
     pub exists: bool,
+    /// Whether the check found the crate, found it missing, or failed
+    pub outcome: CheckOutcome,
     pub latest_version: Option<String>,
     pub requested_version: Option<String>,
     pub version_exists: Option<bool>,
+    /// Published version the request resolved to (after normalization or channel lookup)
+    #[serde(default)]
+    pub resolved_version: Option<String>,
+    /// Nearest published version when the requested one does not exist
+    #[serde(default)]
+    pub suggested_version: Option<String>,
     pub error: Option<String>,
     pub info: Option<CrateInfo>,
+    /// Dependencies, for a `deps` batch operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<Vec<Dependency>>,
+    /// Download statistics, for a `stats` batch operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_stats: Option<DownloadStats>,
+    /// Published versions, for a `versions` batch operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub versions: Option<Vec<Version>>,
 }
 
-/// Overall batch processing result
+/// How a crate check ended
+///
+/// `Missing` is a definite answer from the registry, while `Error` means the check (or a
+/// fetch its batch operation asked for) failed and is worth retrying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckOutcome {
+    Exists,
+    Missing,
+    Error,
+}
+
+impl CheckOutcome {
+    /// Outcome of a check that failed with `error`; a crate the registry does not have is missing
+    pub fn from_error(error: &crate::error::CrateCheckerError) -> Self {
+        match error {
+            crate::error::CrateCheckerError::CrateNotFound(_) => Self::Missing,
+            _ => Self::Error,
+        }
+    }
+}
+
+/// Batch result after re-running failed checks
 #[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetriedBatchResult {
+    #[serde(flatten)]
+    pub result: BatchResult,
+    /// Checks that errored at first but succeeded (or found the crate missing) on a retry
+    pub recovered: usize,
+    /// Retry passes run, at most the requested maximum
+    pub retry_passes: usize,
+}
+
+/// Outcome of matching a user-supplied version string against published versions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionResolution {
+    /// The version (after normalization) is published
+    Found(String),
+    /// The version is not published; carries the nearest published version, if any
+    NotFound { suggestion: Option<String> },
+}
+
+/// Overall batch processing result
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct BatchResult {
     pub results: Vec<CrateCheckResult>,
+    /// Number of inputs processed: crates, or operations for an operations batch
     pub total_processed: usize,
+    /// Number of crates checked, counting every crate of a multi-crate operation
+    #[serde(default)]
+    pub total_crates_checked: usize,
+    /// Checks that found the crate
     pub successful: usize,
+    /// Checks that found the crate does not exist
+    #[serde(default)]
+    pub missing: usize,
+    /// Checks that errored, e.g. on a network failure
     pub failed: usize,
     pub processing_time_ms: u64,
 }
 
+impl BatchResult {
+    /// Count the outcomes of `results`
+    pub fn new(
+        results: Vec<CrateCheckResult>,
+        total_processed: usize,
+        processing_time_ms: u64,
+    ) -> Self {
+        let count = |outcome: CheckOutcome| results.iter().filter(|r| r.outcome == outcome).count();
+        Self {
+            total_processed,
+            total_crates_checked: results.len(),
+            successful: count(CheckOutcome::Exists),
+            missing: count(CheckOutcome::Missing),
+            failed: count(CheckOutcome::Error),
+            results,
+            processing_time_ms,
+        }
+    }
+}
+
 // Server API types
 
 /// Request format for batch API endpoint
@@ -284,6 +1183,31 @@ pub struct BatchRequest {
     pub options: BatchOptions,
 }
 
+/// Query parameters for the batch API endpoint
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct BatchQuery {
+    /// Streaming mode; only `ndjson` is supported
+    pub stream: Option<String>,
+}
+
+/// Query parameters accepted by cacheable API endpoints
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ExplainQuery {
+    /// Debug detail to include in the body; only `cache` is supported
+    pub explain: Option<String>,
+}
+
+/// One line of a batch resolution plan
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VersionPlanEntry {
+    pub crate_name: String,
+    /// Version or requirement as given in the batch input
+    pub requested: String,
+    /// Concrete version the request resolves to; absent when unresolvable
+    pub resolved: Option<String>,
+    pub error: Option<String>,
+}
+
 /// Options for batch processing
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct BatchOptions {
@@ -323,6 +1247,50 @@ pub struct BatchResponse {
     pub result: BatchResult,
 }
 
+/// Final line of a streamed batch response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchSummary {
+    pub request_id: String,
+    pub status: String,
+    pub total_processed: usize,
+    pub successful: usize,
+    #[serde(default)]
+    pub missing: usize,
+    pub failed: usize,
+    pub processing_time_ms: u64,
+}
+
+/// Per-entry result of the yanked-check endpoint
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct YankedCheckEntry {
+    pub crate_name: String,
+    pub version: String,
+    /// Whether this exact version is published
+    pub exists: bool,
+    pub yanked: bool,
+    pub error: Option<String>,
+}
+
+/// Response format for the yanked-check endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YankedCheckResponse {
+    pub results: Vec<YankedCheckEntry>,
+    pub any_yanked: bool,
+}
+
+/// Request body for the bulk existence endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExistsRequest {
+    pub crates: Vec<String>,
+}
+
+/// Response format for the bulk existence endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExistsResponse {
+    pub results: BTreeMap<String, bool>,
+    pub all_exist: bool,
+}
+
 /// Health check response
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HealthResponse {
@@ -333,12 +1301,73 @@ pub struct HealthResponse {
 }
 
 /// Search request parameters
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SearchParams {
     pub q: String,
+    /// Results per page, at most 100 (defaults to 10)
     pub limit: Option<usize>,
+    /// Number of results to skip before the first one returned
     pub offset: Option<usize>,
-    pub sort: Option<String>,
+    pub sort: Option<SearchSort>,
+}
+
+/// Result ordering understood by the crates.io search API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SearchSort {
+    #[default]
+    Relevance,
+    Downloads,
+    RecentDownloads,
+    RecentUpdates,
+}
+
+impl SearchSort {
+    /// Value of the crates.io `sort` query parameter
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Relevance => "relevance",
+            Self::Downloads => "downloads",
+            Self::RecentDownloads => "recent-downloads",
+            Self::RecentUpdates => "recent-updates",
+        }
+    }
+}
+
+impl std::str::FromStr for SearchSort {
+    type Err = crate::error::CrateCheckerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "relevance" => Ok(Self::Relevance),
+            "downloads" => Ok(Self::Downloads),
+            "recent-downloads" => Ok(Self::RecentDownloads),
+            "recent-updates" => Ok(Self::RecentUpdates),
+            other => Err(crate::error::CrateCheckerError::validation(format!(
+                "Unknown search sort '{}' (expected relevance, downloads, recent-downloads or recent-updates)",
+                other
+            ))),
+        }
+    }
+}
+
+/// One page of search results together with the total number of matches
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchPage {
+    pub crates: Vec<CrateSearchResult>,
+    /// Total matches reported by crates.io across all pages
+    pub total: u64,
+}
+
+/// Snapshot of a client's upstream request counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientStats {
+    /// Logical requests issued (retries of the same request are not counted)
+    pub requests: u64,
+    /// Extra attempts made after recoverable failures
+    pub retries: u64,
+    /// Requests that still failed once retries were exhausted
+    pub failures: u64,
 }
 
 /// Metrics response
@@ -351,12 +1380,48 @@ pub struct MetricsResponse {
     pub cache_hits: u64,
     pub cache_misses: u64,
     pub uptime_seconds: u64,
+    /// Upstream crates.io request counters of the server's client
+    #[serde(default)]
+    pub upstream: ClientStats,
+    /// Request counters per route, keyed by route pattern (e.g. `/api/search`)
+    #[serde(default)]
+    pub by_endpoint: BTreeMap<String, EndpointMetrics>,
+}
+
+/// Server response cache statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheStatsResponse {
+    pub enabled: bool,
+    pub backend: crate::config::CacheBackend,
+    /// Stored entries, including expired ones not yet purged
+    pub entries: usize,
+    /// Approximate storage used by the entries, in bytes
+    pub approximate_size_bytes: u64,
+    pub hits: u64,
+    pub misses: u64,
+    /// Share of cache lookups that were hits, 0.0 before any lookup
+    pub hit_ratio: f64,
+}
+
+/// Result of a cache invalidation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheInvalidationResponse {
+    /// Number of entries removed
+    pub removed: usize,
+}
+
+/// Request counters of a single server route
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndpointMetrics {
+    pub requests: u64,
+    pub failed: u64,
+    pub total_response_time_ms: u64,
 }
 
 // Crates.io API response types (internal)
 
 /// Response from crates.io for crate information
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CrateResponse {
     #[serde(rename = "crate")]
     pub crate_info: CrateApiInfo,
@@ -366,7 +1431,7 @@ pub struct CrateResponse {
 }
 
 /// Crate information from crates.io API
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct CrateApiInfo {
     pub name: String,
     pub description: Option<String>,
@@ -380,60 +1445,109 @@ pub struct CrateApiInfo {
     pub max_upload_size: Option<u64>,
     #[serde(rename = "recent_downloads")]
     pub recent_downloads: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<CrateLinks>,
 }
 
 /// Keyword information
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Keyword {
     pub keyword: String,
 }
 
 /// Category information
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Category {
     pub category: String,
 }
 
 /// Response from crates.io versions endpoint
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct VersionsResponse {
     pub versions: Vec<Version>,
+    #[serde(default)]
+    pub meta: PageMeta,
+}
+
+/// Response from crates.io single version endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionResponse {
+    pub version: Version,
+}
+
+/// Pagination metadata of crates.io listings
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PageMeta {
+    pub total: Option<u64>,
+    /// Query string of the next page, absent on the last page
+    pub next_page: Option<String>,
 }
 
 /// Response from crates.io search endpoint
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SearchResponse {
     pub crates: Vec<CrateSearchResult>,
     pub meta: SearchMeta,
 }
 
 /// Search metadata
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SearchMeta {
     pub total: u32,
+    /// Query string of the next page, absent on the last page
+    pub next_page: Option<String>,
 }
 
 /// Response from dependencies endpoint
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DependenciesResponse {
     pub dependencies: Vec<Dependency>,
 }
 
+/// Response from reverse dependencies endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReverseDependenciesResponse {
+    pub dependencies: Vec<ReverseDependencyApi>,
+    /// The dependent versions referenced by `dependencies[].version_id`
+    pub versions: Vec<DependentVersionApi>,
+    #[serde(default)]
+    pub meta: PageMeta,
+}
+
+/// A dependency declared by a dependent crate version
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReverseDependencyApi {
+    pub version_id: u64,
+    pub req: String,
+    /// Downloads of the dependent crate
+    #[serde(default)]
+    pub downloads: u64,
+}
+
+/// A crate version that depends on the looked-up crate
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DependentVersionApi {
+    pub id: u64,
+    #[serde(rename = "crate")]
+    pub crate_name: String,
+    pub num: String,
+}
+
 /// Response from downloads endpoint
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DownloadsResponse {
     pub version_downloads: Vec<VersionDownloadApi>,
     pub meta: DownloadsMeta,
 }
 
 /// Downloads metadata
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct DownloadsMeta {
     pub extra_downloads: Vec<ExtraDownload>,
 }
 
 /// Version download info from API
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct VersionDownloadApi {
     pub version: String,
     pub downloads: u64,
@@ -441,7 +1555,7 @@ pub struct VersionDownloadApi {
 }
 
 /// Extra download information
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ExtraDownload {
     pub date: String, // API returns date as string
     pub downloads: u64,
@@ -464,7 +1578,9 @@ impl From<CrateApiInfo> for CrateInfo {
             max_upload_size: api_info.max_upload_size,
             license: None,
             yanked: None,
-            links: None,
+            links: api_info.links,
+            msrv: None,
+            edition: None,
         }
     }
 }
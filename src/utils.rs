@@ -1,7 +1,7 @@
 //! Utility functions for the crate checker application
 
 use crate::error::{CrateCheckerError, Result};
-use crate::types::BatchInput;
+use crate::types::{BatchInput, Version, VersionResolution};
 use serde_json::Value;
 use std::fs;
 use std::path::Path;
@@ -65,6 +65,16 @@ pub fn parse_json_file<P: AsRef<Path>>(path: P) -> Result<BatchInput> {
     parse_json_input(&content)
 }
 
+/// Parse a newline-separated list of crate names, skipping blank lines and `#` comments
+pub fn parse_crate_list(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect()
+}
+
 /// Validate a batch input structure
 pub fn validate_batch_input(input: &BatchInput) -> Result<()> {
     match input {
@@ -112,11 +122,7 @@ pub fn validate_batch_input(input: &BatchInput) -> Result<()> {
             }
 
             for operation in operations {
-                if operation.operation.is_empty() {
-                    return Err(CrateCheckerError::ValidationError(
-                        "Operation type cannot be empty".to_string(),
-                    ));
-                }
+                operation.kind()?;
             }
         }
     }
@@ -177,6 +183,20 @@ pub fn format_download_count(count: u64) -> String {
     }
 }
 
+/// Render values as a one-line bar chart, scaled between their minimum and maximum
+pub fn sparkline(values: &[u64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = (max - min).max(1) as f64;
+    values
+        .iter()
+        .map(|&v| BARS[(((v - min) as f64 / range) * 7.0).round() as usize])
+        .collect()
+}
+
 /// Sanitize crate name for safe usage
 pub fn sanitize_crate_name(name: &str) -> String {
     name.chars()
@@ -184,6 +204,16 @@ pub fn sanitize_crate_name(name: &str) -> String {
         .collect()
 }
 
+/// Canonical form crates.io compares names by: lowercase, with `-` replaced by `_`
+pub fn normalize_crate_name(name: &str) -> String {
+    name.to_ascii_lowercase().replace('-', "_")
+}
+
+/// Whether two crate names count as the same name on crates.io
+pub fn names_collide(a: &str, b: &str) -> bool {
+    normalize_crate_name(a) == normalize_crate_name(b)
+}
+
 /// Check if a version string looks like a semver version
 pub fn is_semver_like(version: &str) -> bool {
     // Basic check for semver-like pattern: X.Y.Z with optional pre-release/build
@@ -201,6 +231,265 @@ pub fn extract_version_core(version: &str) -> Option<String> {
     }
 }
 
+/// Normalize common mistakes in a user-supplied version string
+///
+/// Strips a leading `v`, drops surplus zero components (`1.0.0.0` → `1.0.0`) and pads
+/// short versions (`1.2` → `1.2.0`). Pre-release and build suffixes are kept as-is.
+pub fn normalize_version_string(version: &str) -> String {
+    let trimmed = version.trim();
+    let trimmed = trimmed.strip_prefix(['v', 'V']).unwrap_or(trimmed);
+
+    let (core, suffix) = trimmed.split_at(trimmed.find(['-', '+']).unwrap_or(trimmed.len()));
+    let mut parts: Vec<&str> = core.split('.').collect();
+    if !parts
+        .iter()
+        .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+    {
+        return trimmed.to_string();
+    }
+
+    while parts.len() > 3
+        && parts
+            .last()
+            .is_some_and(|part| part.chars().all(|c| c == '0'))
+    {
+        parts.pop();
+    }
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+
+    format!("{}{}", parts.join("."), suffix)
+}
+
+/// Find the candidate version numerically nearest to `target`
+///
+/// Distance is compared major first, then minor, then patch; ties prefer stable
+/// releases and then the higher version (so `1.1` suggests the newest `1.0.x`).
+pub fn closest_version<'a, I>(target: &str, candidates: I) -> Option<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let normalized = normalize_version_string(target);
+    let target = semver::Version::parse(&normalized)
+        .ok()
+        .or_else(|| semver::Version::parse(&extract_version_core(&normalized)?).ok())?;
+
+    candidates
+        .into_iter()
+        .filter_map(|num| semver::Version::parse(num).ok())
+        .min_by_key(|candidate| {
+            // Lower components only matter once the higher ones agree
+            let same_major = candidate.major == target.major;
+            let same_minor = same_major && candidate.minor == target.minor;
+            (
+                candidate.major.abs_diff(target.major),
+                if same_major {
+                    candidate.minor.abs_diff(target.minor)
+                } else {
+                    0
+                },
+                if same_minor {
+                    candidate.patch.abs_diff(target.patch)
+                } else {
+                    0
+                },
+                !candidate.pre.is_empty(),
+                std::cmp::Reverse(candidate.clone()),
+            )
+        })
+        .map(|candidate| candidate.to_string())
+}
+
+/// Release channel accepted in place of a concrete version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VersionChannel {
+    /// `latest`: newest stable release, or newest pre-release if there is no stable one
+    Latest,
+    /// `latest-stable`
+    Stable,
+    /// `latest-prerelease`
+    Prerelease,
+    /// `latest-N`: newest stable release with major version N
+    Major(u64),
+}
+
+/// Whether a requested version means "the newest release": `latest` in any case, `*`, or blank
+pub fn is_latest_keyword(spec: &str) -> bool {
+    let spec = spec.trim();
+    spec.is_empty() || spec == "*" || spec.eq_ignore_ascii_case("latest")
+}
+
+fn parse_version_channel(spec: &str) -> Option<VersionChannel> {
+    if is_latest_keyword(spec) {
+        return Some(VersionChannel::Latest);
+    }
+
+    match spec.trim().to_ascii_lowercase().as_str() {
+        "latest-stable" => Some(VersionChannel::Stable),
+        "latest-prerelease" => Some(VersionChannel::Prerelease),
+        other => other
+            .strip_prefix("latest-")?
+            .parse()
+            .ok()
+            .map(VersionChannel::Major),
+    }
+}
+
+/// Highest non-yanked version in a release channel
+fn latest_in_channel(channel: VersionChannel, versions: &[Version]) -> Option<String> {
+    let published: Vec<semver::Version> = versions
+        .iter()
+        .filter(|v| !v.yanked)
+        .filter_map(|v| semver::Version::parse(&v.num).ok())
+        .collect();
+    let newest = |matches: &dyn Fn(&semver::Version) -> bool| {
+        published.iter().filter(|v| matches(v)).max().cloned()
+    };
+
+    let version = match channel {
+        VersionChannel::Latest => newest(&|v| v.pre.is_empty()).or_else(|| newest(&|_| true)),
+        VersionChannel::Stable => newest(&|v| v.pre.is_empty()),
+        VersionChannel::Prerelease => newest(&|v| !v.pre.is_empty()),
+        VersionChannel::Major(major) => newest(&|v| v.pre.is_empty() && v.major == major),
+    };
+    version.map(|v| v.to_string())
+}
+
+/// Highest non-yanked, non-prerelease version
+pub fn latest_stable_version(versions: &[Version]) -> Option<String> {
+    latest_in_channel(VersionChannel::Stable, versions)
+}
+
+/// Sort versions newest first by semver precedence; unparseable versions go last
+pub fn sort_versions_desc(versions: &mut [Version]) {
+    versions.sort_by_cached_key(|v| std::cmp::Reverse(semver::Version::parse(&v.num).ok()));
+}
+
+/// Match a user-supplied version string against a crate's published versions
+///
+/// Accepts the channels `latest`, `latest-stable`, `latest-prerelease` and `latest-N`.
+/// Otherwise tries the exact string, then its normalized form, and finally suggests
+/// the nearest non-yanked version.
+pub fn resolve_version(requested: &str, versions: &[Version]) -> VersionResolution {
+    if let Some(channel) = parse_version_channel(requested) {
+        return match latest_in_channel(channel, versions) {
+            Some(version) => VersionResolution::Found(version),
+            None => VersionResolution::NotFound { suggestion: None },
+        };
+    }
+
+    if versions.iter().any(|v| v.num == requested) {
+        return VersionResolution::Found(requested.to_string());
+    }
+
+    let normalized = normalize_version_string(requested);
+    if versions.iter().any(|v| v.num == normalized) {
+        return VersionResolution::Found(normalized);
+    }
+
+    let suggestion = closest_version(
+        requested,
+        versions
+            .iter()
+            .filter(|v| !v.yanked)
+            .map(|v| v.num.as_str()),
+    );
+    VersionResolution::NotFound { suggestion }
+}
+
+/// Resolve a version or semver requirement (e.g. `^1`, `>=0.3, <0.5`) to a concrete version
+///
+/// Exact versions and channels are handled as in [`resolve_version`]; anything else is
+/// parsed as a requirement and matched against non-yanked versions, newest first.
+pub fn resolve_requirement(requested: &str, versions: &[Version]) -> Option<String> {
+    if let VersionResolution::Found(version) = resolve_version(requested, versions) {
+        return Some(version);
+    }
+
+    highest_matching(requested, versions)
+}
+
+/// Resolve a version, channel or semver requirement, keeping exact matching for concrete versions
+///
+/// Concrete versions (`1.0.130`) go through [`resolve_version`] alone, so a missing one is
+/// reported with a suggestion rather than silently widened to `^1.0.130`. Anything else that
+/// parses as a requirement (`^1.0`, `>=1.0, <2.0`) resolves to the highest matching
+/// non-yanked version.
+pub fn resolve_version_spec(requested: &str, versions: &[Version]) -> VersionResolution {
+    let resolution = resolve_version(requested, versions);
+    if matches!(resolution, VersionResolution::Found(_))
+        || semver::Version::parse(requested.trim()).is_ok()
+    {
+        return resolution;
+    }
+
+    match highest_matching(requested, versions) {
+        Some(version) => VersionResolution::Found(version),
+        None => resolution,
+    }
+}
+
+/// Semver-compatible series a requirement selects from: `1` for `^1.2`, `0.3` for `~0.3.1`
+///
+/// Cargo unifies requirements within one series and builds separate copies across
+/// series. Returns `None` for unparseable or unbounded requirements such as `*`.
+pub fn semver_compat_series(requirement: &str) -> Option<String> {
+    let requirement = semver::VersionReq::parse(requirement.trim()).ok()?;
+    let comparator = requirement.comparators.first()?;
+    match (comparator.major, comparator.minor, comparator.patch) {
+        (0, Some(0), Some(patch)) => Some(format!("0.0.{}", patch)),
+        (0, Some(minor), _) => Some(format!("0.{}", minor)),
+        (0, None, _) => Some("0".to_string()),
+        (major, _, _) => Some(major.to_string()),
+    }
+}
+
+/// Highest non-yanked version matching a semver requirement
+fn highest_matching(requirement: &str, versions: &[Version]) -> Option<String> {
+    let requirement = semver::VersionReq::parse(requirement.trim()).ok()?;
+    versions
+        .iter()
+        .filter(|v| !v.yanked)
+        .filter_map(|v| semver::Version::parse(&v.num).ok())
+        .filter(|v| requirement.matches(v))
+        .max()
+        .map(|v| v.to_string())
+}
+
+/// Approximate all-time download counts of crates.io crates at given percentiles
+///
+/// A coarse snapshot of the registry; good enough to tell niche crates from
+/// ecosystem staples, not for precise ranking.
+const DOWNLOAD_PERCENTILES: &[(f64, u64)] = &[
+    (0.0, 0),
+    (10.0, 150),
+    (25.0, 400),
+    (50.0, 1_500),
+    (75.0, 9_000),
+    (90.0, 60_000),
+    (95.0, 300_000),
+    (99.0, 10_000_000),
+    (99.9, 150_000_000),
+    (100.0, 1_000_000_000),
+];
+
+/// Estimate the percentile of a download count, interpolating log-linearly
+/// between the points of the reference distribution
+pub fn download_percentile(downloads: u64) -> f64 {
+    let log = |n: u64| ((n + 1) as f64).ln();
+
+    for window in DOWNLOAD_PERCENTILES.windows(2) {
+        let ((low_pct, low), (high_pct, high)) = (window[0], window[1]);
+        if downloads <= high {
+            let fraction = (log(downloads) - log(low)) / (log(high) - log(low));
+            return low_pct + fraction.clamp(0.0, 1.0) * (high_pct - low_pct);
+        }
+    }
+
+    100.0
+}
+
 /// Create example batch inputs for help/documentation
 pub fn create_example_batch_inputs() -> Vec<(&'static str, &'static str)> {
     vec![
@@ -216,9 +505,9 @@ pub fn create_example_batch_inputs() -> Vec<(&'static str, &'static str)> {
             "Advanced operations",
             r#"{
   "operations": [
-    {"crate": "serde", "version": "1.0.0", "operation": "check_version"},
+    {"crate": "serde", "version": "1.0.0", "operation": "check"},
     {"crate": "tokio", "operation": "info"},
-    {"crates": ["tokio", "reqwest"], "operation": "batch_check"}
+    {"crates": ["tokio", "reqwest"], "operation": "deps"}
   ]
 }"#,
         ),
@@ -247,6 +536,245 @@ pub fn progress_indicator(current: usize, total: usize, width: usize) -> String
     format!("[{}{}] {}/{}", filled, empty, current, total)
 }
 
+/// Generate a random W3C trace id (32 lowercase hex characters)
+pub fn generate_trace_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Build a W3C `traceparent` header value for a trace id, if the id is in W3C form
+pub fn traceparent_header(trace_id: &str) -> Option<String> {
+    let is_w3c = trace_id.len() == 32
+        && trace_id
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+        && trace_id.chars().any(|c| c != '0');
+
+    if !is_w3c {
+        return None;
+    }
+
+    let parent_id = &uuid::Uuid::new_v4().simple().to_string()[..16];
+    Some(format!("00-{}-{}-01", trace_id, parent_id))
+}
+
+/// Exponential backoff with full jitter for retry number `attempt` (starting at zero)
+///
+/// The delay is drawn uniformly between zero and `base * 2^attempt`, capped at `max`,
+/// so concurrent requests that fail together spread their retries out.
+pub fn backoff_delay(
+    attempt: u32,
+    base: std::time::Duration,
+    max: std::time::Duration,
+) -> std::time::Duration {
+    backoff_delay_with(attempt, base, max, random_fraction())
+}
+
+/// [`backoff_delay`] with the jitter given as `fraction` of the ceiling (0.0 - 1.0)
+pub fn backoff_delay_with(
+    attempt: u32,
+    base: std::time::Duration,
+    max: std::time::Duration,
+    fraction: f64,
+) -> std::time::Duration {
+    let ceiling = base.saturating_mul(2u32.saturating_pow(attempt)).min(max);
+    ceiling.mul_f64(fraction.clamp(0.0, 1.0))
+}
+
+/// Uniformly random number in `[0, 1)`
+fn random_fraction() -> f64 {
+    // The low 53 bits of a v4 UUID are all random (version and variant bits sit higher)
+    const MANTISSA_BITS: u32 = 53;
+    let bits = uuid::Uuid::new_v4().as_u128() as u64 & ((1 << MANTISSA_BITS) - 1);
+    bits as f64 / (1u64 << MANTISSA_BITS) as f64
+}
+
+/// Parse a `Retry-After` header value given as delay-seconds or as an HTTP-date
+///
+/// Dates that already passed yield a zero delay.
+pub fn parse_retry_after(
+    value: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<std::time::Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(std::time::Duration::from_secs(secs));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+/// Extract the crates.io packages pinned in a Cargo.lock as `(name, version)` pairs
+///
+/// Path, git and alternate-registry packages are skipped.
+pub fn parse_cargo_lock(content: &str) -> Result<Vec<(String, String)>> {
+    #[derive(serde::Deserialize)]
+    struct LockedPackage {
+        name: String,
+        version: String,
+        source: Option<String>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Lockfile {
+        #[serde(default)]
+        package: Vec<LockedPackage>,
+    }
+
+    let lockfile: Lockfile = toml::from_str(content)
+        .map_err(|e| CrateCheckerError::validation(format!("Invalid Cargo.lock: {}", e)))?;
+
+    Ok(lockfile
+        .package
+        .into_iter()
+        .filter(|package| {
+            matches!(
+                package.source.as_deref(),
+                Some(crate::CRATES_IO_SOURCE) | Some(crate::CRATES_IO_SPARSE_SOURCE)
+            )
+        })
+        .map(|package| (package.name, package.version))
+        .collect())
+}
+
+/// Compare a raw JSON document with the re-serialized value it was parsed into
+///
+/// Returns one description per unknown field (present in `raw` only) and per
+/// defaulted field (present in `parsed` only), using dotted paths such as
+/// `crate.name` or `versions[0].license`.
+pub fn schema_drift(raw: &Value, parsed: &Value) -> Vec<String> {
+    fn walk(raw: &Value, parsed: &Value, path: &str, drift: &mut Vec<String>) {
+        let field = |key: &str| {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", path, key)
+            }
+        };
+
+        match (raw, parsed) {
+            (Value::Object(raw), Value::Object(parsed)) => {
+                for (key, value) in raw {
+                    match parsed.get(key) {
+                        Some(parsed_value) => walk(value, parsed_value, &field(key), drift),
+                        None => drift.push(format!("unknown field `{}`", field(key))),
+                    }
+                }
+                for key in parsed.keys().filter(|key| !raw.contains_key(*key)) {
+                    drift.push(format!("missing field `{}`", field(key)));
+                }
+            }
+            (Value::Array(raw), Value::Array(parsed)) => {
+                for (index, (raw, parsed)) in raw.iter().zip(parsed).enumerate() {
+                    walk(raw, parsed, &format!("{}[{}]", path, index), drift);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut drift = Vec::new();
+    walk(raw, parsed, "", &mut drift);
+    drift
+}
+
+/// Extract the dependencies of a Cargo.toml as `name -> requirement`
+///
+/// Covers `[dependencies]`, `[dev-dependencies]` and `[build-dependencies]`; renamed
+/// dependencies are keyed by their `package` name and path or git dependencies
+/// without a version get the requirement `*`.
+pub fn parse_manifest_dependencies(
+    content: &str,
+) -> Result<std::collections::BTreeMap<String, String>> {
+    let manifest = parse_manifest(content)?;
+    Ok(manifest_dependency_specs(&manifest)
+        .map(|(name, spec)| {
+            let requirement = match spec {
+                toml::Value::String(requirement) => requirement.as_str(),
+                spec => spec.get("version").and_then(|v| v.as_str()).unwrap_or("*"),
+            };
+            (name.to_string(), requirement.to_string())
+        })
+        .collect())
+}
+
+/// Names of the dependencies of a Cargo.toml that come from crates.io
+///
+/// Covers the same sections as [`parse_manifest_dependencies`], skipping `path` and
+/// `git` dependencies and those from another `registry`.
+pub fn parse_manifest_registry_dependencies(
+    content: &str,
+) -> Result<std::collections::BTreeSet<String>> {
+    let manifest = parse_manifest(content)?;
+    Ok(manifest_dependency_specs(&manifest)
+        .filter(|(_, spec)| {
+            ["path", "git", "registry"]
+                .iter()
+                .all(|key| spec.get(key).is_none())
+        })
+        .map(|(name, _)| name.to_string())
+        .collect())
+}
+
+fn parse_manifest(content: &str) -> Result<toml::Table> {
+    toml::from_str(content)
+        .map_err(|e| CrateCheckerError::validation(format!("Invalid Cargo.toml: {}", e)))
+}
+
+/// `(package name, spec)` of every dependency in the dependency sections of a manifest
+fn manifest_dependency_specs(manifest: &toml::Table) -> impl Iterator<Item = (&str, &toml::Value)> {
+    ["dependencies", "dev-dependencies", "build-dependencies"]
+        .into_iter()
+        .filter_map(|section| manifest.get(section).and_then(|s| s.as_table()))
+        .flatten()
+        .filter_map(|(key, spec)| match spec {
+            toml::Value::String(_) => Some((key.as_str(), spec)),
+            toml::Value::Table(table) => Some((
+                table
+                    .get("package")
+                    .and_then(|p| p.as_str())
+                    .unwrap_or(key.as_str()),
+                spec,
+            )),
+            _ => None,
+        })
+}
+
+/// Count non-yanked versions released after `from` up to and including `to`
+///
+/// Both ends are requirements resolved with [`resolve_requirement`]; the span is
+/// the same for upgrades and downgrades. Returns `None` if either end is unresolvable.
+pub fn versions_between(versions: &[Version], from: &str, to: &str) -> Option<usize> {
+    let from = semver::Version::parse(&resolve_requirement(from, versions)?).ok()?;
+    let to = semver::Version::parse(&resolve_requirement(to, versions)?).ok()?;
+    let (low, high) = if from <= to { (from, to) } else { (to, from) };
+
+    Some(
+        versions
+            .iter()
+            .filter(|v| !v.yanked)
+            .filter_map(|v| semver::Version::parse(&v.num).ok())
+            .filter(|v| *v > low && *v <= high)
+            .count(),
+    )
+}
+
+/// Format a GitHub Actions workflow command such as `::error::message`
+///
+/// `level` is one of `error`, `warning` or `notice`; the message is escaped so
+/// multi-line text stays within a single annotation.
+pub fn github_annotation(level: &str, message: &str) -> String {
+    let escaped = message
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A");
+    format!("::{}::{}", level, escaped)
+}
+
 /// Parse a timeout string (e.g., "30s", "2m", "1h")
 pub fn parse_timeout(input: &str) -> Result<std::time::Duration> {
     let input = input.trim().to_lowercase();
@@ -282,6 +810,16 @@ pub fn parse_timeout(input: &str) -> Result<std::time::Duration> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_normalize_crate_name() {
+        assert_eq!(normalize_crate_name("Foo-Bar"), "foo_bar");
+        assert_eq!(normalize_crate_name("foo_bar"), "foo_bar");
+        assert_eq!(normalize_crate_name("foo-bar"), "foo_bar");
+        assert!(names_collide("Foo-Bar", "foo_bar"));
+        assert!(names_collide("foo-bar", "FOO_BAR"));
+        assert!(!names_collide("foo-bar", "foobar"));
+    }
+
     #[test]
     fn test_parse_json_input_crate_version_map() {
         let json = r#"{"serde": "1.0.0", "tokio": "latest"}"#;
@@ -310,6 +848,236 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_normalize_version_string() {
+        assert_eq!(normalize_version_string("v1.2.3"), "1.2.3");
+        assert_eq!(normalize_version_string("1.0.0.0"), "1.0.0");
+        assert_eq!(normalize_version_string("v1.2"), "1.2.0");
+        assert_eq!(normalize_version_string(" 1.0.0-beta.1 "), "1.0.0-beta.1");
+        assert_eq!(normalize_version_string("1.0.0.5"), "1.0.0.5");
+        assert_eq!(normalize_version_string("latest"), "latest");
+    }
+
+    #[test]
+    fn test_closest_version() {
+        let versions = ["0.9.15", "1.0.130", "1.0.210", "2.0.0-alpha.1"];
+        assert_eq!(
+            closest_version("1.0.200", versions),
+            Some("1.0.210".to_string())
+        );
+        assert_eq!(
+            closest_version("v1.1", versions),
+            Some("1.0.210".to_string())
+        );
+        assert_eq!(closest_version("0.9", versions), Some("0.9.15".to_string()));
+        assert_eq!(closest_version("not-a-version", versions), None);
+    }
+
+    fn published(num: &str, yanked: bool) -> Version {
+        Version {
+            num: num.to_string(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            downloads: 0,
+            yanked,
+            id: None,
+            crate_size: None,
+            published_by: None,
+            audit_actions: None,
+            license: None,
+            features: None,
+            links: None,
+            rust_version: None,
+            edition: None,
+        }
+    }
+
+    fn channel_fixture() -> Vec<Version> {
+        vec![
+            published("0.9.15", false),
+            published("1.0.0", false),
+            published("1.4.2", false),
+            published("1.5.0", true),
+            published("2.0.0-beta.1", false),
+            published("2.0.0-alpha.3", false),
+            published("2.1.0-rc.1", true),
+        ]
+    }
+
+    #[test]
+    fn test_resolve_version_channels() {
+        let versions = channel_fixture();
+        let found = |v: &str| VersionResolution::Found(v.to_string());
+
+        assert_eq!(resolve_version("latest", &versions), found("1.4.2"));
+        assert_eq!(resolve_version("latest-stable", &versions), found("1.4.2"));
+        assert_eq!(
+            resolve_version("latest-prerelease", &versions),
+            found("2.0.0-beta.1")
+        );
+        assert_eq!(resolve_version("latest-1", &versions), found("1.4.2"));
+        assert_eq!(resolve_version("latest-0", &versions), found("0.9.15"));
+        assert_eq!(
+            resolve_version("latest-3", &versions),
+            VersionResolution::NotFound { suggestion: None }
+        );
+    }
+
+    #[test]
+    fn test_resolve_version_spec() {
+        let versions = channel_fixture();
+        let found = |v: &str| VersionResolution::Found(v.to_string());
+
+        assert_eq!(
+            resolve_version_spec(">=1.0, <2.0", &versions),
+            found("1.4.2")
+        );
+        assert_eq!(resolve_version_spec("^0.9", &versions), found("0.9.15"));
+        assert_eq!(resolve_version_spec("1.4.2", &versions), found("1.4.2"));
+        // A missing concrete version is not widened to a caret requirement
+        assert!(matches!(
+            resolve_version_spec("1.4.3", &versions),
+            VersionResolution::NotFound { .. }
+        ));
+        assert!(matches!(
+            resolve_version_spec(">=3", &versions),
+            VersionResolution::NotFound { .. }
+        ));
+    }
+
+    #[test]
+    fn test_latest_keyword_synonyms() {
+        let versions = channel_fixture();
+        let found = VersionResolution::Found("1.4.2".to_string());
+
+        for spec in ["latest", "Latest", "LATEST", " latest ", "*", "", "  "] {
+            assert!(is_latest_keyword(spec), "{spec:?} should mean latest");
+            assert_eq!(resolve_version(spec, &versions), found, "{spec:?}");
+        }
+        assert!(!is_latest_keyword("latest-stable"));
+        assert!(!is_latest_keyword("1.*"));
+        assert_eq!(
+            resolve_version("Latest-Stable", &versions),
+            VersionResolution::Found("1.4.2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_latest_falls_back_to_prerelease() {
+        let versions = vec![
+            published("0.1.0-alpha.1", false),
+            published("0.1.0-alpha.2", false),
+        ];
+
+        assert_eq!(
+            resolve_version("latest", &versions),
+            VersionResolution::Found("0.1.0-alpha.2".to_string())
+        );
+        assert_eq!(
+            resolve_version("latest-stable", &versions),
+            VersionResolution::NotFound { suggestion: None }
+        );
+    }
+
+    #[test]
+    fn test_sort_versions_desc() {
+        let mut versions = vec![
+            published("1.2.0", false),
+            published("1.10.0", false),
+            published("not-semver", false),
+            published("2.0.0-beta.1", false),
+            published("1.9.3", false),
+        ];
+        sort_versions_desc(&mut versions);
+
+        let order: Vec<&str> = versions.iter().map(|v| v.num.as_str()).collect();
+        assert_eq!(
+            order,
+            ["2.0.0-beta.1", "1.10.0", "1.9.3", "1.2.0", "not-semver"]
+        );
+        assert_eq!(latest_stable_version(&versions).as_deref(), Some("1.10.0"));
+    }
+
+    #[test]
+    fn test_download_percentile() {
+        assert_eq!(download_percentile(0), 0.0);
+        assert!((download_percentile(1_500) - 50.0).abs() < 1e-9);
+        assert!(download_percentile(50) < download_percentile(5_000));
+        assert!(download_percentile(300_000_000) > 99.9);
+        assert_eq!(download_percentile(u64::MAX), 100.0);
+    }
+
+    #[test]
+    fn test_schema_drift() {
+        let raw = serde_json::json!({"crate": {"name": "serde", "extra": 1}, "versions": [{"num": "1.0.0"}]});
+        let parsed = serde_json::json!({"crate": {"name": "serde"}, "versions": [{"num": "1.0.0", "license": null}]});
+
+        assert_eq!(
+            schema_drift(&raw, &parsed),
+            [
+                "unknown field `crate.extra`",
+                "missing field `versions[0].license`"
+            ]
+        );
+        assert!(schema_drift(&raw, &raw).is_empty());
+    }
+
+    #[test]
+    fn test_github_annotation() {
+        assert_eq!(
+            github_annotation("error", "Crate 'foo' is missing"),
+            "::error::Crate 'foo' is missing"
+        );
+        assert_eq!(
+            github_annotation("warning", "100% broken\nsecond line"),
+            "::warning::100%25 broken%0Asecond line"
+        );
+    }
+
+    #[test]
+    fn test_parse_crate_list() {
+        let content = "serde\n\n# comment line\ntokio  # trailing comment\n  reqwest  \n";
+        assert_eq!(parse_crate_list(content), vec!["serde", "tokio", "reqwest"]);
+    }
+
+    #[test]
+    fn test_traceparent_header() {
+        let trace_id = generate_trace_id();
+        let header = traceparent_header(&trace_id).unwrap();
+        let parts: Vec<&str> = header.split('-').collect();
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[0], "00");
+        assert_eq!(parts[1], trace_id);
+        assert_eq!(parts[2].len(), 16);
+        assert_eq!(parts[3], "01");
+
+        assert!(traceparent_header("my-request-42").is_none());
+        assert!(traceparent_header(&"0".repeat(32)).is_none());
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        use chrono::TimeZone;
+        let now = chrono::Utc
+            .with_ymd_and_hms(2015, 10, 21, 7, 28, 0)
+            .unwrap();
+
+        assert_eq!(
+            parse_retry_after("2", now),
+            Some(std::time::Duration::from_secs(2))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(std::time::Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(std::time::Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(parse_retry_after("-1", now), None);
+    }
+
     #[test]
     fn test_format_file_size() {
         assert_eq!(format_file_size(0), "0 B");
@@ -327,6 +1095,31 @@ mod tests {
         assert_eq!(format_download_count(2500000000), "2.5B");
     }
 
+    #[test]
+    fn test_semver_compat_series() {
+        assert_eq!(semver_compat_series("^1.2").as_deref(), Some("1"));
+        assert_eq!(semver_compat_series("1.0.130").as_deref(), Some("1"));
+        assert_eq!(semver_compat_series("~0.3.1").as_deref(), Some("0.3"));
+        assert_eq!(semver_compat_series("=0.0.4").as_deref(), Some("0.0.4"));
+        assert_eq!(semver_compat_series(">=2.0, <3.0").as_deref(), Some("2"));
+        assert_eq!(semver_compat_series("*"), None);
+        assert_eq!(semver_compat_series("not a req"), None);
+    }
+
+    #[test]
+    fn test_sparkline_ramp() {
+        let ramp: Vec<u64> = (0..8).map(|i| 100 + i * 10).collect();
+        assert_eq!(sparkline(&ramp), "▁▂▃▄▅▆▇█");
+        assert_eq!(sparkline(&[1, 8, 4]), "▁█▄");
+    }
+
+    #[test]
+    fn test_sparkline_flat_and_empty() {
+        assert_eq!(sparkline(&[5, 5, 5]), "▁▁▁");
+        assert_eq!(sparkline(&[0]), "▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+
     #[test]
     fn test_is_semver_like() {
         assert!(is_semver_like("1.0.0"));
@@ -356,4 +1149,62 @@ mod tests {
         );
         assert!(parse_timeout("invalid").is_err());
     }
+
+    #[test]
+    fn test_parse_manifest_registry_dependencies() {
+        let manifest = r#"
+[dependencies]
+serde = "1.0"
+json = { package = "serde_json", version = "1" }
+local = { path = "../local", version = "0.1" }
+forked = { git = "https://example.com/forked" }
+internal = { version = "1", registry = "company" }
+
+[dev-dependencies]
+tempfile = "3"
+
+[build-dependencies]
+cc = { version = "1.0" }
+"#;
+        let names: Vec<String> = parse_manifest_registry_dependencies(manifest)
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(names, ["cc", "serde", "serde_json", "tempfile"]);
+
+        let all = parse_manifest_dependencies(manifest).unwrap();
+        assert_eq!(all.len(), 7);
+        assert_eq!(all["forked"], "*");
+        assert_eq!(all["serde_json"], "1");
+        assert!(parse_manifest_registry_dependencies("not = [toml").is_err());
+    }
+
+    #[test]
+    fn test_backoff_delay_grows_and_is_capped() {
+        use std::time::Duration;
+        let base = Duration::from_millis(100);
+        let max = Duration::from_secs(1);
+
+        let ceilings: Vec<Duration> = (0..6)
+            .map(|attempt| backoff_delay_with(attempt, base, max, 1.0))
+            .collect();
+        assert_eq!(
+            ceilings[..4],
+            [100, 200, 400, 800].map(Duration::from_millis)
+        );
+        assert_eq!(ceilings[4], max);
+        assert_eq!(ceilings[5], max);
+        assert_eq!(
+            backoff_delay_with(3, base, max, 0.5),
+            Duration::from_millis(400)
+        );
+        assert_eq!(backoff_delay_with(3, base, max, 0.0), Duration::ZERO);
+        assert_eq!(backoff_delay_with(u32::MAX, base, max, 1.0), max);
+
+        for attempt in 0..40 {
+            let delay = backoff_delay(attempt, base, max);
+            assert!(delay <= max);
+            assert!(delay <= backoff_delay_with(attempt, base, max, 1.0));
+        }
+    }
 }
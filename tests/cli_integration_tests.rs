@@ -1,3 +1,5 @@
+mod common;
+
 use assert_cmd::Command;
 use predicates::prelude::*;
 use std::fs;
@@ -5,7 +7,10 @@ use tempfile::TempDir;
 
 /// Helper to create a command for testing
 fn crate_checker_cmd() -> Command {
-    Command::cargo_bin("crate-checker").unwrap()
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("crate-checker");
+    // Keep output stable when the suite itself runs under GitHub Actions
+    cmd.env_remove("GITHUB_ACTIONS");
+    cmd
 }
 
 /// Test basic help output
@@ -26,7 +31,7 @@ fn test_version_output() {
         .arg("--version")
         .assert()
         .success()
-        .stdout(predicate::str::contains("1.0.0"));
+        .stdout(format!("crate-checker {}\n", crate_checker::VERSION));
 }
 
 /// Test checking an existing crate
@@ -98,6 +103,32 @@ fn test_check_multiple_summary_only() {
         .stdout(predicate::str::contains("Total checked: 2"));
 }
 
+/// Test repeated crate names are checked once unless --allow-duplicates is given
+#[test]
+fn test_check_multiple_deduplicates() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url])
+        .args(["check-multiple", "serde", "serde", "tokio"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Total checked: 2"));
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url])
+        .args([
+            "check-multiple",
+            "serde",
+            "serde",
+            "tokio",
+            "--allow-duplicates",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Total checked: 3"));
+}
+
 /// Test check multiple with fail on missing
 #[test]
 fn test_check_multiple_fail_on_missing() {
@@ -189,6 +220,41 @@ fn test_search_with_limit() {
         .success();
 }
 
+/// Test browsing a category and a keyword, and the error for an unknown category
+#[test]
+fn test_category_and_keyword() {
+    let api_url = common::mock_registry();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["category", "command-line-utilities", "--limit", "2"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let crates: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = crates
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["tree-root", "tree-left"]);
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "keyword", "async"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("tokio"));
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "category", "command-line-utilites"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Category 'command-line-utilites' not found",
+        ));
+}
+
 /// Test getting dependencies
 #[test]
 fn test_crate_deps() {
@@ -277,6 +343,61 @@ fn test_config_output_to_file() {
     assert!(content.contains("[server]"));
 }
 
+/// Test the sample config follows --format or the output file's extension
+#[test]
+fn test_config_formats() {
+    let sample = |format: &str| {
+        let output = crate_checker_cmd()
+            .args(["config", "--format", format])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let toml: toml::Value = toml::from_str(&sample("toml")).unwrap();
+    assert_eq!(toml["server"]["port"].as_integer(), Some(3000));
+    let json: serde_json::Value = serde_json::from_str(&sample("json")).unwrap();
+    assert_eq!(json["server"]["port"], 3000);
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&sample("yaml")).unwrap();
+    assert_eq!(yaml["server"]["port"], serde_yaml::Value::from(3000));
+
+    // Output formats that are not config formats are rejected
+    crate_checker_cmd()
+        .args(["config", "--format", "csv"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'csv'"));
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("crate-checker.json");
+    crate_checker_cmd()
+        .args(["config", "--output", config_path.to_str().unwrap()])
+        .assert()
+        .success();
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    assert_eq!(json["crates_io"]["api_url"], "https://crates.io/api/v1");
+
+    // The generated file is accepted as configuration
+    crate_checker_cmd()
+        .args(["--config", config_path.to_str().unwrap(), "examples"])
+        .assert()
+        .success();
+
+    // An explicit --format wins over the output file's extension
+    crate_checker_cmd()
+        .args([
+            "config",
+            "--format",
+            "toml",
+            "--output",
+            config_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    assert!(toml::from_str::<toml::Value>(&fs::read_to_string(&config_path).unwrap()).is_ok());
+}
+
 /// Test examples command
 #[test]
 fn test_examples_command() {
@@ -288,6 +409,60 @@ fn test_examples_command() {
         .stdout(predicate::str::contains("Crate version map"));
 }
 
+/// Test generating bash completions
+#[test]
+fn test_completions_command() {
+    crate_checker_cmd()
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("_crate-checker()"))
+        .stdout(predicate::str::contains("check-multiple"));
+
+    crate_checker_cmd()
+        .args(["completions", "tcsh"])
+        .assert()
+        .failure();
+}
+
+/// Test the JSON Schema export of output types
+#[test]
+fn test_schema_command() {
+    let output = crate_checker_cmd()
+        .args(["schema", "crate-info"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["title"], "CrateInfo");
+    assert_eq!(schema["properties"]["newest_version"]["type"], "string");
+    assert!(schema["required"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("newest_version")));
+
+    let output = crate_checker_cmd().arg("schema").output().unwrap();
+    assert!(output.status.success());
+    let schemas: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut names: Vec<&str> = schemas
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        [
+            "BatchResult",
+            "CrateCheckResult",
+            "CrateInfo",
+            "CrateSearchResult"
+        ]
+    );
+    assert!(schemas["CrateCheckResult"]["definitions"]["CrateInfo"].is_object());
+}
+
 /// Test verbose output
 #[test]
 fn test_verbose_output() {
@@ -308,6 +483,79 @@ fn test_quiet_mode() {
         .success();
 }
 
+/// Test --quiet silences everything but errors on stderr, and --verbose shows debug logs
+#[test]
+fn test_quiet_and_verbose_logging() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--quiet", "check", "serde"])
+        .assert()
+        .success()
+        .stderr("");
+
+    crate_checker_cmd()
+        .args([
+            "--api-url",
+            &api_url,
+            "--quiet",
+            "check-multiple",
+            "serde",
+            "nope",
+        ])
+        .assert()
+        .success()
+        .stderr("");
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--verbose", "--format", "json"])
+        .args(["check", "serde"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("DEBUG"));
+}
+
+/// Test logging.format = "json" writes one JSON object per log line
+#[test]
+fn test_json_logging_format() {
+    let api_url = common::mock_registry();
+
+    let output = crate_checker_cmd()
+        .env("CRATE_CHECKER__LOGGING__FORMAT", "json")
+        .args(["--api-url", &api_url, "--verbose", "check", "serde"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.lines().count() > 0);
+    for line in stderr.lines() {
+        let entry: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(entry["level"].is_string());
+    }
+}
+
+/// Test logging.file appends log lines to the file, creating missing directories
+#[test]
+fn test_logging_to_file() {
+    let api_url = common::mock_registry();
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("logs/nested/crate-checker.log");
+
+    crate_checker_cmd()
+        .env("CRATE_CHECKER__LOGGING__FILE", &log_path)
+        .args(["--api-url", &api_url, "--verbose", "check", "serde"])
+        .assert()
+        .success();
+
+    let logged = fs::read_to_string(&log_path).unwrap();
+    assert!(logged.contains("DEBUG"));
+    assert!(
+        !logged.contains('\u{1b}'),
+        "file logs should not contain ANSI colors"
+    );
+}
+
 /// Test custom timeout
 #[test]
 fn test_custom_timeout() {
@@ -470,3 +718,1358 @@ fn test_csv_output() {
         .assert()
         .success();
 }
+
+/// Test --trace-id is propagated as headers on outbound requests
+#[test]
+fn test_trace_id_header_propagation() {
+    let (api_url, log) = common::serve_recorded(common::registry_router());
+    let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    crate_checker_cmd()
+        .args([
+            "--api-url",
+            &api_url,
+            "--trace-id",
+            trace_id,
+            "check",
+            "serde",
+        ])
+        .assert()
+        .success();
+
+    let requests = log.headers();
+    assert!(!requests.is_empty());
+    for headers in requests {
+        assert_eq!(headers["x-trace-id"], trace_id);
+        let traceparent = headers["traceparent"].to_str().unwrap();
+        assert!(traceparent.starts_with(&format!("00-{}-", trace_id)));
+    }
+}
+
+/// Test --trace generates a trace id when none is given
+#[test]
+fn test_trace_generates_id() {
+    let (api_url, log) = common::serve_recorded(common::registry_router());
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--trace", "check", "serde"])
+        .assert()
+        .success();
+
+    let requests = log.headers();
+    assert!(!requests.is_empty());
+    let trace_id = requests[0]["x-trace-id"].to_str().unwrap();
+    assert_eq!(trace_id.len(), 32);
+}
+
+/// Test typo'd version strings are normalized or get a suggestion
+#[test]
+fn test_check_version_normalization_and_suggestion() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["check", "serde", "--version", "v1.0.130.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""resolved_version": "1.0.130""#));
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["check", "serde", "--version", "1.0.200"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(r#""exists": false"#))
+        .stdout(predicate::str::contains(r#""suggestion": "1.0.210""#));
+}
+
+/// Test semver requirements resolve to the highest matching non-yanked release
+#[test]
+fn test_check_version_requirement() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["check", "serde", "--version", ">=1.0, <2.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""exists": true"#))
+        .stdout(predicate::str::contains(r#""resolved_version": "1.0.210""#));
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["deps", "tokio", "--version", "^1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("pin-project-lite"));
+}
+
+/// Test pre-releases are hidden from versions unless --include-prerelease is given
+#[test]
+fn test_include_prerelease() {
+    let api_url = common::mock_registry();
+
+    let list = |extra: &[&str]| -> Vec<String> {
+        let output = crate_checker_cmd()
+            .args(["--api-url", &api_url, "--format", "json"])
+            .args(["versions", "serde"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let versions: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+        versions
+            .iter()
+            .map(|v| v["num"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let hidden = list(&[]);
+    assert_eq!(hidden.len(), 5);
+    assert!(!hidden.iter().any(|v| v.contains('-')), "{:?}", hidden);
+
+    let shown = list(&["--include-prerelease"]);
+    assert_eq!(shown.len(), 7);
+    assert!(shown.contains(&"2.0.0-alpha.1".to_string()));
+    assert!(shown.contains(&"1.0.0-rc.1".to_string()));
+}
+
+/// Test --stable lists and resolves only stable releases
+#[test]
+fn test_stable_versions() {
+    let api_url = common::mock_registry();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["versions", "serde", "--stable"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let versions: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let nums: Vec<&str> = versions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["num"].as_str().unwrap())
+        .collect();
+    assert_eq!(nums, ["1.0.210", "1.0.130", "1.0.0", "0.9.15"]);
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["check", "serde", "--stable"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""latest_stable": "1.0.210""#));
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["check", "serde", "--version", "latest", "--stable"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""resolved_version": "1.0.210""#));
+}
+
+/// Test the analyze command lists the dependency shared by two crates
+#[test]
+fn test_analyze_shared_dependency() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "analyze", "tree-left", "tree-right"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("tree-shared"))
+        .stdout(predicate::str::contains("tree-left (^1), tree-right (^1)"))
+        .stdout(predicate::str::contains("1 shared, 0 conflicting"));
+}
+
+/// Test --color controls ANSI escapes in the check-multiple summary
+#[test]
+fn test_check_multiple_color() {
+    let api_url = common::mock_registry();
+    let args = [
+        "--api-url",
+        &api_url,
+        "check-multiple",
+        "serde",
+        "no-such-crate",
+    ];
+
+    let output = crate_checker_cmd()
+        .args(args)
+        .args(["--color", "never"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(!output.stdout.contains(&0x1b), "stdout has ANSI escapes");
+    assert!(!output.stderr.contains(&0x1b), "stderr has ANSI escapes");
+
+    crate_checker_cmd()
+        .args(args)
+        .args(["--color", "always"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\u{1b}[32m✓ serde\u{1b}[0m"))
+        .stdout(predicate::str::contains(
+            "\u{1b}[31m✗ no-such-crate\u{1b}[0m",
+        ));
+
+    // NO_COLOR only affects auto-detection
+    crate_checker_cmd()
+        .env("NO_COLOR", "1")
+        .args(args)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\u{1b}").not());
+}
+
+/// Test the info table shows the declared MSRV
+#[test]
+fn test_info_shows_msrv() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "info", "tokio"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("MSRV"))
+        .stdout(predicate::str::contains("1.70"))
+        .stdout(predicate::str::contains("Edition: 2021"));
+}
+
+/// Test the lock command emits parseable [[package]] entries with resolved versions
+#[test]
+fn test_lock_command_output() {
+    let api_url = common::mock_registry();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "lock", "tokio", "serde"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+
+    let lock: toml::Value =
+        toml::from_str(&String::from_utf8(output.stdout).unwrap()).expect("Output is not TOML");
+    let packages = lock["package"]
+        .as_array()
+        .expect("Missing [[package]] entries");
+    assert_eq!(packages.len(), 2);
+
+    for (package, name) in packages.iter().zip(["serde", "tokio"]) {
+        assert_eq!(package["name"].as_str(), Some(name));
+        assert_eq!(
+            package["version"].as_str(),
+            Some(common::mock_crate(name).unwrap().newest_version())
+        );
+        assert_eq!(
+            package["source"].as_str(),
+            Some("registry+https://github.com/rust-lang/crates.io-index")
+        );
+    }
+}
+
+/// Test info shows a crate's custom max upload size in table and structured output
+#[test]
+fn test_info_max_upload_size() {
+    use axum::{routing::get, Json, Router};
+
+    let serde = common::mock_crate("serde").unwrap();
+    let router = Router::new().route(
+        "/crates/serde",
+        get(move || async move {
+            let mut body = common::crate_json(serde);
+            body["crate"]["max_upload_size"] = serde_json::json!(20_971_520);
+            Json(body)
+        }),
+    );
+    let api_url = common::serve(router);
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "info", "serde"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Max upload size: 20.0 MB"))
+        .stdout(predicate::str::contains("License: MIT OR Apache-2.0"));
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json", "info", "serde"])
+        .output()
+        .expect("Failed to run command");
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(info["max_upload_size"], 20_971_520);
+}
+
+/// Test info with several crates renders one row per crate and a JSON array
+#[test]
+fn test_info_multiple_crates() {
+    let api_url = common::mock_registry();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "info", "serde", "tokio", "bytes"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.starts_with('|'))
+        .skip(1)
+        .collect();
+    assert_eq!(rows.len(), 3);
+    for (row, name) in rows.iter().zip(["serde", "tokio", "bytes"]) {
+        assert!(row.contains(name), "row {:?} should be {}", row, name);
+    }
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["info", "serde", "tokio", "bytes"])
+        .output()
+        .expect("Failed to run command");
+    let infos: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = infos
+        .as_array()
+        .expect("Expected a JSON array")
+        .iter()
+        .map(|info| info["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["serde", "tokio", "bytes"]);
+}
+
+/// Test a tiny bench against the mock reports throughput and latency
+#[test]
+fn test_bench_report() {
+    let (api_url, hits) = common::serve_counted(common::registry_router());
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["bench", "--crates", "6", "--concurrency", "2"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["requests"], 6);
+    assert_eq!(report["concurrency"], 2);
+    assert_eq!(report["errors"], 0);
+    assert!(report["requests_per_sec"].as_f64().unwrap() > 0.0);
+    assert!(report["p50_ms"].as_f64().unwrap() > 0.0);
+    assert!(report["p95_ms"].as_f64().unwrap() >= report["p50_ms"].as_f64().unwrap());
+    assert_eq!(hits.count(), 6);
+}
+
+/// Test the stats table draws the download history as a sparkline
+#[test]
+fn test_stats_sparkline() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "stats", "serde"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("Last 90 days: ▁[▁-█]{88}█").unwrap());
+}
+
+/// Test stats reports a rising trend for a steadily growing download history
+#[test]
+fn test_stats_trend_rising() {
+    let api_url = common::mock_registry();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json", "stats", "serde"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["trend"]["direction"], "rising");
+    assert!(stats["trend"]["change_percent"].as_f64().unwrap() > 0.0);
+    assert!(
+        stats["trend"]["recent_downloads"].as_u64().unwrap()
+            > stats["trend"]["previous_downloads"].as_u64().unwrap()
+    );
+}
+
+/// Test batch --resolve --dry-run prints the plan with requirements resolved to concrete versions
+#[test]
+fn test_batch_resolve_dry_run() {
+    let (api_url, hits) = common::serve_counted(common::registry_router());
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json", "batch"])
+        .args(["--json", r#"{"serde": "^1", "tokio": "~9"}"#])
+        .args(["--resolve", "--dry-run"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(plan[0]["crate_name"], "serde");
+    assert_eq!(plan[0]["requested"], "^1");
+    assert_eq!(plan[0]["resolved"], "1.0.210");
+    assert_eq!(plan[1]["crate_name"], "tokio");
+    assert!(plan[1]["resolved"].is_null());
+
+    // Dry run only fetches the version lists
+    assert_eq!(hits.count(), 2);
+}
+
+/// Test manifest-diff sorts dependency changes into added, removed and bumped buckets
+#[test]
+fn test_manifest_diff() {
+    let api_url = common::mock_registry();
+    let temp_dir = TempDir::new().unwrap();
+    let old = temp_dir.path().join("old.toml");
+    let new = temp_dir.path().join("new.toml");
+    fs::write(
+        &old,
+        r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+serde = "1.0.0"
+tokio = { version = "1.0", features = ["full"] }
+bytes = "1"
+"#,
+    )
+    .unwrap();
+    fs::write(
+        &new,
+        r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+serde = "1.0.210"
+tokio = { version = "1.0", features = ["rt"] }
+
+[dev-dependencies]
+pin-project-lite = "0.2"
+"#,
+    )
+    .unwrap();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json", "manifest-diff"])
+        .args([&old, &new])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+
+    let diff: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        diff["added"],
+        serde_json::json!([{ "name": "pin-project-lite", "requirement": "0.2" }])
+    );
+    assert_eq!(
+        diff["removed"],
+        serde_json::json!([{ "name": "bytes", "requirement": "1" }])
+    );
+    // 1.0.130 and 1.0.210 lie after 1.0.0; the yanked 1.0.172 is not counted
+    assert_eq!(
+        diff["bumped"],
+        serde_json::json!([{
+            "name": "serde",
+            "old_requirement": "1.0.0",
+            "new_requirement": "1.0.210",
+            "versions_between": 2,
+        }])
+    );
+}
+
+/// Test markdown output renders tables with a separator row and fences non-tabular output
+#[test]
+fn test_markdown_output() {
+    let api_url = common::mock_registry();
+
+    let output = crate_checker_cmd()
+        .args([
+            "--api-url",
+            &api_url,
+            "--format",
+            "markdown",
+            "search",
+            "serde",
+        ])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(
+        lines[0].starts_with("| Name"),
+        "unexpected header: {}",
+        lines[0]
+    );
+    assert!(
+        lines[1].starts_with("|---") && lines[1].chars().all(|c| c == '|' || c == '-'),
+        "missing separator row: {}",
+        lines[1]
+    );
+    assert!(lines[2].contains("serde"));
+
+    crate_checker_cmd()
+        .args([
+            "--api-url",
+            &api_url,
+            "--format",
+            "markdown",
+            "popularity",
+            "serde",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("```json\n{"))
+        .stdout(predicate::str::ends_with("}\n```\n"));
+}
+
+/// Test check-lock --sarif reports yanked and unpublished versions as a SARIF 2.1.0 log
+#[test]
+fn test_check_lock_sarif() {
+    let api_url = common::mock_registry();
+    let temp_dir = TempDir::new().unwrap();
+    let lockfile = temp_dir.path().join("Cargo.lock");
+    fs::write(
+        &lockfile,
+        r#"version = 3
+
+[[package]]
+name = "my-app"
+version = "0.1.0"
+
+[[package]]
+name = "serde"
+version = "1.0.172"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "tokio"
+version = "1.40.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "bytes"
+version = "9.9.9"
+source = "sparse+https://index.crates.io/"
+"#,
+    )
+    .unwrap();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "check-lock", "--sarif"])
+        .arg(&lockfile)
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+
+    let sarif: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Output is not JSON");
+    assert_eq!(sarif["version"], "2.1.0");
+    let runs = sarif["runs"].as_array().expect("Missing runs");
+    assert_eq!(runs.len(), 1);
+
+    let results = runs[0]["results"].as_array().expect("Missing results");
+    let findings: Vec<(&str, &str)> = results
+        .iter()
+        .map(|result| {
+            (
+                result["ruleId"].as_str().unwrap(),
+                result["locations"][0]["logicalLocations"][0]["fullyQualifiedName"]
+                    .as_str()
+                    .unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        findings,
+        [
+            ("unknown-version", "bytes@9.9.9"),
+            ("yanked-version", "serde@1.0.172")
+        ]
+    );
+}
+
+/// Test --fast reports existence with a single request per crate and skips versions
+#[test]
+fn test_check_multiple_fast() {
+    let (api_url, hits) = common::serve_counted(common::registry_router());
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args([
+            "check-multiple",
+            "--fast",
+            "serde",
+            "tokio",
+            "no-such-crate",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""existing": 2"#))
+        .stdout(predicate::str::contains(r#""missing": 1"#))
+        .stdout(predicate::str::contains(r#""version": "—""#))
+        .stdout(predicate::str::contains("1.0.210").not());
+
+    assert_eq!(hits.count(), 3);
+}
+
+/// Test --compact-summary prints exactly one summary line regardless of format
+#[test]
+fn test_check_multiple_compact_summary() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["check-multiple", "--compact-summary"])
+        .args(["serde", "missing-one", "tokio", "missing-two"])
+        .assert()
+        .success()
+        .stdout("2/4 exist (2 missing: missing-one, missing-two)\n");
+}
+
+/// Test the compact format prints a pipe-delimited line for check-multiple and batch
+#[test]
+fn test_compact_format_line() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "compact"])
+        .args(["check-multiple", "serde", "unknown-crate"])
+        .assert()
+        .success()
+        .stdout("serde=1.0.210 OK | unknown-crate MISSING | total=2 ok=1 fail=1\n");
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "compact", "batch"])
+        .args(["--json", r#"{"serde": "1.0.0", "tokio": "9.9.9"}"#])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("serde=1.0.0 OK"))
+        .stdout(predicate::str::contains("tokio@9.9.9 MISSING"))
+        .stdout(predicate::str::contains("| total=2 ok=1 fail=1"));
+}
+
+/// Test jsonl batch output is one parseable JSON object per crate
+#[test]
+fn test_jsonl_batch_output() {
+    let api_url = common::mock_registry();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "jsonl", "batch"])
+        .args([
+            "--json",
+            r#"{"crates": ["serde", "tokio", "bytes", "unknown-crate"]}"#,
+        ])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4);
+    for line in lines {
+        let result: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(result["crate_name"].is_string());
+    }
+}
+
+/// Test jsonl batch output writes each line as soon as its check completes
+#[test]
+fn test_jsonl_batch_output_streams() {
+    use axum::{extract::Request, middleware::Next};
+    use std::io::BufRead;
+    use std::process::{Command as StdCommand, Stdio};
+    use std::time::{Duration, Instant};
+
+    let router = common::registry_router().layer(axum::middleware::from_fn(
+        |req: Request, next: Next| async move {
+            if req.uri().path().starts_with("/crates/tokio") {
+                tokio::time::sleep(Duration::from_secs(2)).await;
+            }
+            next.run(req).await
+        },
+    ));
+    let api_url = common::serve(router);
+
+    let start = Instant::now();
+    let mut child = StdCommand::new(env!("CARGO_BIN_EXE_crate-checker"))
+        .env_remove("GITHUB_ACTIONS")
+        .args(["--api-url", &api_url, "--format", "jsonl", "batch"])
+        .args(["--json", r#"{"crates": ["serde", "tokio"]}"#])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to run command");
+    let mut lines = std::io::BufReader::new(child.stdout.take().unwrap()).lines();
+
+    let first: serde_json::Value = serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap();
+    assert_eq!(first["crate_name"], "serde");
+    // The slow crate is still being checked when the first line arrives
+    assert!(start.elapsed() < Duration::from_millis(1500));
+
+    let second: serde_json::Value = serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap();
+    assert_eq!(second["crate_name"], "tokio");
+    assert!(child.wait().unwrap().success());
+}
+
+/// Test an offline batch reports latest versions from a fixture cargo registry index
+#[test]
+fn test_offline_batch_reads_local_index() {
+    let cargo_home = TempDir::new().unwrap();
+    let entry = cargo_home
+        .path()
+        .join("registry/index/index.crates.io-6f17d22bba15001f/se/rd/serde");
+    fs::create_dir_all(entry.parent().unwrap()).unwrap();
+    fs::write(
+        &entry,
+        "{\"name\":\"serde\",\"vers\":\"1.0.0\",\"yanked\":false}\n\
+         {\"name\":\"serde\",\"vers\":\"1.0.210\",\"yanked\":false}\n",
+    )
+    .unwrap();
+
+    // Nothing listens on the API URL, so any request would fail the check
+    let output = crate_checker_cmd()
+        .env("CARGO_HOME", cargo_home.path())
+        .args([
+            "--api-url",
+            "http://127.0.0.1:1",
+            "--offline",
+            "--format",
+            "json",
+        ])
+        .args(["batch", "--json", r#"{"crates": ["serde", "tokio"]}"#])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let batch: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let results = batch["results"].as_array().unwrap();
+    assert_eq!(results[0]["crate_name"], "serde");
+    assert_eq!(results[0]["outcome"], "exists");
+    assert_eq!(results[0]["latest_version"], "1.0.210");
+    assert_eq!(results[1]["crate_name"], "tokio");
+    assert_eq!(results[1]["outcome"], "missing");
+    assert_eq!(batch["failed"], 0);
+}
+
+/// Test health reports a running server and fails for unhealthy or unreachable ones
+#[test]
+fn test_health() {
+    let url = common::health_server("healthy");
+    crate_checker_cmd()
+        .args(["health", "--url", &url])
+        .assert()
+        .success()
+        .stdout(format!("{}: healthy (version 1.0.0, up 2m 5s)\n", url));
+
+    let output = crate_checker_cmd()
+        .args(["--format", "json", "health", "--url", &url])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let health: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(health["uptime_seconds"], 125);
+
+    crate_checker_cmd()
+        .args(["health", "--url", &common::health_server("degraded")])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("degraded"));
+
+    // Nothing listens on the discard port
+    crate_checker_cmd()
+        .args(["--timeout", "2s", "health", "--url", "http://127.0.0.1:9"])
+        .assert()
+        .failure();
+}
+
+/// Test check --status reports partially yanked crates with their yanked versions
+#[test]
+fn test_check_status() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "check", "serde", "--status"])
+        .assert()
+        .success()
+        .stdout("serde: partially_yanked\nYanked versions: 1.0.172\n");
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["check", "tokio", "--status"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["crate"], "tokio");
+    assert_eq!(report["status"], "exists");
+    assert_eq!(report["yanked_versions"], serde_json::json!([]));
+
+    crate_checker_cmd()
+        .args([
+            "--api-url",
+            &api_url,
+            "check",
+            "not-a-real-crate",
+            "--status",
+        ])
+        .assert()
+        .failure()
+        .stdout("not-a-real-crate: not_found\n");
+}
+
+/// Test available answers yes/no with a matching exit code
+#[test]
+fn test_available() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "available", "brand-new-name"])
+        .assert()
+        .success()
+        .stdout("brand-new-name: yes, the name is available\n");
+
+    // Taken through the hyphen/underscore equivalence with pin-project-lite
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "available", "pin_project_lite"])
+        .assert()
+        .failure()
+        .stdout("pin_project_lite: no, the name is taken\n");
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["available", "not a name"])
+        .output()
+        .expect("Failed to run command");
+    assert!(!output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result["available"], false);
+    assert!(result["reason"].is_string());
+}
+
+/// Test check --version accepts release channel specifiers
+#[test]
+fn test_check_version_channels() {
+    let api_url = common::mock_registry();
+
+    for (channel, expected) in [
+        ("latest-stable", "1.0.210"),
+        ("latest-prerelease", "2.0.0-alpha.1"),
+        ("latest-0", "0.9.15"),
+    ] {
+        crate_checker_cmd()
+            .args(["--api-url", &api_url, "--format", "json"])
+            .args(["check", "serde", "--version", channel])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(format!(
+                r#""resolved_version": "{}""#,
+                expected
+            )));
+    }
+}
+
+/// Test a missing crate produces a GitHub Actions error annotation
+#[test]
+fn test_check_multiple_github_annotations() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--github-annotations"])
+        .args(["check-multiple", "serde", "no-such-crate"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "::error::Crate 'no-such-crate' does not exist on crates.io",
+        ))
+        .stderr(predicate::str::contains("::error::Crate 'serde'").not())
+        .stdout(predicate::str::contains("::error::").not());
+
+    // Auto-enabled inside GitHub Actions
+    crate_checker_cmd()
+        .env("GITHUB_ACTIONS", "true")
+        .args(["--api-url", &api_url, "check-multiple", "no-such-crate"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("::error::Crate 'no-such-crate'"));
+
+    // Annotations do not break machine-readable output
+    let output = crate_checker_cmd()
+        .env("GITHUB_ACTIONS", "true")
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["check-multiple", "serde", "no-such-crate"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .expect("stdout should be JSON even with annotations enabled");
+    assert_eq!(parsed["summary"]["missing"], 1);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("::error::Crate 'no-such-crate'"));
+
+    // Crates with every version yanked are flagged
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "--github-annotations"])
+        .args(["check-multiple", "abandoned"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "::warning::Crate 'abandoned' has every version yanked",
+        ));
+}
+
+/// Test outdated annotates outdated, yanked and unresolvable dependencies
+#[test]
+fn test_outdated_github_annotations() {
+    let api_url = common::mock_registry();
+    let temp_dir = TempDir::new().unwrap();
+    let manifest = temp_dir.path().join("Cargo.toml");
+    fs::write(
+        &manifest,
+        r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+serde = "1.0.210"
+tokio = "0.3"
+abandoned = "0.2"
+no-such-crate = "1.0"
+"#,
+    )
+    .unwrap();
+
+    let output = crate_checker_cmd()
+        .env("GITHUB_ACTIONS", "true")
+        .args(["--api-url", &api_url, "--format", "json", "outdated"])
+        .arg("--manifest")
+        .arg(&manifest)
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    serde_json::from_slice::<serde_json::Value>(&output.stdout)
+        .expect("stdout should be JSON even with annotations enabled");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let annotations: Vec<&str> = stderr.lines().filter(|l| l.starts_with("::")).collect();
+    assert_eq!(annotations.len(), 3, "{}", stderr);
+    assert!(annotations
+        .iter()
+        .any(|l| l.starts_with("::warning::Dependency 'tokio' (0.3) is outdated")));
+    assert!(annotations.contains(&"::warning::Crate 'abandoned' has every version yanked"));
+    assert!(annotations
+        .iter()
+        .any(|l| l.starts_with("::error::Failed to check dependency 'no-such-crate'")));
+
+    // Nothing is annotated unless asked
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "outdated"])
+        .arg("--manifest")
+        .arg(&manifest)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("::").not());
+}
+
+/// Test rdeps lists dependents with the total dependent count
+#[test]
+fn test_rdeps_command() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "rdeps", "bytes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Dependents of bytes: 1"))
+        .stdout(predicate::str::contains("tokio"));
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json", "rdeps", "bytes"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["total"], 1);
+    assert_eq!(report["dependents"][0]["name"], "tokio");
+    assert_eq!(report["dependents"][0]["newest_version"], "1.40.0");
+}
+
+/// Test features lists the default feature first, and resolves "latest"
+#[test]
+fn test_features_command() {
+    let api_url = common::mock_registry();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "features", "tokio"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Features of tokio 1.40.0: 4"));
+    let default = stdout.find("default").unwrap();
+    assert!(default < stdout.find("bytes").unwrap());
+    assert!(default < stdout.find("std").unwrap());
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json"])
+        .args(["features", "serde", "--version", "1.0.0"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["version"], "1.0.0");
+    assert_eq!(report["features"]["default"][0], "std");
+}
+
+/// Test tree prints an indented dependency tree, marking crates already expanded above
+#[test]
+fn test_tree_command() {
+    let api_url = common::mock_registry();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "tree", "tree-root", "--depth", "2"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "tree-root v1.0.0\n\
+         ├── tree-left v1.1.0\n\
+         │   └── tree-shared v1.0.0\n\
+         └── tree-right v1.0.0\n    \
+             ├── tree-shared v1.0.0\n    \
+             └── tree-root v1.0.0 (*)\n"
+    );
+}
+
+/// Test tree --format json nests resolved nodes with each edge's kind, and --runtime-only
+#[test]
+fn test_tree_json_output() {
+    let api_url = common::mock_registry();
+    let tree = |extra: &[&str]| -> serde_json::Value {
+        let output = crate_checker_cmd()
+            .args(["--api-url", &api_url, "--format", "json"])
+            .args(["tree", "buildscript", "--depth", "1"])
+            .args(extra)
+            .output()
+            .expect("Failed to run command");
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    let full = tree(&[]);
+    assert_eq!(full["name"], "buildscript");
+    assert_eq!(full["version"], "0.3.0");
+    let children = full["children"].as_array().unwrap();
+    let edges: Vec<(&str, &str, &str)> = children
+        .iter()
+        .map(|child| {
+            assert_eq!(child["optional"], false);
+            (
+                child["name"].as_str().unwrap(),
+                child["version"].as_str().unwrap(),
+                child["kind"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    // dev-dependencies are never part of the tree
+    assert_eq!(
+        edges,
+        [
+            ("tree-leaf", "0.1.0", "normal"),
+            ("tree-shared", "1.0.0", "build")
+        ]
+    );
+    assert_eq!(children[0]["requirement"], "^0.1");
+
+    let runtime = tree(&["--runtime-only"]);
+    let names: Vec<&str> = runtime["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|child| child["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["tree-leaf"]);
+}
+
+/// Test outdated classifies each dependency and fails only when asked to
+#[test]
+fn test_outdated_command() {
+    let api_url = common::mock_registry();
+    let temp_dir = TempDir::new().unwrap();
+    let manifest = temp_dir.path().join("Cargo.toml");
+    fs::write(
+        &manifest,
+        r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+serde = "1.0.210"
+tokio = { version = "0.3", features = ["full"] }
+bytes = "1.0"
+pin-project-lite = "0.2.0"
+"#,
+    )
+    .unwrap();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json", "outdated"])
+        .arg("--manifest")
+        .arg(&manifest)
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let updates: Vec<(&str, &str)> = results
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| (r["name"].as_str().unwrap(), r["update"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        updates,
+        [
+            ("bytes", "minor"),
+            ("pin-project-lite", "patch"),
+            ("serde", "up-to-date"),
+            ("tokio", "major"),
+        ]
+    );
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "outdated", "--fail-on-outdated"])
+        .arg("--manifest")
+        .arg(&manifest)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("major"));
+
+    fs::write(&manifest, "[dependencies]\nserde = \"1.0.210\"\n").unwrap();
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "outdated", "--fail-on-outdated"])
+        .arg("--manifest")
+        .arg(&manifest)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("up to date"));
+}
+
+/// Test validate reports manifest dependencies missing from crates.io
+#[test]
+fn test_validate_command() {
+    let api_url = common::mock_registry();
+    let temp_dir = TempDir::new().unwrap();
+    let manifest = temp_dir.path().join("Cargo.toml");
+    fs::write(
+        &manifest,
+        r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+serde = "1.0"
+local = { path = "../local" }
+
+[dev-dependencies]
+sedre = "1.0"
+"#,
+    )
+    .unwrap();
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--format", "json", "validate"])
+        .arg("--manifest")
+        .arg(&manifest)
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let validation: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(validation["checked"], 2);
+    assert_eq!(validation["missing"], serde_json::json!(["sedre"]));
+    assert_eq!(validation["failed"], serde_json::json!([]));
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "validate", "--fail-on-missing"])
+        .arg("--manifest")
+        .arg(&manifest)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("✗ sedre"));
+
+    fs::write(&manifest, "[dependencies]\nserde = \"1.0\"\n").unwrap();
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "validate", "--fail-on-missing"])
+        .arg("--manifest")
+        .arg(&manifest)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("All 1 dependencies exist"));
+
+    // An unreachable registry checks nothing, which must not pass the gate
+    crate_checker_cmd()
+        .args([
+            "--api-url",
+            "http://127.0.0.1:1",
+            "validate",
+            "--fail-on-missing",
+        ])
+        .arg("--manifest")
+        .arg(&manifest)
+        .assert()
+        .failure();
+}
+
+/// Test `-` reads newline-separated crate names from stdin, skipping blanks and comments
+#[test]
+fn test_crate_names_from_stdin() {
+    let api_url = common::mock_registry();
+    let names = "# from cargo metadata\nserde\n\n  tokio  # runtime\nnot-a-real-crate\n";
+
+    crate_checker_cmd()
+        .args([
+            "--api-url",
+            &api_url,
+            "check-multiple",
+            "-",
+            "--compact-summary",
+        ])
+        .write_stdin(names)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "2/3 exist (1 missing: not-a-real-crate)",
+        ));
+
+    let output = crate_checker_cmd()
+        .args([
+            "--api-url",
+            &api_url,
+            "--format",
+            "json",
+            "batch",
+            "--file",
+            "-",
+        ])
+        .write_stdin(names)
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let checked: Vec<&str> = result["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["crate_name"].as_str().unwrap())
+        .collect();
+    assert_eq!(checked, ["serde", "tokio", "not-a-real-crate"]);
+}
+
+/// Test watch --once polls a single time and reports the current versions
+#[test]
+fn test_watch_once() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args([
+            "--api-url",
+            &api_url,
+            "watch",
+            "serde",
+            "tokio",
+            "not-a-real-crate",
+            "--once",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("serde: 1.0.210"))
+        .stdout(predicate::str::contains("tokio: 1.40.0"))
+        .stdout(predicate::str::contains("not-a-real-crate").not());
+
+    let output = crate_checker_cmd()
+        .args([
+            "--api-url",
+            &api_url,
+            "--format",
+            "json",
+            "watch",
+            "serde",
+            "--once",
+        ])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let changes: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(changes[0]["crate_name"], "serde");
+    assert_eq!(changes[0]["new_version"], "1.0.210");
+    assert!(changes[0]["previous_version"].is_null());
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "watch", "serde", "--interval", "0s"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("greater than zero"));
+}
+
+/// Test CARGO_REGISTRY_TOKEN is sent as a bearer token, and no Authorization header without it
+#[test]
+fn test_cargo_registry_token_env() {
+    let (api_url, log) = common::serve_recorded(common::registry_router());
+    let check = || {
+        let mut cmd = crate_checker_cmd();
+        cmd.env_remove("CRATE_CHECKER__CRATES_IO__TOKEN")
+            .env_remove("CRATE_CHECKER__CRATES_IO__REGISTRY_TOKEN")
+            .args(["--api-url", &api_url, "--verbose", "check", "serde"]);
+        cmd
+    };
+
+    check()
+        .env("CARGO_REGISTRY_TOKEN", "cio-bearer")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("cio-bearer").not());
+    let sent = log.headers();
+    assert!(!sent.is_empty());
+    assert!(sent
+        .iter()
+        .all(|headers| headers["authorization"] == "Bearer cio-bearer"));
+
+    let before = sent.len();
+    check()
+        .env_remove("CARGO_REGISTRY_TOKEN")
+        .assert()
+        .success();
+    let sent = log.headers();
+    assert!(sent.len() > before);
+    assert!(sent[before..]
+        .iter()
+        .all(|headers| !headers.contains_key("authorization")));
+}
+
+/// Test --raw-numbers prints exact download counts instead of abbreviating them
+#[test]
+fn test_raw_numbers() {
+    let api_url = common::mock_registry();
+
+    crate_checker_cmd()
+        .args(["--api-url", &api_url, "info", "serde"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("300.0M"));
+
+    let output = crate_checker_cmd()
+        .args(["--api-url", &api_url, "--raw-numbers", "info", "serde"])
+        .output()
+        .expect("Failed to run command");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("300.0M"), "{}", stdout);
+    // The Downloads column holds the exact, all-digit count
+    let header = stdout.lines().find(|l| l.contains("Downloads")).unwrap();
+    let column = header
+        .split('|')
+        .position(|c| c.trim() == "Downloads")
+        .unwrap();
+    let row = stdout.lines().find(|l| l.contains("serde")).unwrap();
+    let downloads = row.split('|').nth(column).unwrap().trim();
+    assert_eq!(downloads, "300000000");
+    assert!(downloads.chars().all(|c| c.is_ascii_digit()));
+}
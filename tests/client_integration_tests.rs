@@ -1,5 +1,13 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+mod common;
+
 use crate_checker::client::CrateClient;
-use crate_checker::types::{BatchOperation, BatchTarget, CrateStatus};
+use crate_checker::config::CacheConfig;
+use crate_checker::types::{
+    BatchOperation, BatchTarget, CheckOutcome, ClientStats, CrateInfo, CrateSearchResult,
+    CrateStatus, DepNode, Dependency, DependencyReport, EcosystemReport, PopularityLabel,
+    SearchParams, SearchSort, Version,
+};
 use std::collections::HashMap;
 use std::time::Duration;
 
@@ -318,11 +326,96 @@ async fn test_process_crate_version_map() {
 
     assert_eq!(result.total_processed, 3);
     assert_eq!(result.successful, 2);
-    assert_eq!(result.failed, 1);
+    assert_eq!(result.missing, 1);
+    assert_eq!(result.failed, 0);
     assert_eq!(result.results.len(), 3);
     assert!(result.processing_time_ms > 0);
 }
 
+/// Test the progress callback fires once per crate, from the calling task
+#[tokio::test]
+async fn test_process_crate_version_map_with_progress() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .max_concurrent(3)
+        .build()
+        .expect("Failed to build client");
+
+    let input: HashMap<String, String> = [
+        ("serde", "1.0.0"),
+        ("tokio", "latest"),
+        ("bytes", "latest"),
+        ("serde_derive", "latest"),
+        ("missing-crate", "1.0.0"),
+    ]
+    .into_iter()
+    .map(|(name, version)| (name.to_string(), version.to_string()))
+    .collect();
+
+    // A RefCell is neither Sync nor shared across tasks
+    let calls = std::cell::RefCell::new(Vec::new());
+    let result = client
+        .process_crate_version_map_with_progress(input, |processed, total| {
+            calls.borrow_mut().push((processed, total));
+        })
+        .await
+        .expect("Request failed");
+
+    assert_eq!(calls.into_inner(), [(1, 5), (2, 5), (3, 5), (4, 5), (5, 5)]);
+    assert_eq!(result.total_processed, 5);
+    assert_eq!(result.successful, 4);
+    assert_eq!(result.missing, 1);
+    assert_eq!(result.failed, 0);
+}
+
+/// Test a failed check is reported as an error, not as a missing crate
+#[tokio::test]
+async fn test_check_outcome_distinguishes_errors() {
+    use axum::{http::StatusCode, routing::get};
+
+    let router = common::registry_router().route(
+        "/crates/flaky",
+        get(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+    );
+    let client = CrateClient::builder()
+        .base_url(common::serve(router))
+        .retry_attempts(0)
+        .build()
+        .expect("Failed to build client");
+
+    let result = client
+        .process_crate_list(vec![
+            "serde".to_string(),
+            "missing-crate".to_string(),
+            "flaky".to_string(),
+        ])
+        .await
+        .expect("Request failed");
+    let outcomes: Vec<CheckOutcome> = result.iter().map(|r| r.outcome).collect();
+    assert_eq!(
+        outcomes,
+        [
+            CheckOutcome::Exists,
+            CheckOutcome::Missing,
+            CheckOutcome::Error
+        ]
+    );
+    assert!(result[1].error.is_none());
+    assert!(result[2].error.is_some());
+
+    let input: HashMap<String, String> = [("missing-crate", "1.0.0"), ("flaky", "1.0.0")]
+        .into_iter()
+        .map(|(name, version)| (name.to_string(), version.to_string()))
+        .collect();
+    let batch = client
+        .process_crate_version_map(input)
+        .await
+        .expect("Request failed");
+    assert_eq!(batch.successful, 0);
+    assert_eq!(batch.missing, 1);
+    assert_eq!(batch.failed, 1);
+}
+
 /// Test processing batch operations
 #[tokio::test]
 async fn test_process_batch_operations() {
@@ -345,7 +438,7 @@ async fn test_process_batch_operations() {
     ];
 
     let response = client
-        .process_batch_operations(operations)
+        .process_batch_operations(operations, 1)
         .await
         .expect("Request failed");
 
@@ -355,6 +448,108 @@ async fn test_process_batch_operations() {
     assert!(response.result.results.len() >= 3); // At least 3 results from the operations
 }
 
+/// Test `info` and `deps` operations attach what they fetch, and unknown operations fail
+#[tokio::test]
+async fn test_process_batch_operation_kinds() {
+    let client = CrateClient::builder()
+        .base_url(common::serve(common::registry_router()))
+        .build()
+        .expect("Failed to build client");
+
+    let operations = vec![
+        BatchOperation {
+            target: BatchTarget::Single {
+                crate_name: "serde".to_string(),
+                version: None,
+            },
+            operation: "info".to_string(),
+        },
+        BatchOperation {
+            target: BatchTarget::Single {
+                crate_name: "serde".to_string(),
+                version: None,
+            },
+            operation: "deps".to_string(),
+        },
+    ];
+    let result = client
+        .process_batch_operations(operations, 2)
+        .await
+        .expect("Request failed")
+        .result;
+    assert_eq!(result.failed, 0);
+
+    let info = &result.results[0];
+    assert_eq!(info.info.as_ref().map(|i| i.name.as_str()), Some("serde"));
+    assert!(info.dependencies.is_none());
+
+    let deps = &result.results[1];
+    let names: Vec<&str> = deps
+        .dependencies
+        .as_ref()
+        .expect("deps operation should attach dependencies")
+        .iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(names, ["serde_derive"]);
+
+    let unknown = vec![BatchOperation {
+        target: BatchTarget::Single {
+            crate_name: "serde".to_string(),
+            version: None,
+        },
+        operation: "frobnicate".to_string(),
+    }];
+    let err = client
+        .process_batch_operations(unknown, 1)
+        .await
+        .expect_err("unknown operation should be rejected");
+    assert!(err
+        .to_string()
+        .contains("Unknown batch operation 'frobnicate'"));
+}
+
+/// Test a multi-crate operation is checked concurrently and counted per crate
+#[tokio::test]
+async fn test_process_batch_operations_concurrent() {
+    let router = common::delay_each(common::registry_router(), Duration::from_millis(200));
+    let client = CrateClient::builder()
+        .base_url(common::serve(router))
+        .build()
+        .expect("Failed to build client");
+
+    let crates: Vec<String> = ["serde", "tokio", "serde_derive", "bytes"]
+        .into_iter()
+        .chain(["missing-a", "missing-b", "missing-c", "missing-d"])
+        .map(String::from)
+        .collect();
+    let operations = vec![BatchOperation {
+        target: BatchTarget::Multiple {
+            crates: crates.clone(),
+        },
+        operation: "batch_check".to_string(),
+    }];
+
+    let start = std::time::Instant::now();
+    let response = client
+        .process_batch_operations(operations, 8)
+        .await
+        .expect("Request failed");
+    // Sequentially the existence checks alone would take 8 x 200ms
+    assert!(start.elapsed() < Duration::from_millis(1600));
+
+    let result = response.result;
+    assert_eq!(result.total_processed, 1);
+    assert_eq!(result.total_crates_checked, 8);
+    let checked: Vec<&str> = result
+        .results
+        .iter()
+        .map(|r| r.crate_name.as_str())
+        .collect();
+    assert_eq!(checked, crates);
+    assert_eq!(result.results.iter().filter(|r| r.exists).count(), 4);
+}
+
 /// Test error handling for invalid API responses
 #[tokio::test]
 async fn test_error_handling_invalid_crate_name() {
@@ -379,6 +574,157 @@ async fn test_timeout_behavior() {
     assert!(result.is_err());
 }
 
+/// Test a retry pass recovers a check that failed once, without retrying missing crates
+#[tokio::test]
+async fn test_process_crate_list_with_retry() {
+    use crate_checker::transport::{HttpRequest, HttpResponse, HttpTransport, ReqwestTransport};
+    use futures::future::BoxFuture;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    /// Forwards to the mock registry, except the first request for `bytes` fails
+    #[derive(Debug)]
+    struct FailOnce {
+        inner: ReqwestTransport,
+        failed: AtomicBool,
+        urls: Mutex<Vec<String>>,
+    }
+
+    impl HttpTransport for FailOnce {
+        fn send(&self, request: HttpRequest) -> BoxFuture<'_, crate_checker::Result<HttpResponse>> {
+            self.urls.lock().unwrap().push(request.url.clone());
+            if request.url.ends_with("/crates/bytes") && !self.failed.swap(true, Ordering::SeqCst) {
+                let error = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+                return Box::pin(async move { Err(error.into()) });
+            }
+            self.inner.send(request)
+        }
+    }
+
+    let transport = std::sync::Arc::new(FailOnce {
+        inner: ReqwestTransport::new(reqwest::Client::new()),
+        failed: AtomicBool::new(false),
+        urls: Mutex::new(Vec::new()),
+    });
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .transport(transport.clone())
+        .retry_attempts(0)
+        .build()
+        .expect("Failed to build client");
+
+    let crates = ["serde", "bytes", "missing-crate"]
+        .map(String::from)
+        .to_vec();
+    let retried = client
+        .process_crate_list_with_retry(crates, 3)
+        .await
+        .expect("Request failed");
+
+    assert_eq!(retried.recovered, 1);
+    assert_eq!(retried.retry_passes, 1);
+    let outcomes: Vec<CheckOutcome> = retried.result.results.iter().map(|r| r.outcome).collect();
+    assert_eq!(
+        outcomes,
+        [
+            CheckOutcome::Exists,
+            CheckOutcome::Exists,
+            CheckOutcome::Missing
+        ]
+    );
+    assert_eq!(retried.result.successful, 2);
+    assert_eq!(retried.result.missing, 1);
+    assert_eq!(retried.result.failed, 0);
+
+    let urls = transport.urls.lock().unwrap();
+    let checks = |suffix: &str| urls.iter().filter(|url| url.ends_with(suffix)).count();
+    // Missing crates are a definite answer and are not re-checked
+    assert!(checks("/crates/bytes") > 1);
+    assert_eq!(checks("/crates/missing-crate"), 1);
+}
+
+/// Test a fetch failing after the existence check errors the check instead of passing it
+#[tokio::test]
+async fn test_failed_follow_up_fetch_is_an_error() {
+    use crate_checker::transport::{HttpRequest, HttpResponse, HttpTransport, ReqwestTransport};
+    use futures::future::BoxFuture;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Forwards to the mock registry, except the second request for `bytes` fails
+    #[derive(Debug)]
+    struct FailSecond {
+        inner: ReqwestTransport,
+        requests: AtomicUsize,
+    }
+
+    impl HttpTransport for FailSecond {
+        fn send(&self, request: HttpRequest) -> BoxFuture<'_, crate_checker::Result<HttpResponse>> {
+            if request.url.ends_with("/crates/bytes")
+                && self.requests.fetch_add(1, Ordering::SeqCst) == 1
+            {
+                let error = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+                return Box::pin(async move { Err(error.into()) });
+            }
+            self.inner.send(request)
+        }
+    }
+
+    let api_url = common::mock_registry();
+    let client = || {
+        CrateClient::builder()
+            .base_url(&api_url)
+            .transport(std::sync::Arc::new(FailSecond {
+                inner: ReqwestTransport::new(reqwest::Client::new()),
+                requests: AtomicUsize::new(0),
+            }))
+            .retry_attempts(0)
+            .build()
+            .expect("Failed to build client")
+    };
+    let checks = vec!["bytes".to_string()];
+
+    let results = client()
+        .process_crate_list(checks.clone())
+        .await
+        .expect("Request failed");
+    assert_eq!(results[0].outcome, CheckOutcome::Error);
+    assert!(results[0].error.is_some());
+    assert_eq!(results[0].latest_version, None);
+    assert_eq!(results[0].version_exists, None);
+
+    // The errored check is picked up by a retry pass
+    let retried = client()
+        .process_crate_list_with_retry(checks, 1)
+        .await
+        .expect("Request failed");
+    assert_eq!(retried.recovered, 1);
+    let result = &retried.result.results[0];
+    assert_eq!(result.outcome, CheckOutcome::Exists);
+    assert_eq!(
+        result.latest_version.as_deref(),
+        Some(common::mock_crate("bytes").unwrap().newest_version())
+    );
+}
+
+/// Test an empty user agent is rejected and the default names the crate version
+#[test]
+fn test_user_agent_validation() {
+    for agent in ["", "   "] {
+        let err = CrateClient::builder()
+            .user_agent(agent)
+            .build()
+            .expect_err("empty user agent should be rejected");
+        assert!(matches!(
+            err,
+            crate_checker::CrateCheckerError::ValidationError(_)
+        ));
+    }
+
+    assert!(crate_checker::DEFAULT_USER_AGENT
+        .starts_with(&format!("crate-checker/{} (", env!("CARGO_PKG_VERSION"))));
+    assert!(CrateClient::builder().build().is_ok());
+}
+
 /// Test client with custom base URL
 #[tokio::test]
 async fn test_custom_base_url() {
@@ -556,3 +902,1353 @@ async fn test_large_batch_processing() {
         "Expected at least 8 popular crates to exist"
     );
 }
+
+fn report_crate(name: &str, downloads: u64, created_at: DateTime<Utc>) -> CrateInfo {
+    CrateInfo {
+        name: name.to_string(),
+        description: None,
+        newest_version: "1.0.0".to_string(),
+        downloads,
+        created_at,
+        updated_at: created_at,
+        homepage: None,
+        repository: None,
+        documentation: None,
+        keywords: Vec::new(),
+        categories: Vec::new(),
+        max_upload_size: None,
+        license: None,
+        yanked: None,
+        links: None,
+        msrv: None,
+        edition: None,
+    }
+}
+
+fn report_version(num: &str, yanked: bool, license: &str) -> Version {
+    Version {
+        num: num.to_string(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        downloads: 0,
+        yanked,
+        id: None,
+        crate_size: None,
+        published_by: None,
+        audit_actions: None,
+        license: Some(license.to_string()),
+        features: None,
+        links: None,
+        rust_version: None,
+        edition: None,
+    }
+}
+
+/// Test ecosystem report aggregates over a small fixed set of crates
+#[test]
+fn test_ecosystem_report_aggregates() {
+    let now = Utc::now();
+    let crates = vec![
+        (
+            report_crate("alpha", 1_000, now - ChronoDuration::days(100)),
+            vec![
+                report_version("0.9.0", true, "MIT"),
+                report_version("1.0.0", false, "MIT"),
+            ],
+        ),
+        (
+            report_crate("beta", 500, now - ChronoDuration::days(300)),
+            vec![
+                report_version("0.1.0", false, "Apache-2.0"),
+                report_version("1.0.0", false, "MIT OR Apache-2.0"),
+            ],
+        ),
+        (
+            report_crate("gamma", 250, now - ChronoDuration::days(200)),
+            vec![
+                report_version("0.5.0", true, "MIT"),
+                report_version("0.6.0", true, "MIT"),
+                report_version("1.0.0", false, "MIT"),
+                report_version("1.0.1", false, "MIT"),
+            ],
+        ),
+    ];
+
+    let report = EcosystemReport::from_crates(&crates, vec!["missing".to_string()], now);
+
+    assert_eq!(report.crate_count, 3);
+    assert_eq!(report.total_downloads, 1_750);
+    assert!((report.average_age_days - 200.0).abs() < f64::EPSILON);
+    assert_eq!(report.total_versions, 8);
+    assert_eq!(report.yanked_versions, 3);
+    assert!((report.yank_rate - 0.375).abs() < f64::EPSILON);
+    assert_eq!(report.license_distribution.get("MIT"), Some(&2));
+    assert_eq!(
+        report.license_distribution.get("MIT OR Apache-2.0"),
+        Some(&1)
+    );
+    assert_eq!(report.failed, vec!["missing".to_string()]);
+}
+
+/// Test the ecosystem report fetches crates concurrently from the registry and totals them
+#[tokio::test]
+async fn test_generate_report_from_registry() {
+    let (url, gauge) = common::serve_gauged(common::delay_each(
+        common::registry_router(),
+        Duration::from_millis(50),
+    ));
+    let client = CrateClient::builder()
+        .base_url(url)
+        .max_concurrent(4)
+        .build()
+        .expect("Failed to build client");
+
+    let names = ["serde", "tokio", "abandoned"];
+    let report = client
+        .generate_report(
+            names
+                .iter()
+                .copied()
+                .chain(["no-such-crate"])
+                .map(String::from)
+                .collect(),
+        )
+        .await
+        .expect("Request failed");
+
+    let fixtures: Vec<_> = names
+        .iter()
+        .map(|name| common::mock_crate(name).unwrap())
+        .collect();
+    assert_eq!(report.crate_count, 3);
+    assert_eq!(
+        report.total_downloads,
+        fixtures.iter().map(|c| c.downloads).sum::<u64>()
+    );
+    assert_eq!(
+        report.total_versions,
+        fixtures.iter().map(|c| c.versions.len()).sum::<usize>()
+    );
+    assert_eq!(
+        report.yanked_versions,
+        fixtures
+            .iter()
+            .flat_map(|c| c.versions)
+            .filter(|(_, yanked)| *yanked)
+            .count()
+    );
+    let reported: Vec<&str> = report.crates.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(reported, names);
+    assert_eq!(report.failed, vec!["no-such-crate".to_string()]);
+    assert!(gauge.peak() > 1, "peak concurrency {}", gauge.peak());
+}
+
+/// Test download history is parsed in date order and cached within the TTL
+#[tokio::test]
+async fn test_download_history_is_cached() {
+    let (base_url, hits) = common::serve_counted(common::registry_router());
+    let client = CrateClient::builder()
+        .base_url(&base_url)
+        .cache(CacheConfig::default())
+        .history_cache_ttl(Duration::from_secs(60))
+        .build()
+        .expect("Failed to build client");
+
+    let history = client
+        .get_download_history("serde")
+        .await
+        .expect("Request failed");
+    assert_eq!(history.len(), 90);
+    assert!(history.windows(2).all(|w| w[0].0 < w[1].0));
+    // The last day folds in the extra_downloads bucket
+    assert_eq!(history.last().unwrap().1, 100 + 89 * 10 + 5);
+
+    let again = client
+        .get_download_history("serde")
+        .await
+        .expect("Request failed");
+    assert_eq!(again, history);
+    assert_eq!(hits.count(), 1);
+
+    // A zero history TTL leaves histories out of the cache
+    let client = CrateClient::builder()
+        .base_url(&base_url)
+        .cache(CacheConfig::default())
+        .history_cache_ttl(Duration::ZERO)
+        .build()
+        .expect("Failed to build client");
+    for _ in 0..2 {
+        client
+            .get_download_history("serde")
+            .await
+            .expect("Request failed");
+    }
+    assert_eq!(hits.count(), 3);
+}
+
+/// Test a transient 503 is retried once and counted in the client stats
+#[tokio::test]
+async fn test_retry_is_counted_in_stats() {
+    let router = common::fail_first(
+        common::registry_router(),
+        1,
+        axum::http::StatusCode::SERVICE_UNAVAILABLE,
+    );
+    let client = CrateClient::builder()
+        .base_url(common::serve(router))
+        .build()
+        .expect("Failed to build client");
+
+    let info = client
+        .get_crate_info("serde")
+        .await
+        .expect("Request failed");
+    assert_eq!(info.name, "serde");
+
+    assert_eq!(
+        client.stats(),
+        ClientStats {
+            requests: 1,
+            retries: 1,
+            failures: 0,
+        }
+    );
+}
+
+/// Test the configured retry budget decides whether repeated 503s are survived
+#[tokio::test]
+async fn test_retry_attempts_is_honored() {
+    let flaky_client = |retry_attempts| {
+        let router = common::fail_first(
+            common::registry_router(),
+            2,
+            axum::http::StatusCode::SERVICE_UNAVAILABLE,
+        );
+        CrateClient::builder()
+            .base_url(common::serve(router))
+            .retry_attempts(retry_attempts)
+            .build()
+            .expect("Failed to build client")
+    };
+
+    let client = flaky_client(2);
+    let info = client
+        .get_crate_info("serde")
+        .await
+        .expect("Request failed");
+    assert_eq!(info.name, "serde");
+    assert_eq!(client.stats().retries, 2);
+
+    let client = flaky_client(1);
+    let err = client.get_crate_info("serde").await.unwrap_err();
+    assert!(err.is_recoverable());
+    assert_eq!(client.stats().failures, 1);
+}
+
+/// Test a 429 waits for its Retry-After delay before retrying and exposes it on the error
+#[tokio::test]
+async fn test_retry_after_is_honored() {
+    let throttled_client = |retry_attempts| {
+        let router = common::throttle_first(common::registry_router(), 1, "2");
+        CrateClient::builder()
+            .base_url(common::serve(router))
+            .retry_attempts(retry_attempts)
+            .build()
+            .expect("Failed to build client")
+    };
+
+    let client = throttled_client(1);
+    let start = std::time::Instant::now();
+    let info = client
+        .get_crate_info("serde")
+        .await
+        .expect("Request failed");
+    assert_eq!(info.name, "serde");
+    assert!(start.elapsed() >= Duration::from_secs(2));
+    assert_eq!(client.stats().retries, 1);
+
+    let client = throttled_client(0);
+    match client.get_crate_info("serde").await {
+        Err(crate_checker::CrateCheckerError::RateLimitExceeded { retry_after }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(2)));
+        }
+        other => panic!("expected a rate limit error, got {:?}", other),
+    }
+}
+
+/// Test a Retry-After longer than the retry delay cap fails fast instead of waiting
+#[tokio::test]
+async fn test_oversized_retry_after_fails_fast() {
+    let router = common::throttle_first(common::registry_router(), 1, "86400");
+    let client = CrateClient::builder()
+        .base_url(common::serve(router))
+        .retry_attempts(3)
+        .build()
+        .expect("Failed to build client");
+
+    let start = std::time::Instant::now();
+    match client.get_crate_info("serde").await {
+        Err(crate_checker::CrateCheckerError::RateLimitExceeded { retry_after }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(86400)));
+        }
+        other => panic!("expected a rate limit error, got {:?}", other),
+    }
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(client.stats().retries, 0);
+    assert_eq!(client.stats().failures, 1);
+}
+
+/// Test a mega-crate lands at a high popularity percentile and a small one does not
+#[tokio::test]
+async fn test_popularity_percentile() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .build()
+        .expect("Failed to build client");
+
+    let serde = client.popularity("serde").await.expect("Request failed");
+    assert_eq!(serde.downloads, 300_000_000);
+    assert!(serde.percentile > 99.0);
+    assert_eq!(serde.label, PopularityLabel::Ubiquitous);
+
+    let abandoned = client
+        .popularity("abandoned")
+        .await
+        .expect("Request failed");
+    assert!(abandoned.percentile < 50.0);
+    assert_eq!(abandoned.label, PopularityLabel::Niche);
+}
+
+/// Test strict mode rejects an unexpected upstream field that lenient mode ignores
+#[tokio::test]
+async fn test_strict_mode_rejects_unknown_fields() {
+    use axum::{routing::get, Json, Router};
+    use crate_checker::CrateCheckerError;
+    use serde_json::json;
+
+    let body = |extra: bool| {
+        let mut body = json!({
+            "crate": {
+                "name": "serde",
+                "description": "A serialization framework",
+                "newest_version": "1.0.210",
+                "downloads": 300_000_000u64,
+                "recent_downloads": 30_000_000u64,
+                "created_at": "2015-01-01T00:00:00Z",
+                "updated_at": "2024-01-01T00:00:00Z",
+                "homepage": null,
+                "repository": null,
+                "documentation": null,
+                "max_upload_size": null,
+            },
+            "versions": [],
+            "keywords": [],
+            "categories": [],
+        });
+        if extra {
+            body["crate"]["trustpub_only"] = json!(false);
+        }
+        body
+    };
+    let router = Router::new()
+        .route(
+            "/crates/serde",
+            get(move || async move { Json(body(false)) }),
+        )
+        .route(
+            "/crates/drifted",
+            get(move || async move { Json(body(true)) }),
+        );
+    let base_url = common::serve(router);
+
+    let strict = CrateClient::builder()
+        .base_url(&base_url)
+        .strict(true)
+        .build()
+        .expect("Failed to build client");
+    let lenient = CrateClient::builder()
+        .base_url(&base_url)
+        .build()
+        .expect("Failed to build client");
+
+    assert!(strict.get_crate_info("serde").await.is_ok());
+    assert!(lenient.get_crate_info("drifted").await.is_ok());
+    match strict.get_crate_info("drifted").await {
+        Err(CrateCheckerError::SchemaMismatch(drift)) => {
+            assert_eq!(drift, "unknown field `crate.trustpub_only`");
+        }
+        other => panic!("expected a schema mismatch, got {:?}", other),
+    }
+}
+
+/// Test paginated listings are followed across pages in order, up to the requested cap
+#[tokio::test]
+async fn test_pagination_collects_all_pages() {
+    use axum::{extract::Query, routing::get, Json, Router};
+    use serde_json::json;
+
+    let serde = common::mock_crate("serde").unwrap();
+    let versions = move |Query(params): Query<HashMap<String, String>>| async move {
+        let page = match params.get("page").map(String::as_str) {
+            None | Some("1") => json!({
+                "versions": [common::version_json(serde, 2), common::version_json(serde, 1)],
+                "meta": { "total": 3, "next_page": "?page=2" },
+            }),
+            _ => json!({
+                "versions": [common::version_json(serde, 0)],
+                "meta": { "total": 3, "next_page": null },
+            }),
+        };
+        Json(page)
+    };
+    let search = |Query(params): Query<HashMap<String, String>>| async move {
+        let page: usize = params.get("page").and_then(|p| p.parse().ok()).unwrap_or(1);
+        let crates: Vec<_> = (0..2)
+            .map(|i| {
+                json!({
+                    "name": format!("crate-{}", (page - 1) * 2 + i),
+                    "description": null,
+                    "newest_version": "1.0.0",
+                    "downloads": 1,
+                    "exact_match": false,
+                })
+            })
+            .collect();
+        Json(json!({
+            "crates": crates,
+            "meta": { "total": 100, "next_page": format!("?page={}&q=crate", page + 1) },
+        }))
+    };
+    let router = Router::new()
+        .route("/crates/serde/versions", get(versions))
+        .route("/crates", get(search));
+    let client = CrateClient::builder()
+        .base_url(common::serve(router))
+        .build()
+        .expect("Failed to build client");
+
+    let versions = client
+        .get_all_versions("serde")
+        .await
+        .expect("Request failed");
+    let nums: Vec<&str> = versions.iter().map(|v| v.num.as_str()).collect();
+    assert_eq!(nums, ["1.0.0", "1.0.0-rc.1", "0.9.15"]);
+
+    let results = client
+        .search_crates("crate", Some(5))
+        .await
+        .expect("Request failed");
+    let names: Vec<&str> = results.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(
+        names,
+        ["crate-0", "crate-1", "crate-2", "crate-3", "crate-4"]
+    );
+    assert_eq!(client.stats().requests, 5);
+}
+
+/// Test a `links.versions` URL from the crate response is used for the following versions fetch
+#[tokio::test]
+async fn test_versions_fetch_follows_crate_links() {
+    use axum::{routing::get, Json, Router};
+    use serde_json::json;
+
+    let serde = common::mock_crate("serde").unwrap();
+    let router =
+        Router::new()
+            .route(
+                "/crates/serde",
+                get(move || async move {
+                    let mut body = common::crate_json(serde);
+                    body["crate"]["links"] = json!({
+                        "owner_team": null,
+                        "owner_user": null,
+                        "owners": null,
+                        "reverse_dependencies": null,
+                        "version_downloads": null,
+                        "versions": "/mirror/serde/versions",
+                    });
+                    Json(body)
+                }),
+            )
+            .route(
+                "/mirror/serde/versions",
+                get(move || async move {
+                    Json(json!({ "versions": [common::version_json(serde, 0)] }))
+                }),
+            );
+    let client = CrateClient::builder()
+        .base_url(common::serve(router))
+        .build()
+        .expect("Failed to build client");
+
+    // Without a known link the conventional path is used, which this mock does not serve
+    assert!(client.get_all_versions("serde").await.is_err());
+
+    let info = client
+        .get_crate_info("serde")
+        .await
+        .expect("Request failed");
+    assert!(info.links.is_some());
+
+    let versions = client
+        .get_all_versions("serde")
+        .await
+        .expect("Versions should be fetched from the linked URL");
+    let nums: Vec<&str> = versions.iter().map(|v| v.num.as_str()).collect();
+    assert_eq!(nums, ["0.9.15"]);
+}
+
+/// Test a `links.versions` URL on another host is ignored in favor of the conventional path
+#[tokio::test]
+async fn test_off_origin_links_are_ignored() {
+    use axum::{routing::get, Json};
+    use serde_json::json;
+
+    let (other_host, other_hits) = common::serve_counted(common::registry_router());
+    let serde = common::mock_crate("serde").unwrap();
+    let versions_link = format!("{}/crates/serde/versions", other_host);
+    let router = common::registry_router().route(
+        "/crates/serde",
+        get(move || {
+            let versions_link = versions_link.clone();
+            async move {
+                let mut body = common::crate_json(serde);
+                body["crate"]["links"] = json!({
+                    "owner_team": null,
+                    "owner_user": null,
+                    "owners": null,
+                    "reverse_dependencies": null,
+                    "version_downloads": null,
+                    "versions": versions_link,
+                });
+                Json(body)
+            }
+        }),
+    );
+    let client = CrateClient::builder()
+        .base_url(common::serve(router))
+        .registry_token("secret")
+        .build()
+        .expect("Failed to build client");
+
+    client
+        .get_crate_info("serde")
+        .await
+        .expect("Request failed");
+    let versions = client
+        .get_all_versions("serde")
+        .await
+        .expect("Versions should be fetched from the conventional path");
+    assert_eq!(versions.len(), serde.versions.len());
+    assert_eq!(other_hits.count(), 0);
+}
+
+/// Test a short search timeout only affects search, not other requests
+#[tokio::test]
+async fn test_search_timeout_only_applies_to_search() {
+    use axum::{extract::Request, middleware::Next};
+
+    let router = common::registry_router().layer(axum::middleware::from_fn(
+        |req: Request, next: Next| async move {
+            if req.uri().path() == "/crates" {
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+            next.run(req).await
+        },
+    ));
+    let client = CrateClient::builder()
+        .base_url(common::serve(router))
+        .search_timeout(Duration::from_millis(50))
+        .build()
+        .expect("Failed to build client");
+
+    assert!(client.search_crates("serde", Some(5)).await.is_err());
+    assert!(client.crate_exists("serde").await.expect("Request failed"));
+}
+
+/// Test "Latest", "*" and "latest" all resolve to the newest version
+#[tokio::test]
+async fn test_latest_keyword_synonyms() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .build()
+        .expect("Failed to build client");
+
+    let mut input = HashMap::new();
+    input.insert("serde".to_string(), "Latest".to_string());
+    input.insert("tokio".to_string(), "*".to_string());
+    input.insert("bytes".to_string(), "latest".to_string());
+
+    let result = client
+        .process_crate_version_map(input)
+        .await
+        .expect("Request failed");
+
+    assert_eq!(result.successful, 3);
+    for check in &result.results {
+        let newest = common::mock_crate(&check.crate_name)
+            .unwrap()
+            .newest_version();
+        assert!(check.exists);
+        assert_eq!(check.latest_version.as_deref(), Some(newest));
+        // Synonyms are treated exactly like "latest": no specific version was requested
+        assert_eq!(check.requested_version, None);
+        assert_eq!(check.version_exists, None);
+    }
+}
+
+/// Test a shared token bucket throttles a burst of calls made through client clones
+#[tokio::test]
+async fn test_rate_limit_throttles_requests() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .rate_limit(1200, 5)
+        .build()
+        .expect("Failed to build client");
+
+    // 5 permits are available up front; the other 25 arrive at 20 per second
+    let start = std::time::Instant::now();
+    let calls = (0..30).map(|_| {
+        let client = client.clone();
+        async move { client.get_crate_info("serde").await }
+    });
+    let results = futures::future::join_all(calls).await;
+    let elapsed = start.elapsed();
+
+    assert!(results.iter().all(|r| r.is_ok()));
+    assert_eq!(client.stats().requests, 30);
+    assert!(
+        elapsed >= Duration::from_millis(1200),
+        "30 calls finished in {:?}",
+        elapsed
+    );
+}
+
+/// Test a zero request rate is rejected when building the client
+#[test]
+fn test_rate_limit_rejects_zero_rate() {
+    let result = CrateClient::builder().rate_limit(0, 5).build();
+    assert!(result.is_err());
+}
+
+/// Test concurrent batch processing is faster than sequential and returns the same results
+#[tokio::test]
+async fn test_concurrent_batch_matches_sequential() {
+    let router = common::delay_each(common::registry_router(), Duration::from_millis(50));
+    let client = CrateClient::builder()
+        .base_url(common::serve(router))
+        .max_concurrent(10)
+        .build()
+        .expect("Failed to build client");
+    let crates: Vec<String> = common::MOCK_CRATES
+        .iter()
+        .cycle()
+        .take(10)
+        .map(|c| c.name.to_string())
+        .collect();
+
+    let start = std::time::Instant::now();
+    let sequential = client
+        .process_crate_list(crates.clone())
+        .await
+        .expect("Request failed");
+    let sequential_elapsed = start.elapsed();
+
+    let start = std::time::Instant::now();
+    let concurrent = client
+        .process_crate_list_concurrent(crates.clone())
+        .await
+        .expect("Request failed");
+    let concurrent_elapsed = start.elapsed();
+
+    assert_eq!(
+        serde_json::to_value(&concurrent).unwrap(),
+        serde_json::to_value(&sequential).unwrap()
+    );
+    let names: Vec<&str> = concurrent.iter().map(|r| r.crate_name.as_str()).collect();
+    assert_eq!(names, crates);
+    assert!(
+        concurrent_elapsed * 3 < sequential_elapsed,
+        "concurrent {:?} vs sequential {:?}",
+        concurrent_elapsed,
+        sequential_elapsed
+    );
+}
+
+/// Test reverse dependencies are paged by page number until the limit is reached
+#[tokio::test]
+async fn test_reverse_dependencies_pagination() {
+    use axum::{extract::Query, routing::get, Json, Router};
+    use serde_json::json;
+
+    let router = Router::new().route(
+        "/crates/:name/reverse_dependencies",
+        get(|Query(params): Query<HashMap<String, usize>>| async move {
+            let (per_page, page) = (params["per_page"], params["page"]);
+            let ids: Vec<usize> = ((page - 1) * per_page..page * per_page)
+                .filter(|id| *id < 250)
+                .collect();
+            Json(json!({
+                "dependencies": ids.iter().map(|id| json!({
+                    "version_id": id, "req": "^1", "downloads": 1_000 - id,
+                })).collect::<Vec<_>>(),
+                "versions": ids.iter().map(|id| json!({
+                    "id": id, "crate": format!("dependent-{}", id), "num": "1.0.0",
+                })).collect::<Vec<_>>(),
+                "meta": { "total": 250 },
+            }))
+        }),
+    );
+    let (url, hits) = common::serve_counted(router);
+    let client = CrateClient::builder()
+        .base_url(url)
+        .build()
+        .expect("Failed to build client");
+
+    let report = client
+        .get_reverse_dependency_report("core-lib", Some(150))
+        .await
+        .expect("Request failed");
+    assert_eq!(report.total, 250);
+    assert_eq!(report.dependents.len(), 150);
+    assert_eq!(report.dependents[0].name, "dependent-0");
+    assert_eq!(report.dependents[149].name, "dependent-149");
+    assert_eq!(hits.count(), 2);
+
+    let dependents = client
+        .get_reverse_dependencies("core-lib", None)
+        .await
+        .expect("Request failed");
+    assert_eq!(dependents.len(), 10);
+}
+
+/// Test crate info carries the license of the newest version
+#[tokio::test]
+async fn test_crate_info_license() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .build()
+        .expect("Failed to build client");
+
+    let info = client
+        .get_crate_info("serde")
+        .await
+        .expect("Request failed");
+    assert_eq!(info.license.as_deref(), Some("MIT OR Apache-2.0"));
+}
+
+/// Test the dependency tree dedupes shared crates, stops at cycles and fetches each crate once
+#[tokio::test]
+async fn test_dependency_tree() {
+    let (url, hits) = common::serve_counted(common::registry_router());
+    let client = CrateClient::builder()
+        .base_url(url)
+        .build()
+        .expect("Failed to build client");
+
+    let tree = client
+        .build_dependency_tree("tree-root", "latest", 3)
+        .await
+        .expect("Request failed");
+
+    let label = |node: &DepNode| format!("{}@{}", node.name, node.version.as_deref().unwrap());
+    let children = |node: &DepNode| node.children.iter().map(label).collect::<Vec<_>>();
+    assert_eq!(label(&tree), "tree-root@1.0.0");
+    assert_eq!(children(&tree), ["tree-left@1.1.0", "tree-right@1.0.0"]);
+
+    let (left, right) = (&tree.children[0], &tree.children[1]);
+    let shared = &left.children[0];
+    assert_eq!(children(shared), ["tree-leaf@0.1.0"]);
+    assert!(!shared.duplicate);
+    assert_eq!(children(right), ["tree-shared@1.0.0", "tree-root@1.0.0"]);
+    assert!(right.children.iter().all(|child| child.duplicate));
+    assert!(right.children.iter().all(|child| child.children.is_empty()));
+
+    // Versions of 5 crates plus the dependencies of the 4 crate versions above the leaf
+    assert_eq!(hits.count(), 9);
+}
+
+/// Test the response cache answers repeated lookups without another HTTP request
+#[tokio::test]
+async fn test_response_cache() {
+    let (url, hits) = common::serve_counted(common::registry_router());
+    let client = CrateClient::builder()
+        .base_url(url)
+        .cache(CacheConfig {
+            enabled: true,
+            ttl_seconds: 60,
+            max_entries: 100,
+            ..CacheConfig::default()
+        })
+        .build()
+        .expect("Failed to build client");
+
+    let first = client
+        .get_crate_info("serde")
+        .await
+        .expect("Request failed");
+    let second = client
+        .get_crate_info("serde")
+        .await
+        .expect("Request failed");
+    assert_eq!(first, second);
+    assert!(client.crate_exists("serde").await.expect("Request failed"));
+    assert_eq!(hits.count(), 1);
+
+    // Misses are not cached
+    assert!(!client
+        .crate_exists("not-a-real-crate")
+        .await
+        .expect("Request failed"));
+    assert!(!client
+        .crate_exists("not-a-real-crate")
+        .await
+        .expect("Request failed"));
+    assert_eq!(hits.count(), 3);
+}
+
+/// Test the response cache never holds more than `max_entries` responses
+#[tokio::test]
+async fn test_response_cache_is_bounded() {
+    let (url, hits) = common::serve_counted(common::registry_router());
+    let client = CrateClient::builder()
+        .base_url(url)
+        .cache(CacheConfig {
+            enabled: true,
+            ttl_seconds: 60,
+            max_entries: 2,
+            ..CacheConfig::default()
+        })
+        .build()
+        .expect("Failed to build client");
+
+    for name in ["serde", "tokio", "bytes"] {
+        client.get_crate_info(name).await.expect("Request failed");
+    }
+    assert_eq!(hits.count(), 3);
+
+    // The oldest entry made room for the newest
+    client
+        .get_crate_info("bytes")
+        .await
+        .expect("Request failed");
+    assert_eq!(hits.count(), 3);
+    client
+        .get_crate_info("serde")
+        .await
+        .expect("Request failed");
+    assert_eq!(hits.count(), 4);
+}
+
+/// Test consecutive search pages are disjoint and report the total match count
+#[tokio::test]
+async fn test_search_pagination() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .build()
+        .expect("Failed to build client");
+
+    let page = |offset| SearchParams {
+        q: "tree".to_string(),
+        limit: Some(2),
+        offset: Some(offset),
+        sort: Some(SearchSort::Downloads),
+    };
+    let first = client.search_page(&page(0)).await.expect("Request failed");
+    let second = client.search_page(&page(2)).await.expect("Request failed");
+
+    assert_eq!(first.total, 5);
+    assert_eq!(second.total, 5);
+    assert_eq!(first.crates.len(), 2);
+    assert_eq!(second.crates.len(), 2);
+    assert!(first
+        .crates
+        .iter()
+        .all(|a| second.crates.iter().all(|b| a.name != b.name)));
+
+    // An offset inside a page spans the overlapping pages
+    let shifted = client.search_page(&page(1)).await.expect("Request failed");
+    assert_eq!(shifted.crates[0].name, first.crates[1].name);
+    assert_eq!(shifted.crates[1].name, second.crates[0].name);
+
+    assert!("newest".parse::<SearchSort>().is_err());
+}
+
+/// Test category and keyword listings are ordered by downloads and reject unknown tags
+#[tokio::test]
+async fn test_list_category_and_keyword() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .build()
+        .expect("Failed to build client");
+
+    let names = |crates: Vec<CrateSearchResult>| -> Vec<String> {
+        crates.into_iter().map(|c| c.name).collect()
+    };
+    let listed = client
+        .list_category("command-line-utilities", None)
+        .await
+        .expect("Request failed");
+    assert_eq!(names(listed), ["tree-root", "tree-left", "abandoned"]);
+
+    let listed = client
+        .list_category("asynchronous", Some(2))
+        .await
+        .expect("Request failed");
+    assert_eq!(names(listed), ["tokio", "bytes"]);
+
+    let listed = client
+        .list_keyword("serialization", None)
+        .await
+        .expect("Request failed");
+    assert_eq!(names(listed), ["serde", "serde_derive"]);
+
+    match client.list_category("no-such-category", None).await {
+        Err(crate_checker::CrateCheckerError::CategoryNotFound(slug)) => {
+            assert_eq!(slug, "no-such-category")
+        }
+        other => panic!("expected CategoryNotFound, got {:?}", other),
+    }
+    assert!(matches!(
+        client.list_keyword("no-such-keyword", None).await,
+        Err(crate_checker::CrateCheckerError::KeywordNotFound(_))
+    ));
+    assert!(client.list_keyword(" ", None).await.is_err());
+}
+
+/// Test a registry token is sent as the Authorization header of every request
+#[tokio::test]
+async fn test_registry_token_sent() {
+    let (api_url, log) = common::serve_recorded(common::registry_router());
+    let client = CrateClient::builder()
+        .base_url(api_url)
+        .header("Authorization", "overridden")
+        .registry_token("cio-secret")
+        .build()
+        .expect("Failed to build client");
+
+    assert!(client.crate_exists("serde").await.expect("Request failed"));
+    client
+        .get_all_versions("serde")
+        .await
+        .expect("Request failed");
+
+    let requests = log.headers();
+    assert_eq!(requests.len(), 2);
+    for headers in requests {
+        assert_eq!(headers["authorization"], "cio-secret");
+    }
+
+    let invalid = CrateClient::builder().registry_token("bad\ntoken").build();
+    assert!(invalid.is_err());
+}
+
+/// Test fetching the metadata of a single version
+#[tokio::test]
+async fn test_get_version() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .build()
+        .expect("Failed to build client");
+
+    let version = client
+        .get_version("serde", "1.0.0")
+        .await
+        .expect("Request failed");
+    assert!(!version.num.is_empty());
+    assert_eq!(version.num, "1.0.0");
+    assert!(version.created_at.timestamp() > 0);
+    assert_eq!(version.crate_size, Some(10_240));
+    assert!(!version.yanked);
+
+    let yanked = client
+        .get_version("serde", "1.0.172")
+        .await
+        .expect("Request failed");
+    assert!(yanked.yanked);
+
+    let missing = client.get_version("serde", "9.9.9").await;
+    assert!(matches!(
+        missing,
+        Err(crate_checker::CrateCheckerError::VersionNotFound { .. })
+    ));
+}
+
+/// Test the feature map of a version includes the default feature
+#[tokio::test]
+async fn test_get_crate_features() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .build()
+        .expect("Failed to build client");
+
+    let features = client
+        .get_crate_features("serde", "1.0.210")
+        .await
+        .expect("Request failed");
+    assert_eq!(features["default"], ["std"]);
+    assert_eq!(features["serde_derive"], ["dep:serde_derive"]);
+    assert!(features["std"].is_empty());
+
+    assert!(client.get_crate_features("serde", "9.9.9").await.is_err());
+}
+
+/// Test offline mode answers from a fixture cargo registry without any HTTP request
+#[tokio::test]
+async fn test_offline_mode_reads_local_index() {
+    let cargo_home = tempfile::TempDir::new().unwrap();
+    let index = cargo_home
+        .path()
+        .join("registry/index/index.crates.io-6f17d22bba15001f");
+
+    // Sparse index cache entry: binary header, then NUL-separated version/JSON pairs
+    let cache_file = index.join(".cache/se/rd/serde");
+    std::fs::create_dir_all(cache_file.parent().unwrap()).unwrap();
+    let mut cache = b"\x03\x02\x00\x00\x00etag: \"fixture\"\x00".to_vec();
+    for (version, yanked) in [("1.0.0", false), ("1.0.210", false), ("1.0.211", true)] {
+        cache.extend_from_slice(version.as_bytes());
+        cache.push(0);
+        cache.extend_from_slice(
+            format!(r#"{{"name":"serde","vers":"{version}","deps":[],"yanked":{yanked}}}"#)
+                .as_bytes(),
+        );
+        cache.push(0);
+    }
+    std::fs::write(&cache_file, cache).unwrap();
+
+    // Checked-out git index entry: one JSON line per version
+    let git_file = index.join("3/s/syn");
+    std::fs::create_dir_all(git_file.parent().unwrap()).unwrap();
+    std::fs::write(
+        &git_file,
+        "{\"name\":\"syn\",\"vers\":\"2.0.0\",\"yanked\":false}\n\
+         {\"name\":\"syn\",\"vers\":\"2.1.0-rc.1\",\"yanked\":false}\n",
+    )
+    .unwrap();
+
+    let (url, hits) = common::serve_counted(common::registry_router());
+    let client = CrateClient::builder()
+        .base_url(url)
+        .offline(true)
+        .cargo_home(cargo_home.path())
+        .build()
+        .expect("Failed to build client");
+
+    assert!(client.crate_exists("serde").await.unwrap());
+    assert!(client.crate_exists("syn").await.unwrap());
+    assert!(!client.crate_exists("tokio").await.unwrap());
+    assert_eq!(client.get_latest_version("serde").await.unwrap(), "1.0.210");
+    assert_eq!(client.get_latest_version("syn").await.unwrap(), "2.0.0");
+    assert!(matches!(
+        client.get_latest_version("tokio").await,
+        Err(crate_checker::CrateCheckerError::CrateNotFound(name)) if name == "tokio"
+    ));
+    assert!(matches!(
+        client.get_crate_info("serde").await,
+        Err(crate_checker::CrateCheckerError::NetworkError(_))
+    ));
+    assert_eq!(hits.count(), 0);
+}
+
+/// Test name availability treats hyphens and underscores as the same name
+#[tokio::test]
+async fn test_is_name_available() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .build()
+        .expect("Failed to build client");
+
+    assert!(client.is_name_available("brand-new-name").await.unwrap());
+    assert!(!client.is_name_available("serde").await.unwrap());
+    // serde_derive and pin-project-lite collide with their separator-swapped forms
+    assert!(!client.is_name_available("serde-derive").await.unwrap());
+    assert!(!client.is_name_available("pin_project_lite").await.unwrap());
+    assert!(!client.is_name_available("not a name").await.unwrap());
+}
+
+/// Test bulk existence checks over a mix of existing and missing crates
+#[tokio::test]
+async fn test_crates_exist_bulk() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .max_concurrent(2)
+        .build()
+        .expect("Failed to build client");
+
+    let names: Vec<String> = ["serde", "tokio", "no-such-crate", "bytes", "missing-too"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let results = client.crates_exist(&names).await.expect("Request failed");
+
+    assert_eq!(results.len(), 5);
+    assert!(results["serde"]);
+    assert!(results["tokio"]);
+    assert!(!results["no-such-crate"]);
+    assert!(!results["missing-too"]);
+    assert!(results["bytes"]);
+}
+
+/// Test existence checks fall back to GET when the registry rejects HEAD
+#[tokio::test]
+async fn test_crates_exist_without_head_support() {
+    use axum::{extract::Request, http::Method, middleware::Next, response::IntoResponse};
+
+    let router = common::registry_router().layer(axum::middleware::from_fn(
+        |req: Request, next: Next| async move {
+            if req.method() == Method::HEAD {
+                return axum::http::StatusCode::METHOD_NOT_ALLOWED.into_response();
+            }
+            next.run(req).await
+        },
+    ));
+    let client = CrateClient::builder()
+        .base_url(common::serve(router))
+        .build()
+        .expect("Failed to build client");
+
+    let names = vec!["serde".to_string(), "no-such-crate".to_string()];
+    let results = client.crates_exist(&names).await.expect("Request failed");
+    assert!(results["serde"]);
+    assert!(!results["no-such-crate"]);
+}
+
+/// Test the latest stable version skips pre-releases and yanked versions
+#[tokio::test]
+async fn test_latest_stable_version() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .build()
+        .expect("Failed to build client");
+
+    // serde has 2.0.0-alpha.1 published after 1.0.210
+    let stable = client
+        .get_latest_stable_version("serde")
+        .await
+        .expect("Request failed");
+    assert_eq!(stable, "1.0.210");
+
+    assert!(client
+        .get_latest_stable_version("no-such-crate")
+        .await
+        .is_err());
+}
+
+/// Test dependency analysis finds the dependency two crates share
+#[tokio::test]
+async fn test_analyze_dependencies_shared() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .build()
+        .expect("Failed to build client");
+
+    let report = client
+        .analyze_dependencies(vec![
+            "tree-left".to_string(),
+            "tree-right".to_string(),
+            "serde".to_string(),
+            "no-such-crate".to_string(),
+        ])
+        .await
+        .expect("Analysis failed");
+
+    assert_eq!(report.crates["tree-left"], "1.1.0");
+    assert_eq!(report.shared, ["tree-shared"]);
+    assert!(report.conflicts.is_empty());
+    let requesters: Vec<&str> = report.dependencies["tree-shared"]
+        .iter()
+        .map(|r| r.crate_name.as_str())
+        .collect();
+    assert_eq!(requesters, ["tree-left", "tree-right"]);
+    assert_eq!(report.dependencies["serde_derive"].len(), 1);
+    assert_eq!(report.failed, ["no-such-crate"]);
+}
+
+/// Test requirements in different semver series are flagged as conflicts
+#[test]
+fn test_dependency_report_conflicts() {
+    let dep = |name: &str, req: &str, kind: &str| Dependency {
+        name: name.to_string(),
+        req: req.to_string(),
+        features: Vec::new(),
+        optional: false,
+        default_features: true,
+        target: None,
+        kind: kind.to_string(),
+        downloads: None,
+    };
+
+    let report = DependencyReport::from_dependencies(
+        vec![
+            (
+                "a".to_string(),
+                "1.0.0".to_string(),
+                vec![dep("rand", "^0.8", "normal"), dep("log", "^0.4", "normal")],
+            ),
+            (
+                "b".to_string(),
+                "2.0.0".to_string(),
+                vec![
+                    dep("rand", "^0.9", "normal"),
+                    dep("log", "^0.4.20", "build"),
+                ],
+            ),
+            (
+                "c".to_string(),
+                "0.1.0".to_string(),
+                vec![dep("rand", "^0.7", "dev")],
+            ),
+        ],
+        Vec::new(),
+    );
+
+    assert_eq!(report.shared, ["log", "rand"]);
+    assert_eq!(report.conflicts, ["rand"]);
+    // Dev-dependencies are left out
+    assert_eq!(report.dependencies["rand"].len(), 2);
+}
+
+/// Test the MSRV and edition of the newest version are reported with the crate info
+#[tokio::test]
+async fn test_crate_info_msrv() {
+    let client = CrateClient::builder()
+        .base_url(common::mock_registry())
+        .build()
+        .expect("Failed to build client");
+
+    let info = client
+        .get_crate_info("tokio")
+        .await
+        .expect("Request failed");
+    assert_eq!(info.msrv.as_deref(), Some("1.70"));
+    assert_eq!(info.edition.as_deref(), Some("2021"));
+
+    let version = client
+        .get_version("tokio", "1.40.0")
+        .await
+        .expect("Request failed");
+    assert_eq!(version.rust_version.as_deref(), Some("1.70"));
+
+    // No rust-version declared
+    let info = client
+        .get_crate_info("bytes")
+        .await
+        .expect("Request failed");
+    assert_eq!(info.msrv, None);
+    assert_eq!(info.edition, None);
+}
+
+/// Log output captured by a test subscriber
+#[derive(Clone, Default)]
+struct CapturedLogs(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl CapturedLogs {
+    fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+}
+
+impl std::io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Test upstream requests slower than the threshold are logged, unless it is zero
+#[tokio::test]
+async fn test_slow_request_warning() {
+    let logs = CapturedLogs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::WARN)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    // The current-thread test runtime keeps every request on this thread's subscriber
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let api_url = common::serve(common::delay_each(
+        common::registry_router(),
+        Duration::from_millis(150),
+    ));
+    let slow_client = |threshold| {
+        CrateClient::builder()
+            .base_url(&api_url)
+            .slow_request_threshold(threshold)
+            .build()
+            .expect("Failed to build client")
+    };
+
+    assert!(slow_client(Duration::ZERO)
+        .crate_exists("serde")
+        .await
+        .unwrap());
+    assert!(!logs.contents().contains("Slow upstream request"));
+
+    assert!(slow_client(Duration::from_secs(5))
+        .crate_exists("serde")
+        .await
+        .unwrap());
+    assert!(!logs.contents().contains("Slow upstream request"));
+
+    assert!(slow_client(Duration::from_millis(50))
+        .crate_exists("serde")
+        .await
+        .unwrap());
+    let logs = logs.contents();
+    assert!(logs.contains("Slow upstream request"), "{}", logs);
+    assert!(
+        logs.contains(&format!("{}/crates/serde took", api_url)),
+        "{}",
+        logs
+    );
+}
+
+/// Test latest-version selection skips a pre-release newest version unless included
+#[tokio::test]
+async fn test_latest_version_skips_prerelease() {
+    use axum::{routing::get, Json, Router};
+    use serde_json::json;
+
+    let serde = common::mock_crate("serde").unwrap();
+    let mut info = common::crate_json(serde);
+    info["crate"]["newest_version"] = json!("2.0.0-alpha.1");
+    let versions: Vec<_> = (0..serde.versions.len())
+        .map(|i| common::version_json(serde, i))
+        .collect();
+    let router = Router::new()
+        .route("/crates/serde", get(move || async move { Json(info) }))
+        .route(
+            "/crates/serde/versions",
+            get(move || async move { Json(json!({ "versions": versions, "meta": {} })) }),
+        );
+    let api_url = common::serve(router);
+    let client = |include_prerelease| {
+        CrateClient::builder()
+            .base_url(&api_url)
+            .include_prerelease(include_prerelease)
+            .build()
+            .expect("Failed to build client")
+    };
+
+    let hiding = client(false);
+    assert!(!hiding.include_prerelease());
+    assert_eq!(hiding.get_latest_version("serde").await.unwrap(), "1.0.210");
+    let listed = hiding.list_versions("serde").await.unwrap();
+    assert_eq!(listed.len(), 5);
+    assert!(!listed.iter().any(|v| v.num.contains('-')));
+    // Every published version is still available for exact resolution
+    assert_eq!(hiding.get_all_versions("serde").await.unwrap().len(), 7);
+
+    let including = client(true);
+    assert_eq!(
+        including.get_latest_version("serde").await.unwrap(),
+        "2.0.0-alpha.1"
+    );
+    assert_eq!(including.list_versions("serde").await.unwrap().len(), 7);
+}
@@ -0,0 +1,654 @@
+//! Shared helpers for integration tests: an in-process stand-in for the crates.io API
+//!
+//! Each test binary only uses a subset of these helpers.
+#![allow(dead_code)]
+
+use axum::{
+    extract::{Path, Query, Request},
+    http::{HeaderMap, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Serve `router` on an ephemeral local port from a background thread, returning its base URL
+///
+/// The server runs on its own runtime so it works from both sync and async tests.
+pub fn serve(router: Router) -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock");
+    listener.set_nonblocking(true).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async move {
+            let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+            axum::serve(listener, router).await.unwrap();
+        });
+    });
+
+    format!("http://{}", addr)
+}
+
+/// Counts requests received by a mock server
+#[derive(Clone, Default)]
+pub struct HitCounter(Arc<AtomicUsize>);
+
+impl HitCounter {
+    pub fn count(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Serve `router` and count every request it receives
+pub fn serve_counted(router: Router) -> (String, HitCounter) {
+    let counter = HitCounter::default();
+    let hits = counter.clone();
+    let router = router.layer(middleware::from_fn(move |req: Request, next: Next| {
+        let hits = hits.clone();
+        async move {
+            hits.0.fetch_add(1, Ordering::SeqCst);
+            next.run(req).await
+        }
+    }));
+
+    (serve(router), counter)
+}
+
+/// Tracks how many requests a mock server is handling at once
+#[derive(Clone, Default)]
+pub struct ConcurrencyGauge {
+    current: Arc<AtomicUsize>,
+    peak: Arc<AtomicUsize>,
+}
+
+impl ConcurrencyGauge {
+    /// Most requests seen in flight at the same time
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::SeqCst)
+    }
+}
+
+/// Serve `router` and record the peak number of requests it handles concurrently
+pub fn serve_gauged(router: Router) -> (String, ConcurrencyGauge) {
+    let gauge = ConcurrencyGauge::default();
+    let tracked = gauge.clone();
+    let router = router.layer(middleware::from_fn(move |req: Request, next: Next| {
+        let tracked = tracked.clone();
+        async move {
+            let now = tracked.current.fetch_add(1, Ordering::SeqCst) + 1;
+            tracked.peak.fetch_max(now, Ordering::SeqCst);
+            let response = next.run(req).await;
+            tracked.current.fetch_sub(1, Ordering::SeqCst);
+            response
+        }
+    }));
+
+    (serve(router), gauge)
+}
+
+/// Headers of every request received by a mock server, in arrival order
+#[derive(Clone, Default)]
+pub struct RequestLog(Arc<Mutex<Vec<HeaderMap>>>);
+
+impl RequestLog {
+    pub fn headers(&self) -> Vec<HeaderMap> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Serve `router` and record the headers of every request it receives
+pub fn serve_recorded(router: Router) -> (String, RequestLog) {
+    let log = RequestLog::default();
+    let recorded = log.clone();
+    let router = router.layer(middleware::from_fn(move |req: Request, next: Next| {
+        recorded.0.lock().unwrap().push(req.headers().clone());
+        next.run(req)
+    }));
+
+    (serve(router), log)
+}
+
+/// Make `router` answer its first `failures` requests with `status` before serving normally
+pub fn fail_first(router: Router, failures: usize, status: StatusCode) -> Router {
+    fail_first_with(router, failures, move || status.into_response())
+}
+
+/// Make `router` answer its first `failures` requests with 429 and a `Retry-After` header
+pub fn throttle_first(router: Router, failures: usize, retry_after: &'static str) -> Router {
+    fail_first_with(router, failures, move || {
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(axum::http::header::RETRY_AFTER, retry_after)],
+        )
+            .into_response()
+    })
+}
+
+/// Make `router` answer its first `failures` requests with `failure()` before serving normally
+fn fail_first_with(
+    router: Router,
+    failures: usize,
+    failure: impl Fn() -> Response + Clone + Send + Sync + 'static,
+) -> Router {
+    let remaining = Arc::new(AtomicUsize::new(failures));
+    router.layer(middleware::from_fn(move |req: Request, next: Next| {
+        let remaining = remaining.clone();
+        let failure = failure.clone();
+        async move {
+            let failing = remaining
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failing {
+                failure()
+            } else {
+                next.run(req).await
+            }
+        }
+    }))
+}
+
+/// Serve a stand-in API server whose `/health` endpoint reports `status`
+pub fn health_server(status: &'static str) -> String {
+    serve(Router::new().route(
+        "/health",
+        get(move || async move {
+            Json(json!({
+                "status": status,
+                "timestamp": "2024-01-01T00:00:00Z",
+                "version": "1.0.0",
+                "uptime_seconds": 125,
+            }))
+        }),
+    ))
+}
+
+/// Make `router` wait `delay` before answering each request, simulating upstream latency
+pub fn delay_each(router: Router, delay: std::time::Duration) -> Router {
+    router.layer(middleware::from_fn(
+        move |req: Request, next: Next| async move {
+            tokio::time::sleep(delay).await;
+            next.run(req).await
+        },
+    ))
+}
+
+/// A fixture crate known to the mock registry
+pub struct MockCrate {
+    pub name: &'static str,
+    pub downloads: u64,
+    pub license: &'static str,
+    /// Versions as (num, yanked), oldest first; the last non-pre-release entry is the newest
+    pub versions: &'static [(&'static str, bool)],
+    /// Runtime dependencies as (name, requirement)
+    pub dependencies: &'static [(&'static str, &'static str)],
+    /// Declared `rust-version`, reported on every version along with edition 2021
+    pub rust_version: Option<&'static str>,
+}
+
+impl MockCrate {
+    pub fn newest_version(&self) -> &'static str {
+        self.versions
+            .iter()
+            .rev()
+            .find(|(num, _)| !num.contains('-'))
+            .map(|(num, _)| *num)
+            .unwrap_or(self.versions[self.versions.len() - 1].0)
+    }
+}
+
+/// Crates served by [`registry_router`]
+pub const MOCK_CRATES: &[MockCrate] = &[
+    MockCrate {
+        name: "serde",
+        downloads: 300_000_000,
+        license: "MIT OR Apache-2.0",
+        versions: &[
+            ("0.9.15", false),
+            ("1.0.0-rc.1", false),
+            ("1.0.0", false),
+            ("1.0.130", false),
+            ("1.0.172", true),
+            ("1.0.210", false),
+            ("2.0.0-alpha.1", false),
+        ],
+        dependencies: &[("serde_derive", "^1.0")],
+        rust_version: Some("1.31"),
+    },
+    MockCrate {
+        name: "tokio",
+        downloads: 200_000_000,
+        license: "MIT",
+        versions: &[("0.3.0", false), ("1.0.0", false), ("1.40.0", false)],
+        dependencies: &[("bytes", "^1.0"), ("pin-project-lite", "^0.2")],
+        rust_version: Some("1.70"),
+    },
+    MockCrate {
+        name: "serde_derive",
+        downloads: 250_000_000,
+        license: "MIT OR Apache-2.0",
+        versions: &[("1.0.0", false), ("1.0.210", false)],
+        dependencies: &[],
+        rust_version: None,
+    },
+    MockCrate {
+        name: "bytes",
+        downloads: 150_000_000,
+        license: "MIT",
+        versions: &[("1.0.0", false), ("1.7.1", false)],
+        dependencies: &[],
+        rust_version: None,
+    },
+    MockCrate {
+        name: "pin-project-lite",
+        downloads: 140_000_000,
+        license: "Apache-2.0 OR MIT",
+        versions: &[("0.2.0", false), ("0.2.14", false)],
+        dependencies: &[],
+        rust_version: None,
+    },
+    MockCrate {
+        name: "abandoned",
+        downloads: 1_200,
+        license: "MIT",
+        versions: &[("0.1.0", true), ("0.2.0", true)],
+        dependencies: &[],
+        rust_version: None,
+    },
+    // A small graph with a shared dependency and a cycle back to the root
+    MockCrate {
+        name: "tree-root",
+        downloads: 5_000,
+        license: "MIT",
+        versions: &[("1.0.0", false)],
+        dependencies: &[("tree-left", "^1"), ("tree-right", "^1")],
+        rust_version: None,
+    },
+    MockCrate {
+        name: "tree-left",
+        downloads: 4_000,
+        license: "MIT",
+        versions: &[("1.0.0", false), ("1.1.0", false)],
+        dependencies: &[("tree-shared", "^1")],
+        rust_version: None,
+    },
+    MockCrate {
+        name: "tree-right",
+        downloads: 3_000,
+        license: "MIT",
+        versions: &[("1.0.0", false)],
+        dependencies: &[("tree-shared", "^1"), ("tree-root", "^1")],
+        rust_version: None,
+    },
+    MockCrate {
+        name: "tree-shared",
+        downloads: 2_000,
+        license: "MIT",
+        versions: &[("1.0.0", false)],
+        dependencies: &[("tree-leaf", "^0.1")],
+        rust_version: None,
+    },
+    MockCrate {
+        name: "tree-leaf",
+        downloads: 1_000,
+        license: "MIT",
+        versions: &[("0.1.0", false), ("0.1.1", true)],
+        dependencies: &[],
+        rust_version: None,
+    },
+    // Has build and dev dependencies besides its runtime one (see MOCK_DEPENDENCY_KINDS)
+    MockCrate {
+        name: "buildscript",
+        downloads: 500,
+        license: "MIT",
+        versions: &[("0.3.0", false)],
+        dependencies: &[("tree-leaf", "^0.1")],
+        rust_version: None,
+    },
+];
+
+/// Non-runtime dependencies as (crate, dependency, requirement, kind), served after
+/// the crate's runtime ones
+pub const MOCK_DEPENDENCY_KINDS: &[(&str, &str, &str, &str)] = &[
+    ("buildscript", "tree-shared", "^1", "build"),
+    ("buildscript", "tree-right", "^1", "dev"),
+];
+
+/// Category slugs known to [`registry_router`] with the fixture crates in each
+pub const MOCK_CATEGORIES: &[(&str, &[&str])] = &[
+    ("encoding", &["serde", "serde_derive"]),
+    ("asynchronous", &["tokio", "bytes", "pin-project-lite"]),
+    (
+        "command-line-utilities",
+        &["tree-root", "tree-left", "abandoned"],
+    ),
+];
+
+/// Keywords known to [`registry_router`] with the fixture crates tagged with each
+pub const MOCK_KEYWORDS: &[(&str, &[&str])] = &[
+    ("serialization", &["serde", "serde_derive"]),
+    ("async", &["tokio"]),
+];
+
+fn tagged<'a>(tags: &'a [(&str, &'a [&'a str])], tag: &str) -> Option<&'a [&'a str]> {
+    tags.iter()
+        .find(|(name, _)| *name == tag)
+        .map(|(_, crates)| *crates)
+}
+
+/// Look up a fixture crate by name
+pub fn mock_crate(name: &str) -> Option<&'static MockCrate> {
+    MOCK_CRATES.iter().find(|c| c.name == name)
+}
+
+fn timestamp(index: usize) -> String {
+    format!("2020-{:02}-01T00:00:00Z", (index % 12) + 1)
+}
+
+/// JSON for a single entry of the versions endpoint
+pub fn version_json(krate: &MockCrate, index: usize) -> Value {
+    let (num, yanked) = krate.versions[index];
+    json!({
+        "id": index as u64 + 1,
+        "num": num,
+        "created_at": timestamp(index),
+        "updated_at": timestamp(index),
+        "downloads": 1_000 * (index as u64 + 1),
+        "yanked": yanked,
+        "license": krate.license,
+        "crate_size": 10_240,
+        "rust_version": krate.rust_version,
+        "edition": krate.rust_version.map(|_| "2021"),
+        "features": features_json(krate),
+    })
+}
+
+/// Feature map of a mock crate: a default `std` feature plus one per dependency
+fn features_json(krate: &MockCrate) -> Value {
+    let mut features = serde_json::Map::new();
+    features.insert("default".to_string(), json!(["std"]));
+    features.insert("std".to_string(), json!([]));
+    for (dep, _) in krate.dependencies {
+        features.insert(dep.to_string(), json!([format!("dep:{}", dep)]));
+    }
+    Value::Object(features)
+}
+
+/// JSON body of `GET /crates/{name}`
+pub fn crate_json(krate: &MockCrate) -> Value {
+    json!({
+        "crate": {
+            "name": krate.name,
+            "description": format!("The {} crate", krate.name),
+            "newest_version": krate.newest_version(),
+            "downloads": krate.downloads,
+            "recent_downloads": krate.downloads / 10,
+            "created_at": "2015-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "homepage": null,
+            "repository": format!("https://github.com/example/{}", krate.name),
+            "documentation": format!("https://docs.rs/{}", krate.name),
+            "max_upload_size": null,
+        },
+        "versions": (0..krate.versions.len()).rev().map(|i| version_json(krate, i)).collect::<Vec<_>>(),
+        "keywords": [{ "keyword": "fixture" }],
+        "categories": [{ "category": "development-tools" }],
+    })
+}
+
+/// JSON body of `GET /crates/{name}/downloads`: a 90 day rising series ending 2024-03-31
+pub fn downloads_json(krate: &MockCrate) -> Value {
+    let end = chrono::NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+    let newest = krate.newest_version();
+    let version_downloads: Vec<Value> = (0..90)
+        .map(|day| {
+            let date = end - chrono::Duration::days(89 - day);
+            json!({ "version": newest, "downloads": 100 + day as u64 * 10, "date": date.to_string() })
+        })
+        .collect();
+
+    json!({
+        "version_downloads": version_downloads,
+        "meta": { "extra_downloads": [{ "date": end.to_string(), "downloads": 5 }] },
+    })
+}
+
+fn not_found() -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({ "errors": [{ "detail": "Not Found" }] })),
+    )
+        .into_response()
+}
+
+async fn get_crate(Path(name): Path<String>) -> Response {
+    match mock_crate(&name) {
+        Some(krate) => Json(crate_json(krate)).into_response(),
+        None => not_found(),
+    }
+}
+
+async fn get_versions(Path(name): Path<String>) -> Response {
+    match mock_crate(&name) {
+        Some(krate) => {
+            let versions: Vec<Value> = (0..krate.versions.len())
+                .rev()
+                .map(|i| version_json(krate, i))
+                .collect();
+            Json(json!({ "versions": versions, "meta": { "total": versions.len() } }))
+                .into_response()
+        }
+        None => not_found(),
+    }
+}
+
+async fn get_downloads(Path(name): Path<String>) -> Response {
+    match mock_crate(&name) {
+        Some(krate) => Json(downloads_json(krate)).into_response(),
+        None => not_found(),
+    }
+}
+
+async fn get_version(Path((name, version)): Path<(String, String)>) -> Response {
+    let Some(krate) = mock_crate(&name) else {
+        return not_found();
+    };
+    match krate.versions.iter().position(|(num, _)| *num == version) {
+        Some(index) => Json(json!({ "version": version_json(krate, index) })).into_response(),
+        None => not_found(),
+    }
+}
+
+async fn get_dependencies(Path((name, version)): Path<(String, String)>) -> Response {
+    let Some(krate) = mock_crate(&name) else {
+        return not_found();
+    };
+    if !krate.versions.iter().any(|(num, _)| *num == version) {
+        return not_found();
+    }
+
+    let other_kinds = MOCK_DEPENDENCY_KINDS
+        .iter()
+        .filter(|(owner, ..)| *owner == krate.name)
+        .map(|(_, dep, req, kind)| (dep, req, *kind));
+    let dependencies: Vec<Value> = krate
+        .dependencies
+        .iter()
+        .map(|(dep, req)| (dep, req, "normal"))
+        .chain(other_kinds)
+        .map(|(dep, req, kind)| {
+            json!({
+                "crate_id": dep,
+                "req": req,
+                "features": [],
+                "optional": false,
+                "default_features": true,
+                "target": null,
+                "kind": kind,
+                "downloads": 0,
+            })
+        })
+        .collect();
+
+    Json(json!({ "dependencies": dependencies })).into_response()
+}
+
+async fn get_reverse_dependencies(
+    Path(name): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    if mock_crate(&name).is_none() {
+        return not_found();
+    }
+    let param = |key: &str, default: usize| {
+        params
+            .get(key)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    };
+    let (per_page, page) = (param("per_page", 10), param("page", 1));
+
+    let mut dependents: Vec<(usize, &MockCrate)> = MOCK_CRATES
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.dependencies.iter().any(|(dep, _)| *dep == name))
+        .collect();
+    dependents.sort_by_key(|(_, c)| std::cmp::Reverse(c.downloads));
+    let total = dependents.len();
+    let page_items = dependents
+        .into_iter()
+        .skip((page - 1) * per_page)
+        .take(per_page);
+
+    let (dependencies, versions): (Vec<Value>, Vec<Value>) = page_items
+        .map(|(id, c)| {
+            let req = c
+                .dependencies
+                .iter()
+                .find(|(dep, _)| *dep == name)
+                .unwrap()
+                .1;
+            (
+                json!({ "version_id": id, "req": req, "downloads": c.downloads }),
+                json!({ "id": id, "crate": c.name, "num": c.newest_version() }),
+            )
+        })
+        .unzip();
+
+    Json(json!({
+        "dependencies": dependencies,
+        "versions": versions,
+        "meta": { "total": total },
+    }))
+    .into_response()
+}
+
+async fn search(Query(params): Query<HashMap<String, String>>) -> Response {
+    let query = params.get("q").cloned().unwrap_or_default();
+    let param = |key: &str, default: usize| {
+        params
+            .get(key)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    };
+    let (per_page, page) = (param("per_page", 10), param("page", 1));
+
+    let category = params
+        .get("category")
+        .map(|slug| tagged(MOCK_CATEGORIES, slug));
+    let keyword = params.get("keyword").map(|kw| tagged(MOCK_KEYWORDS, kw));
+    let in_tag = |tag: Option<Option<&[&str]>>, name: &str| match tag {
+        Some(crates) => crates.unwrap_or_default().contains(&name),
+        None => true,
+    };
+
+    let mut matches: Vec<&MockCrate> = MOCK_CRATES
+        .iter()
+        .filter(|c| c.name.contains(query.as_str()))
+        .filter(|c| in_tag(category, c.name) && in_tag(keyword, c.name))
+        .collect();
+    if params.get("sort").map(String::as_str) == Some("downloads") {
+        matches.sort_by_key(|c| std::cmp::Reverse(c.downloads));
+    }
+    let total = matches.len();
+    let crates: Vec<Value> = matches
+        .into_iter()
+        .skip((page - 1) * per_page)
+        .take(per_page)
+        .map(|c| {
+            json!({
+                "name": c.name,
+                "description": format!("The {} crate", c.name),
+                "newest_version": c.newest_version(),
+                "downloads": c.downloads,
+                "exact_match": c.name == query,
+            })
+        })
+        .collect();
+    let filters: String = ["category", "keyword"]
+        .iter()
+        .filter_map(|key| Some(format!("&{}={}", key, params.get(*key)?)))
+        .collect();
+    let next_page = (page * per_page < total).then(|| {
+        format!(
+            "?q={}{}&per_page={}&page={}",
+            query,
+            filters,
+            per_page,
+            page + 1
+        )
+    });
+
+    Json(json!({ "meta": { "total": total, "next_page": next_page }, "crates": crates }))
+        .into_response()
+}
+
+async fn get_category(Path(slug): Path<String>) -> Response {
+    match tagged(MOCK_CATEGORIES, &slug) {
+        Some(crates) => Json(json!({
+            "category": { "slug": slug, "category": slug, "crates_cnt": crates.len() }
+        }))
+        .into_response(),
+        None => not_found(),
+    }
+}
+
+async fn get_keyword(Path(keyword): Path<String>) -> Response {
+    match tagged(MOCK_KEYWORDS, &keyword) {
+        Some(crates) => Json(json!({
+            "keyword": { "id": keyword, "keyword": keyword, "crates_cnt": crates.len() }
+        }))
+        .into_response(),
+        None => not_found(),
+    }
+}
+
+/// Router answering the subset of the crates.io API used by the client, backed by [`MOCK_CRATES`]
+pub fn registry_router() -> Router {
+    Router::new()
+        .route("/crates", get(search))
+        .route("/crates/:name", get(get_crate))
+        .route("/crates/:name/versions", get(get_versions))
+        .route("/crates/:name/downloads", get(get_downloads))
+        .route(
+            "/crates/:name/reverse_dependencies",
+            get(get_reverse_dependencies),
+        )
+        .route("/crates/:name/:version", get(get_version))
+        .route("/crates/:name/:version/dependencies", get(get_dependencies))
+        .route("/categories/:slug", get(get_category))
+        .route("/keywords/:keyword", get(get_keyword))
+}
+
+/// Start the fixture registry, returning its base URL
+pub fn mock_registry() -> String {
+    serve(registry_router())
+}
@@ -1,7 +1,10 @@
-use crate_checker::config::{AppConfig, EnvironmentConfig};
+use clap::Parser;
+use crate_checker::cli::{resolve_timeout, Cli};
+use crate_checker::config::{AppConfig, EnvironmentConfig, MAX_CONFIG_FILE_BYTES};
 use serial_test::serial;
 use std::env;
 use std::fs;
+use std::time::Duration;
 use tempfile::TempDir;
 
 // Helper to clean up all environment variables
@@ -14,6 +17,8 @@ fn cleanup_env_vars() {
     env::remove_var("CRATE_CHECKER__CRATES_IO__TIMEOUT_SECONDS");
     env::remove_var("CRATE_CHECKER__CRATES_IO__MAX_CONCURRENT");
     env::remove_var("CRATE_CHECKER__RATE_LIMITING__REQUESTS_PER_MINUTE");
+    env::remove_var("CRATE_CHECKER__CRATES_IO__TOKEN");
+    env::remove_var("CARGO_REGISTRY_TOKEN");
     env::remove_var("RUST_ENV");
     env::remove_var("ENVIRONMENT");
 }
@@ -72,6 +77,9 @@ burst_size = 50
 api_url = "https://test.crates.io/api/v1"
 timeout_seconds = 45
 max_concurrent = 20
+
+[crates_io.headers]
+x-deployment = "staging"
 "#;
 
     fs::write(&config_path, config_content).expect("Failed to write config file");
@@ -100,6 +108,14 @@ max_concurrent = 20
     assert_eq!(config.crates_io.api_url, "https://test.crates.io/api/v1");
     assert_eq!(config.crates_io.timeout_seconds, 45);
     assert_eq!(config.crates_io.max_concurrent, 20);
+    assert_eq!(
+        config
+            .crates_io
+            .headers
+            .get("x-deployment")
+            .map(String::as_str),
+        Some("staging")
+    );
 
     cleanup_env_vars();
 }
@@ -212,6 +228,17 @@ fn test_config_validation() {
     config.logging.format = "pretty".to_string();
     assert!(config.validate().is_ok());
 
+    // Blank API key
+    config.server.api_key = Some(" ".to_string());
+    assert!(config
+        .validate()
+        .unwrap_err()
+        .contains("API key cannot be empty"));
+
+    // Reset to valid state
+    config.server.api_key = Some("k3y".to_string());
+    assert!(config.validate().is_ok());
+
     // Invalid cache config (enabled but max_entries is 0)
     config.cache.enabled = true;
     config.cache.max_entries = 0;
@@ -410,6 +437,51 @@ port = 8080  # Missing closing bracket
     cleanup_env_vars();
 }
 
+/// Test a binary config file is rejected with a friendly error
+#[test]
+#[serial]
+fn test_non_utf8_config_file() {
+    cleanup_env_vars();
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("binary.toml");
+    fs::write(&config_path, [0x5b, 0xff, 0xfe, 0x00, 0x5d]).expect("Failed to write config file");
+
+    let error = AppConfig::load_from_file(Some(&config_path))
+        .expect_err("Binary config should be rejected")
+        .to_string();
+    assert!(
+        error.contains("config file is not valid UTF-8 TOML"),
+        "unexpected error: {}",
+        error
+    );
+
+    cleanup_env_vars();
+}
+
+/// Test an oversized config file is rejected with a friendly error
+#[test]
+#[serial]
+fn test_oversized_config_file() {
+    cleanup_env_vars();
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("huge.toml");
+    let padding = "# padding\n".repeat((MAX_CONFIG_FILE_BYTES as usize / 10) + 1);
+    fs::write(&config_path, padding).expect("Failed to write config file");
+
+    let error = AppConfig::load_from_file(Some(&config_path))
+        .expect_err("Oversized config should be rejected")
+        .to_string();
+    assert!(
+        error.contains("config file too large"),
+        "unexpected error: {}",
+        error
+    );
+
+    cleanup_env_vars();
+}
+
 /// Test configuration with missing file
 #[test]
 #[serial]
@@ -521,7 +593,14 @@ fn test_all_default_values() {
 
     // Crates.io defaults
     assert_eq!(config.crates_io.api_url, "https://crates.io/api/v1");
-    assert_eq!(config.crates_io.user_agent, "crate-checker/0.1.0");
+    assert_eq!(
+        config.crates_io.user_agent,
+        concat!(
+            "crate-checker/",
+            env!("CARGO_PKG_VERSION"),
+            " (https://github.com/sandlbn/crate-checker)"
+        )
+    );
     assert_eq!(config.crates_io.timeout_seconds, 30);
     assert_eq!(config.crates_io.max_concurrent, 10);
     assert_eq!(config.crates_io.retry_attempts, 3);
@@ -594,3 +673,63 @@ fn test_boolean_env_vars() {
 
     cleanup_env_vars();
 }
+
+/// Test timeout precedence: flag > env var > config file > default
+#[test]
+#[serial]
+fn test_timeout_precedence() {
+    cleanup_env_vars();
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = temp_dir.path().join("timeout.toml");
+    fs::write(&config_path, "[crates_io]\ntimeout_seconds = 45\n").unwrap();
+
+    let with_flag = Cli::parse_from(["crate-checker", "--timeout", "5s", "check", "serde"]);
+    let without_flag = Cli::parse_from(["crate-checker", "check", "serde"]);
+    let load = || AppConfig::load_from_file(Some(&config_path)).expect("Failed to load config");
+
+    env::set_var("CRATE_CHECKER__CRATES_IO__TIMEOUT_SECONDS", "20");
+    let config = load();
+    assert_eq!(
+        resolve_timeout(&with_flag, &config).unwrap(),
+        Duration::from_secs(5)
+    );
+    assert_eq!(
+        resolve_timeout(&without_flag, &config).unwrap(),
+        Duration::from_secs(20)
+    );
+
+    env::remove_var("CRATE_CHECKER__CRATES_IO__TIMEOUT_SECONDS");
+    assert_eq!(
+        resolve_timeout(&without_flag, &load()).unwrap(),
+        Duration::from_secs(45)
+    );
+    assert_eq!(
+        resolve_timeout(&without_flag, &AppConfig::default()).unwrap(),
+        Duration::from_secs(30)
+    );
+
+    cleanup_env_vars();
+}
+
+/// Test the API token comes from CRATE_CHECKER__CRATES_IO__TOKEN, then CARGO_REGISTRY_TOKEN
+#[test]
+#[serial]
+fn test_api_token_env_vars() {
+    cleanup_env_vars();
+
+    assert_eq!(AppConfig::load().unwrap().crates_io.token, None);
+
+    env::set_var("CARGO_REGISTRY_TOKEN", "cargo-token");
+    assert_eq!(
+        AppConfig::load().unwrap().crates_io.token.as_deref(),
+        Some("cargo-token")
+    );
+
+    env::set_var("CRATE_CHECKER__CRATES_IO__TOKEN", "checker-token");
+    let config = AppConfig::load().unwrap();
+    assert_eq!(config.crates_io.token.as_deref(), Some("checker-token"));
+    assert!(!format!("{:?}", config).contains("checker-token"));
+
+    cleanup_env_vars();
+}
@@ -11,7 +11,7 @@ use tokio::time::timeout;
 
 /// Helper to create a command for testing
 fn crate_checker_cmd() -> Command {
-    Command::cargo_bin("crate-checker").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("crate-checker")
 }
 
 /// Helper to start a test server on a random port
@@ -167,7 +167,7 @@ fn test_e2e_multi_check_workflow() {
 
     for crates in test_cases {
         let mut cmd = crate_checker_cmd();
-        cmd.args(&["check-multiple"]);
+        cmd.args(["check-multiple"]);
 
         for crate_name in &crates {
             cmd.arg(crate_name);
@@ -177,7 +177,7 @@ fn test_e2e_multi_check_workflow() {
             .assert()
             .success()
             .stdout(predicate::str::contains("SUMMARY"))
-            .stdout(predicate::str::contains(&format!(
+            .stdout(predicate::str::contains(format!(
                 "Total checked: {}",
                 crates.len()
             )));
@@ -301,14 +301,14 @@ fn test_e2e_output_formats() {
                 assert!(stdout.contains("\"exists\""));
                 // Verify it's valid JSON
                 let _: Value = serde_json::from_str(&stdout)
-                    .expect(&format!("Invalid JSON output: {}", stdout));
+                    .unwrap_or_else(|_| panic!("Invalid JSON output: {}", stdout));
             }
             "yaml" => {
                 assert!(stdout.contains("crate:"));
                 assert!(stdout.contains("exists:"));
                 // Verify it's valid YAML
                 let _: serde_yaml::Value = serde_yaml::from_str(&stdout)
-                    .expect(&format!("Invalid YAML output: {}", stdout));
+                    .unwrap_or_else(|_| panic!("Invalid YAML output: {}", stdout));
             }
             "table" => {
                 // Table output should be human-readable
@@ -551,7 +551,7 @@ fn test_e2e_stress_large_batch() {
 
     // Also test multi-check with the same crates
     let mut cmd = crate_checker_cmd();
-    cmd.args(&["check-multiple"]);
+    cmd.args(["check-multiple"]);
     for crate_name in &popular_crates[..10] {
         // Test first 10 to avoid command line length limits
         cmd.arg(crate_name);
@@ -1,5 +1,7 @@
+mod common;
+
 use crate_checker::config::AppConfig;
-use crate_checker::server::start_server;
+use crate_checker::server::{start_server, start_server_with_shutdown};
 use reqwest::Client;
 use serde_json::Value;
 use std::time::Duration;
@@ -7,10 +9,18 @@ use tokio::time::timeout;
 
 /// Helper to start a test server on a random port
 async fn start_test_server() -> (AppConfig, tokio::task::JoinHandle<()>) {
+    start_test_server_with(|_| {}).await
+}
+
+/// Helper to start a test server on a random port after adjusting its configuration
+async fn start_test_server_with(
+    configure: impl FnOnce(&mut AppConfig),
+) -> (AppConfig, tokio::task::JoinHandle<()>) {
     let mut config = AppConfig::default();
     config.server.port = 0; // Let OS choose port
     config.server.host = "127.0.0.1".to_string();
     config.cache.enabled = false; // Disable cache for tests
+    configure(&mut config);
 
     // Find available port
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
@@ -51,7 +61,7 @@ async fn test_health_endpoint() {
     let body: Value = response.json().await.expect("Invalid JSON");
     assert_eq!(body["status"], "healthy");
     assert!(body["timestamp"].is_string());
-    assert_eq!(body["version"], "1.0.0");
+    assert_eq!(body["version"], crate_checker::VERSION);
     assert!(body["uptime_seconds"].is_number());
 }
 
@@ -99,6 +109,292 @@ async fn test_metrics_endpoint() {
     assert!(body["cache_hits"].is_number());
     assert!(body["cache_misses"].is_number());
     assert!(body["uptime_seconds"].is_number());
+    assert!(body["upstream"]["requests"].is_number());
+    assert!(body["upstream"]["retries"].is_number());
+    assert!(body["upstream"]["failures"].is_number());
+}
+
+/// Test the Prometheus metrics endpoint emits parseable samples for the expected metrics
+#[tokio::test]
+async fn test_prometheus_metrics_endpoint() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let client = Client::new();
+    let base = format!("http://{}:{}", config.server.host, config.server.port);
+
+    let response = client
+        .get(format!("{}/api/crates/serde", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+
+    let response = client
+        .get(format!("{}/metrics/prometheus", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/plain; version=0.0.4"
+    );
+
+    let body = response.text().await.expect("Invalid body");
+    let samples: std::collections::HashMap<String, f64> = body
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let (name, value) = line.rsplit_once(' ').expect("Malformed sample");
+            (name.to_string(), value.parse().expect("Non-numeric sample"))
+        })
+        .collect();
+
+    for name in [
+        "crate_checker_requests_total",
+        "crate_checker_requests_successful_total",
+        "crate_checker_requests_failed_total",
+        "crate_checker_cache_hits_total",
+        "crate_checker_cache_misses_total",
+        "crate_checker_upstream_requests_total",
+        "crate_checker_uptime_seconds",
+        "crate_checker_response_time_seconds_sum",
+        "crate_checker_response_time_seconds_count",
+    ] {
+        assert!(samples.contains_key(name), "missing {}", name);
+    }
+    assert_eq!(samples["crate_checker_requests_total"], 1.0);
+    assert_eq!(
+        samples["crate_checker_response_time_seconds_bucket{le=\"+Inf\"}"],
+        1.0
+    );
+    assert!(body.contains("# TYPE crate_checker_response_time_seconds histogram"));
+}
+
+/// Test the effective configuration endpoint redacts secrets and honors the metrics token
+#[tokio::test]
+async fn test_config_endpoint_redacts_token() {
+    let (config, _handle) = start_test_server_with(|config| {
+        config.server.metrics_token = Some("s3cret".to_string());
+    })
+    .await;
+    let client = Client::new();
+    let url = format!(
+        "http://{}:{}/api/config",
+        config.server.host, config.server.port
+    );
+
+    let unauthorized = client.get(&url).send().await.expect("Request failed");
+    assert_eq!(unauthorized.status(), 401);
+
+    let response = client
+        .get(&url)
+        .bearer_auth("s3cret")
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert_eq!(body["server"]["port"], config.server.port);
+    assert_eq!(body["server"]["metrics_token"], "***");
+    assert!(!body.to_string().contains("s3cret"));
+}
+
+/// Test cache debug headers report a MISS first and a HIT once the response is cached
+#[tokio::test]
+async fn test_cache_debug_headers() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+        config.cache.enabled = true;
+        config.server.debug_headers = true;
+    })
+    .await;
+    let client = Client::new();
+    let url = format!(
+        "http://{}:{}/api/crates/serde",
+        config.server.host, config.server.port
+    );
+
+    let first = client.get(&url).send().await.expect("Request failed");
+    assert_eq!(first.status(), 200);
+    assert_eq!(first.headers()["x-cache"], "MISS");
+
+    let second = client
+        .get(format!("{}?explain=cache", url))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(second.headers()["x-cache"], "HIT");
+    let body: Value = second.json().await.expect("Invalid JSON");
+    assert_eq!(body["name"], "serde");
+    assert_eq!(body["_cache"]["status"], "HIT");
+    assert_eq!(body["_cache"]["key"], "crate:serde");
+}
+
+/// Test upstream requests are capped at `max_concurrent` while concurrent clients are
+/// queued rather than rejected
+#[tokio::test]
+async fn test_upstream_concurrency_limit() {
+    let (api_url, gauge) = common::serve_gauged(common::delay_each(
+        common::registry_router(),
+        Duration::from_millis(50),
+    ));
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+        config.crates_io.max_concurrent = 2;
+    })
+    .await;
+    let client = Client::new();
+    let url = format!(
+        "http://{}:{}/api/crates/serde",
+        config.server.host, config.server.port
+    );
+
+    let responses = futures::future::join_all((0..20).map(|_| client.get(&url).send())).await;
+    for response in responses {
+        let response = response.expect("Request failed");
+        assert_eq!(response.status(), 200);
+        let body: Value = response.json().await.expect("Invalid JSON");
+        assert_eq!(body["name"], "serde");
+    }
+    assert!(gauge.peak() >= 1);
+    assert!(
+        gauge.peak() <= 2,
+        "peak upstream concurrency {}",
+        gauge.peak()
+    );
+}
+
+/// Test cache statistics reflect cached responses and the invalidation endpoints remove them
+#[tokio::test]
+async fn test_cache_stats_and_invalidation() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+        config.cache.enabled = true;
+        config.server.api_key = Some("k3y".to_string());
+    })
+    .await;
+    let client = Client::new();
+    let base = format!("http://{}:{}", config.server.host, config.server.port);
+
+    for path in [
+        "/api/crates/serde",
+        "/api/crates/serde",
+        "/api/crates/serde/1.0.0",
+        "/api/crates/tokio",
+    ] {
+        let response = client
+            .get(format!("{}{}", base, path))
+            .bearer_auth("k3y")
+            .send()
+            .await
+            .expect("Request failed");
+        assert_eq!(response.status(), 200, "{path}");
+    }
+
+    let stats = |client: Client| {
+        let base = base.clone();
+        async move {
+            let response = client
+                .get(format!("{}/api/cache/stats", base))
+                .bearer_auth("k3y")
+                .send()
+                .await
+                .expect("Request failed");
+            assert_eq!(response.status(), 200);
+            response.json::<Value>().await.expect("Invalid JSON")
+        }
+    };
+    let body = stats(client.clone()).await;
+    assert_eq!(body["enabled"], true);
+    assert_eq!(body["backend"], "memory");
+    assert_eq!(body["entries"], 3);
+    assert!(body["approximate_size_bytes"].as_u64().unwrap() > 0);
+    assert_eq!(body["hits"], 1);
+    assert_eq!(body["misses"], 3);
+    assert_eq!(body["hit_ratio"], 0.25);
+
+    // Mutating routes sit behind the API key
+    let response = client
+        .delete(format!("{}/api/cache", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 401);
+
+    let response = client
+        .delete(format!("{}/api/cache/crate:serde", base))
+        .bearer_auth("k3y")
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert_eq!(body["removed"], 2);
+    assert_eq!(stats(client.clone()).await["entries"], 1);
+
+    let response = client
+        .delete(format!("{}/api/cache", base))
+        .bearer_auth("k3y")
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert_eq!(body["removed"], 1);
+
+    let body = stats(client.clone()).await;
+    assert_eq!(body["entries"], 0);
+    assert_eq!(body["approximate_size_bytes"], 0);
+}
+
+/// Test crate responses carry Cache-Control and an ETag honored by If-None-Match
+#[tokio::test]
+async fn test_etag_not_modified() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let client = Client::new();
+    let url = format!(
+        "http://{}:{}/api/crates/serde",
+        config.server.host, config.server.port
+    );
+
+    let first = client.get(&url).send().await.expect("Request failed");
+    assert_eq!(first.status(), 200);
+    assert_eq!(
+        first.headers()["cache-control"],
+        format!("max-age={}", config.cache.ttl_seconds).as_str()
+    );
+    let etag = first.headers()["etag"].clone();
+
+    let revalidated = client
+        .get(&url)
+        .header("If-None-Match", etag.clone())
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(revalidated.status(), 304);
+    assert_eq!(revalidated.headers()["etag"], etag);
+    assert!(revalidated.bytes().await.unwrap().is_empty());
+
+    let stale = client
+        .get(&url)
+        .header("If-None-Match", "\"0000000000000000\"")
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(stale.status(), 200);
+    let body: Value = stale.json().await.expect("Invalid JSON");
+    assert_eq!(body["name"], "serde");
 }
 
 /// Test getting crate information via API
@@ -143,6 +439,50 @@ async fn test_get_nonexistent_crate() {
     assert_eq!(response.status(), 404);
 }
 
+/// Test 404 bodies carry a stable error code and the missing crate
+#[tokio::test]
+async fn test_not_found_error_code() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let client = Client::new();
+    let base = format!("http://{}:{}", config.server.host, config.server.port);
+
+    let response = client
+        .get(format!("{}/api/crates/nonexistent", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 404);
+    let body = response.text().await.expect("Invalid body");
+    assert!(body.contains(r#""code":"CRATE_NOT_FOUND""#), "{body}");
+    let body: Value = serde_json::from_str(&body).expect("Invalid JSON");
+    assert_eq!(body["details"]["crate"], "nonexistent");
+
+    let response = client
+        .get(format!("{}/api/crates/serde/9.9.9/info", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 404);
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert_eq!(body["code"], "VERSION_NOT_FOUND");
+    assert_eq!(body["details"]["crate"], "serde");
+    assert_eq!(body["details"]["version"], "9.9.9");
+
+    let response = client
+        .get(format!("{}/api/search", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 400);
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert_eq!(body["code"], "VALIDATION");
+    assert!(body.get("details").is_none());
+}
+
 /// Test checking specific version
 #[tokio::test]
 async fn test_get_crate_version_api() {
@@ -168,6 +508,69 @@ async fn test_get_crate_version_api() {
     assert_eq!(body["version_exists"], true);
 }
 
+/// Test "Latest", "*" and "latest" all resolve to the newest version
+#[tokio::test]
+async fn test_get_crate_version_latest_synonyms() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let client = Client::new();
+
+    for version in ["Latest", "%2A", "latest"] {
+        let url = format!(
+            "http://{}:{}/api/crates/tokio/{}",
+            config.server.host, config.server.port, version
+        );
+        let response = client.get(&url).send().await.expect("Request failed");
+        assert_eq!(response.status(), 200);
+
+        let body: Value = response.json().await.expect("Invalid JSON");
+        assert_eq!(body["version_exists"], true, "{version}");
+        assert_eq!(body["resolved_version"], "1.40.0", "{version}");
+    }
+}
+
+/// Test the single version endpoint returns version metadata and 404s unknown versions
+#[tokio::test]
+async fn test_get_version_info_api() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let client = Client::new();
+    let base = format!("http://{}:{}", config.server.host, config.server.port);
+
+    let response = client
+        .get(format!("{}/api/crates/serde/1.0.172/info", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert_eq!(body["num"], "1.0.172");
+    assert_eq!(body["yanked"], true);
+    assert_eq!(body["crate_size"], 10_240);
+
+    let response = client
+        .get(format!("{}/api/crates/tokio/latest/info", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert_eq!(body["num"], "1.40.0");
+
+    let response = client
+        .get(format!("{}/api/crates/serde/9.9.9/info", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 404);
+}
+
 /// Test getting crate dependencies
 #[tokio::test]
 async fn test_get_crate_dependencies_api() {
@@ -189,6 +592,40 @@ async fn test_get_crate_dependencies_api() {
     assert!(body.is_array());
 }
 
+/// Test the status endpoint reports a valid status string and the yanked versions
+#[tokio::test]
+async fn test_get_crate_status_api() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let client = Client::new();
+    let base = format!("http://{}:{}", config.server.host, config.server.port);
+
+    let response = client
+        .get(format!("{}/api/crates/serde/status", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.expect("Invalid JSON");
+    let status = body["status"].as_str().unwrap();
+    assert!(["exists", "yanked", "partially_yanked", "not_found"].contains(&status));
+    assert_eq!(status, "partially_yanked");
+    assert_eq!(body["yanked_versions"][0], "1.0.172");
+
+    let body: Value = client
+        .get(format!("{}/api/crates/not-a-real-crate/status", base))
+        .send()
+        .await
+        .expect("Request failed")
+        .json()
+        .await
+        .expect("Invalid JSON");
+    assert_eq!(body["status"], "not_found");
+}
+
 /// Test getting crate download stats
 #[tokio::test]
 async fn test_get_crate_stats_api() {
@@ -258,6 +695,112 @@ async fn test_search_missing_query() {
     assert_eq!(response.status(), 400);
 }
 
+/// Test search pages are disjoint and carry the total match count
+#[tokio::test]
+async fn test_search_pagination_api() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let client = Client::new();
+
+    let mut pages = Vec::new();
+    for page in [1, 2] {
+        let url = format!(
+            "http://{}:{}/api/search?q=tree&per_page=2&page={}&sort=downloads",
+            config.server.host, config.server.port, page
+        );
+        let response = client.get(&url).send().await.expect("Request failed");
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["x-total-count"], "5");
+
+        let body: Value = response.json().await.expect("Invalid JSON");
+        let names: Vec<String> = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(names.len(), 2);
+        pages.push(names);
+    }
+    assert!(pages[0].iter().all(|name| !pages[1].contains(name)));
+
+    let url = format!(
+        "http://{}:{}/api/search?q=tree&sort=newest",
+        config.server.host, config.server.port
+    );
+    let response = client.get(&url).send().await.expect("Request failed");
+    assert_eq!(response.status(), 400);
+}
+
+/// Test a configured API key is required everywhere except health and docs
+#[tokio::test]
+async fn test_api_key_required() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+        config.server.api_key = Some("k3y".to_string());
+    })
+    .await;
+    let client = Client::new();
+    let base = format!("http://{}:{}", config.server.host, config.server.port);
+
+    let response = client
+        .get(format!("{}/api/crates/serde", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 401);
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert!(body["error"].as_str().unwrap().contains("API key"));
+
+    for path in ["/api/crates/serde", "/metrics"] {
+        let response = client
+            .get(format!("{}{}", base, path))
+            .bearer_auth("wrong")
+            .send()
+            .await
+            .expect("Request failed");
+        assert_eq!(response.status(), 401, "{path}");
+    }
+
+    for path in ["/health", "/", "/openapi.json"] {
+        let response = client
+            .get(format!("{}{}", base, path))
+            .send()
+            .await
+            .expect("Request failed");
+        assert_eq!(response.status(), 200, "{path}");
+    }
+}
+
+/// Test requests carrying the configured API key are served
+#[tokio::test]
+async fn test_api_key_authenticated_request() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+        config.server.api_key = Some("k3y".to_string());
+    })
+    .await;
+    let client = Client::new();
+
+    let response = client
+        .get(format!(
+            "http://{}:{}/api/crates/serde",
+            config.server.host, config.server.port
+        ))
+        .bearer_auth("k3y")
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert_eq!(body["name"], "serde");
+}
+
 /// Test batch processing API
 #[tokio::test]
 async fn test_batch_processing_api() {
@@ -328,6 +871,257 @@ async fn test_batch_crates_list_api() {
     );
 }
 
+/// Test ndjson streaming of batch results
+#[tokio::test]
+async fn test_batch_ndjson_stream() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let client = Client::new();
+    let url = format!(
+        "http://{}:{}/api/batch?stream=ndjson",
+        config.server.host, config.server.port
+    );
+
+    let batch_input = serde_json::json!({
+        "crates": ["serde", "tokio", "bytes", "no-such-crate"]
+    });
+
+    let mut response = timeout(
+        Duration::from_secs(10),
+        client.post(&url).json(&batch_input).send(),
+    )
+    .await
+    .expect("Request timeout")
+    .expect("Request failed");
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.expect("Failed to read chunk") {
+        body.extend_from_slice(&chunk);
+    }
+    let lines: Vec<Value> = String::from_utf8(body)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Invalid JSON line"))
+        .collect();
+
+    assert_eq!(lines.len(), 5);
+    let (results, summary) = lines.split_at(4);
+    let mut names: Vec<&str> = results
+        .iter()
+        .map(|r| r["crate_name"].as_str().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["bytes", "no-such-crate", "serde", "tokio"]);
+
+    let summary = &summary[0]["summary"];
+    assert_eq!(summary["status"], "completed");
+    assert_eq!(summary["total_processed"], 4);
+}
+
+/// Test the yanked-check endpoint flags yanked locked versions
+#[tokio::test]
+async fn test_yanked_check_api() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let url = format!(
+        "http://{}:{}/api/yanked-check",
+        config.server.host, config.server.port
+    );
+
+    let response = Client::new()
+        .post(&url)
+        .json(&serde_json::json!({
+            "serde": "1.0.172",
+            "tokio": "1.40.0",
+            "bytes": "9.9.9"
+        }))
+        .send()
+        .await
+        .expect("Request failed");
+
+    assert_eq!(response.status(), 200);
+
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert_eq!(body["any_yanked"], true);
+
+    let results = body["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    let entry = |name: &str| results.iter().find(|r| r["crate_name"] == name).unwrap();
+    assert_eq!(entry("serde")["yanked"], true);
+    assert_eq!(entry("tokio")["yanked"], false);
+    assert_eq!(entry("tokio")["exists"], true);
+    assert_eq!(entry("bytes")["exists"], false);
+}
+
+/// Test the bulk existence endpoint reports existing and missing crates
+#[tokio::test]
+async fn test_exists_api() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let url = format!(
+        "http://{}:{}/api/exists",
+        config.server.host, config.server.port
+    );
+
+    let response = Client::new()
+        .post(&url)
+        .json(&serde_json::json!({ "crates": ["serde", "tokio", "no-such-crate"] }))
+        .send()
+        .await
+        .expect("Request failed");
+
+    assert_eq!(response.status(), 200);
+
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert_eq!(body["all_exist"], false);
+    assert_eq!(body["results"]["serde"], true);
+    assert_eq!(body["results"]["tokio"], true);
+    assert_eq!(body["results"]["no-such-crate"], false);
+}
+
+/// Test the OpenAPI document describes the API routes
+#[tokio::test]
+async fn test_openapi_spec() {
+    let (config, _handle) = start_test_server_with(|_| {}).await;
+    let url = format!(
+        "http://{}:{}/openapi.json",
+        config.server.host, config.server.port
+    );
+
+    let response = Client::new()
+        .get(&url)
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+
+    let spec: Value = response.json().await.expect("Invalid JSON");
+    assert!(spec["openapi"].as_str().unwrap().starts_with("3.0"));
+    let search = &spec["paths"]["/api/search"]["get"];
+    assert!(search.is_object());
+    assert_eq!(
+        search["responses"]["200"]["content"]["application/json"]["schema"]["items"]["$ref"],
+        "#/components/schemas/CrateSearchResult"
+    );
+    for path in ["/api/crates/{name}", "/api/batch", "/api/exists"] {
+        assert!(spec["paths"][path].is_object(), "{path}");
+    }
+}
+
+/// Test the WebSocket batch endpoint sends a message per crate, then the summary
+#[tokio::test]
+async fn test_batch_websocket_stream() {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let url = format!(
+        "ws://{}:{}/api/batch/stream",
+        config.server.host, config.server.port
+    );
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .expect("WebSocket handshake failed");
+    let batch = serde_json::json!({ "crates": ["serde", "tokio", "no-such-crate"] });
+    socket
+        .send(Message::Text(batch.to_string()))
+        .await
+        .expect("Send failed");
+
+    let mut per_crate = Vec::new();
+    let mut summary = None;
+    while let Some(message) = socket.next().await {
+        let text = match message.expect("Receive failed") {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let value: Value = serde_json::from_str(&text).expect("Invalid JSON");
+        match value.get("summary") {
+            Some(batch) => summary = Some(batch.clone()),
+            None => per_crate.push(value),
+        }
+    }
+
+    assert_eq!(per_crate.len(), 3);
+    assert!(per_crate
+        .iter()
+        .any(|r| r["crate_name"] == "no-such-crate" && r["exists"] == false));
+    let summary = summary.expect("No summary received");
+    assert_eq!(summary["total_processed"], 3);
+    assert_eq!(summary["results"].as_array().unwrap().len(), 3);
+}
+
+/// Test configured user agent and default headers are sent upstream
+#[tokio::test]
+async fn test_upstream_default_headers() {
+    let (api_url, log) = common::serve_recorded(common::registry_router());
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+        config.crates_io.user_agent = "checker-under-test/1.0".to_string();
+        config
+            .crates_io
+            .headers
+            .insert("x-deployment".to_string(), "staging".to_string());
+    })
+    .await;
+    let url = format!(
+        "http://{}:{}/api/crates/serde",
+        config.server.host, config.server.port
+    );
+
+    let response = Client::new()
+        .get(&url)
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+
+    let requests = log.headers();
+    assert!(!requests.is_empty());
+    for headers in requests {
+        assert_eq!(headers["user-agent"], "checker-under-test/1.0");
+        assert_eq!(headers["x-deployment"], "staging");
+    }
+}
+
+/// Test an unsupported stream format is rejected
+#[tokio::test]
+async fn test_batch_unsupported_stream_format() {
+    let (config, _handle) = start_test_server().await;
+    let client = Client::new();
+    let url = format!(
+        "http://{}:{}/api/batch?stream=xml",
+        config.server.host, config.server.port
+    );
+
+    let response = client
+        .post(&url)
+        .json(&serde_json::json!({ "crates": ["serde"] }))
+        .send()
+        .await
+        .expect("Request failed");
+
+    assert_eq!(response.status(), 400);
+}
+
 /// Test batch with invalid JSON
 #[tokio::test]
 async fn test_batch_invalid_json() {
@@ -544,3 +1338,151 @@ async fn test_response_times() {
     assert_eq!(response.status(), 200);
     assert!(duration < Duration::from_millis(1000)); // Should respond within 1 second
 }
+
+/// Test the server stops cleanly once its shutdown future resolves
+#[tokio::test]
+async fn test_graceful_shutdown() {
+    let mut config = AppConfig::default();
+    config.server.host = "127.0.0.1".to_string();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    config.server.port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    let server = tokio::spawn(start_server_with_shutdown(config.clone(), async {
+        let _ = stopped.await;
+    }));
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let url = format!(
+        "http://{}:{}/health",
+        config.server.host, config.server.port
+    );
+    let client = Client::new();
+    let response = client.get(&url).send().await.expect("Request failed");
+    assert_eq!(response.status(), 200);
+
+    stop.send(()).unwrap();
+    timeout(Duration::from_secs(5), server)
+        .await
+        .expect("Server did not shut down")
+        .expect("Server task panicked")
+        .expect("Server returned an error");
+    assert!(Client::new().get(&url).send().await.is_err());
+}
+
+/// Test a request outliving server.request_timeout gets a 504 and counts as failed
+#[tokio::test]
+async fn test_request_timeout_returns_gateway_timeout() {
+    let api_url = common::serve(common::delay_each(
+        common::registry_router(),
+        Duration::from_secs(3),
+    ));
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+        config.server.request_timeout = 1;
+    })
+    .await;
+    let client = Client::new();
+    let base = format!("http://{}:{}", config.server.host, config.server.port);
+
+    let response = client
+        .get(format!("{}/api/crates/serde", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 504);
+    let body: Value = response.json().await.expect("Invalid JSON");
+    assert!(body["error"].as_str().unwrap().contains("1 seconds"));
+
+    let metrics: Value = client
+        .get(format!("{}/metrics", base))
+        .send()
+        .await
+        .expect("Request failed")
+        .json()
+        .await
+        .expect("Invalid JSON");
+    assert_eq!(metrics["requests_failed"], 1);
+    assert_eq!(metrics["by_endpoint"]["/api/crates/:name"]["failed"], 1);
+}
+
+/// Test metrics break requests down by endpoint
+#[tokio::test]
+async fn test_metrics_by_endpoint() {
+    let api_url = common::mock_registry();
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let client = Client::new();
+    let base = format!("http://{}:{}", config.server.host, config.server.port);
+
+    let response = client
+        .get(format!("{}/api/search?q=serde", base))
+        .send()
+        .await
+        .expect("Request failed");
+    assert_eq!(response.status(), 200);
+    for _ in 0..2 {
+        let response = client
+            .get(format!("{}/health", base))
+            .send()
+            .await
+            .expect("Request failed");
+        assert_eq!(response.status(), 200);
+    }
+
+    let metrics: Value = client
+        .get(format!("{}/metrics", base))
+        .send()
+        .await
+        .expect("Request failed")
+        .json()
+        .await
+        .expect("Invalid JSON");
+    let by_endpoint = &metrics["by_endpoint"];
+    assert_eq!(by_endpoint["/api/search"]["requests"], 1);
+    assert_eq!(by_endpoint["/api/search"]["failed"], 0);
+    assert_eq!(by_endpoint["/health"]["requests"], 2);
+}
+
+/// Test the version endpoint reports the latest stable release when the newest is a pre-release
+#[tokio::test]
+async fn test_crate_version_latest_skips_prerelease() {
+    use axum::{routing::get, Json, Router};
+    use serde_json::json;
+
+    let serde = common::mock_crate("serde").unwrap();
+    let mut info = common::crate_json(serde);
+    info["crate"]["newest_version"] = json!("2.0.0-alpha.1");
+    let versions: Vec<_> = (0..serde.versions.len())
+        .map(|i| common::version_json(serde, i))
+        .collect();
+    let router = Router::new()
+        .route("/crates/serde", get(move || async move { Json(info) }))
+        .route(
+            "/crates/serde/versions",
+            get(move || async move { Json(json!({ "versions": versions, "meta": {} })) }),
+        );
+    let api_url = common::serve(router);
+    let (config, _handle) = start_test_server_with(|config| {
+        config.crates_io.api_url = api_url;
+    })
+    .await;
+    let client = Client::new();
+    let base = format!("http://{}:{}", config.server.host, config.server.port);
+
+    for (version, resolved) in [("latest", "1.0.210"), ("1.0.130", "1.0.130")] {
+        let body: Value = client
+            .get(format!("{}/api/crates/serde/{}", base, version))
+            .send()
+            .await
+            .expect("Request failed")
+            .json()
+            .await
+            .expect("Invalid JSON");
+        assert_eq!(body["latest_version"], "1.0.210");
+        assert_eq!(body["resolved_version"], resolved);
+    }
+}